@@ -0,0 +1,51 @@
+///
+/// Module to remove temporary folders when D-SH is interrupted.
+///
+/// Release under MIT License.
+///
+use std::fs::remove_dir_all;
+use std::process::exit;
+use std::sync::Mutex;
+
+/// Temporary folders to remove if D-SH is interrupted.
+static TMP_DIRS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Exit code when D-SH is interrupted (128 + SIGINT).
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+///
+/// Add temporary folder to remove if D-SH is interrupted.
+///
+pub fn register_tmp_dir(dir: &str) {
+    if let Ok(mut dirs) = TMP_DIRS.lock() {
+        dirs.push(String::from(dir));
+    }
+}
+
+///
+/// Temporary folder is removed or kept by command, nothing to do if D-SH is interrupted.
+///
+pub fn unregister_tmp_dir(dir: &str) {
+    if let Ok(mut dirs) = TMP_DIRS.lock() {
+        dirs.retain(|d| d != dir);
+    }
+}
+
+///
+/// On Ctrl+C, remove temporary folders and exit.
+///
+pub fn install_interrupt_handler() {
+    let result = ctrlc::set_handler(|| {
+        if let Ok(dirs) = TMP_DIRS.lock() {
+            for dir in dirs.iter() {
+                let _ = remove_dir_all(dir);
+            }
+        }
+
+        exit(INTERRUPTED_EXIT_CODE);
+    });
+
+    if result.is_err() {
+        eprintln!("Unable to handle Ctrl+C, temporary folders will not be removed if interrupted.");
+    }
+}
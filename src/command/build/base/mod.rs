@@ -1,9 +1,20 @@
+use command::build::dependencies::get_base_dependencies;
 use command::build::dockerfile::DockerfileParameter;
-use command::build::{generate_dockerfile, BuildOptions};
+use command::build::hash::{compute_hash, HASH_LABEL};
+use command::build::lock::{get_locked_from, lock_base, FROM_LABEL};
+use command::build::log::build_image_with_log;
+use command::build::{
+    display_rendered_dockerfile, generate_dockerfile, get_build_context_dir,
+    get_dockerfile_template, render_dockerfile, BuildOptions,
+};
 use command::{CommandError, CommandExitCode, CommandParameter};
 use config::dockerfile::ENTRYPOINT_FILENAME;
-use config::{create_config_filename_path, get_config_application, Config};
+use config::{
+    create_config_filename_path, get_base_package_manager, get_config_base, get_config_base_names,
+    Config, ConfigDocker, DEFAULT_BASE_NAME,
+};
 use io::InputOutputHelper;
+use serde_json::Value;
 ///
 /// Module to build base image.
 ///
@@ -14,13 +25,16 @@ use std::path::PathBuf;
 ///
 /// Generate template of entrypoint.
 ///
+/// returning content of entrypoint.
+///
 fn generate_entrypoint(
     io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
     output_dir: &String,
-) -> Result<(), CommandError> {
+) -> Result<String, CommandError> {
     let entrypoint_name;
 
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
+    match create_config_filename_path(config_dir, &ENTRYPOINT_FILENAME) {
         Some(r) => entrypoint_name = r,
         None => {
             return Err(CommandError {
@@ -54,88 +68,101 @@ fn generate_entrypoint(
         });
     }
 
-    Ok(())
+    match io_helper.file_read_at_string(&entrypoint_name) {
+        Ok(r) => Ok(r),
+        Err(err) => Err(CommandError {
+            msg: vec![
+                format!("Unable to read '{}'!", entrypoint_name),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotCopyFile,
+        }),
+    }
 }
 
 ///
-/// Get list of dependencies.
+/// Return data of template to generate dockerfile of base image.
 ///
-fn get_dependencies(
-    io_helper: &InputOutputHelper,
+/// `from` is image to start build, from config or locked digest.
+///
+pub fn get_base_dockerfile_data(
+    cmd_param: &CommandParameter,
     config: &Config,
-) -> Result<String, CommandError> {
-    let mut list_applications_file;
-
-    // 1 - We have got configuration
-    match io_helper.dir_list_file(&config.applications_dir, "*.yml") {
-        Ok(r) => list_applications_file = r,
-        Err(err) => {
-            return Err(CommandError {
-                msg: vec![
-                    format!(
-                        "Cannot read list of applications in '{}'!",
-                        &config.applications_dir
-                    ),
-                    format!("{}", err),
-                ],
-                code: CommandExitCode::CannotReadApplicationsFolder,
-            });
-        }
-    }
+    base_name: &str,
+    base: &ConfigDocker,
+    from: &str,
+) -> Value {
+    let package_manager = get_base_package_manager(base);
+    let mut dependencies = String::new();
 
-    list_applications_file.sort();
-    let mut dependencies: Vec<String> = Vec::new();
-
-    // 2 - We have list of application
-    for filename in list_applications_file {
-        match get_config_application(io_helper, &filename) {
-            Ok(config_application) => {
-                if let Some(d) = config_application.dependencies {
-                    dependencies.extend(d.iter().cloned());
-                }
-            }
-            Err(_) => {
-                // Non blocking error
-                io_helper.eprintln(&format!("Cannot read list of dependencies of '{}' application, please check right or file format!", &filename))
-            }
-        };
+    //  Get all dependencies from applications files
+    if let Ok(d) = get_base_dependencies(cmd_param.io_helper, config, base_name, &package_manager) {
+        dependencies = d.join(" ")
     }
 
-    Ok(dependencies.join(" "))
+    json!({
+        "dockerfile_from": from,
+        "dockerfile_base": true,
+        "base_name": base_name,
+        "package_manager": package_manager.name(),
+        "dependencies": dependencies
+    })
 }
 
 ///
 /// Build base image.
 ///
+/// `base_name` is name of base image, `default` for base image define by `dockerfile`.
+///
 pub fn build_base(
     cmd_param: &CommandParameter,
     tmp_dir: &PathBuf,
     options: &BuildOptions,
     config: &Config,
+    base_name: &str,
 ) -> Result<(), CommandError> {
-    let dockerfile = DockerfileParameter::new(tmp_dir);
+    let base;
 
-    if let Err(err) = generate_entrypoint(cmd_param.io_helper, &dockerfile.docker_context_path) {
-        return Err(err);
+    match get_config_base(config, base_name) {
+        Some(r) => base = r,
+        None => {
+            return Err(CommandError {
+                msg: vec![format!(
+                    "Base image '{}' not found in config file!",
+                    base_name
+                )],
+                code: CommandExitCode::BaseImageNotFound,
+            });
+        }
     }
 
-    let mut dependencies = String::new();
+    let log_name = format!("base-{}", base_name);
+    let context_dir = get_build_context_dir(cmd_param.io_helper, tmp_dir, options, &log_name)?;
+    let dockerfile = DockerfileParameter::new(&context_dir);
 
-    //  Get all dependencies from applications files
-    if let Ok(d) = get_dependencies(cmd_param.io_helper, config) {
-        dependencies = d
-    }
+    let entrypoint = generate_entrypoint(
+        cmd_param.io_helper,
+        cmd_param.config_dir,
+        &dockerfile.docker_context_path,
+    )?;
 
-    let data = json!({
-        "dockerfile_from": config.dockerfile.from.to_owned(),
-        "dockerfile_base": true,
-        "dependencies": dependencies
-    });
+    // With --locked, image is pinned by digest
+    let from = if options.locked {
+        get_locked_from(cmd_param.io_helper, cmd_param.config_dir, base_name, base)?
+    } else {
+        base.from.to_owned()
+    };
+
+    let data = get_base_dockerfile_data(cmd_param, config, base_name, base, &from);
 
     // Generate Dockerfile
-    if let Err(err) = generate_dockerfile(cmd_param.io_helper, &dockerfile.docker_filename, &data) {
-        return Err(err);
-    }
+    let dockerfile_content = generate_dockerfile(
+        cmd_param.io_helper,
+        cmd_param.config_dir,
+        get_dockerfile_template(base),
+        &dockerfile.docker_filename,
+        &data,
+    )?;
 
     // Build
     let mut build_args = Vec::new();
@@ -144,16 +171,87 @@ pub fn build_base(
         build_args.push(String::from("--no-cache"));
     }
 
-    if !cmd_param.dck_helper.build_image(
+    // Hash use image of config and not locked digest, to be compared without lock file
+    let hash_dockerfile_content = if from == base.from {
+        dockerfile_content.clone()
+    } else {
+        render_dockerfile(
+            cmd_param.io_helper,
+            cmd_param.config_dir,
+            get_dockerfile_template(base),
+            &get_base_dockerfile_data(cmd_param, config, base_name, base, &base.from),
+        )?
+    };
+
+    build_args.push(String::from("--label"));
+    build_args.push(format!(
+        "{}={}",
+        HASH_LABEL,
+        compute_hash(&[&hash_dockerfile_content, &entrypoint])
+    ));
+    build_args.push(String::from("--label"));
+    build_args.push(format!("{}={}", FROM_LABEL, from));
+
+    if options.render_only {
+        display_rendered_dockerfile(cmd_param.io_helper, options, &dockerfile_content);
+        return Ok(());
+    }
+
+    let msg = if base_name == DEFAULT_BASE_NAME {
+        String::from("Fail to build base image!")
+    } else {
+        format!("Fail to build base image '{}'!", base_name)
+    };
+
+    build_image_with_log(
+        cmd_param,
+        &log_name,
         &dockerfile.docker_filename,
         &dockerfile.docker_context_path,
-        &config.dockerfile.tag,
-        Some(&build_args),
-    ) {
-        return Err(CommandError {
-            msg: vec![String::from("Fail to build base image!")],
-            code: CommandExitCode::DockerBuildFail,
-        });
+        &base.tag,
+        &build_args,
+        msg,
+    )?;
+
+    if options.locked {
+        Ok(())
+    } else {
+        lock_base(cmd_param, base_name, base)
+    }
+}
+
+///
+/// Build many base images.
+///
+/// `base_names` is list of base image to build. If empty, build all base images.
+///
+pub fn build_bases(
+    cmd_param: &CommandParameter,
+    tmp_dir: &PathBuf,
+    options: &BuildOptions,
+    config: &Config,
+    base_names: &[String],
+) -> Result<(), CommandError> {
+    let names = if base_names.is_empty() {
+        get_config_base_names(config)
+    } else {
+        base_names.to_vec()
+    };
+
+    for name in names {
+        if options.render_only {
+            cmd_param
+                .io_helper
+                .println(&format!("Rendering base image '{}'...", name));
+        } else if name == DEFAULT_BASE_NAME {
+            cmd_param.io_helper.println("Building base image...");
+        } else {
+            cmd_param
+                .io_helper
+                .println(&format!("Building base image '{}'...", name));
+        }
+
+        build_base(cmd_param, tmp_dir, options, config, &name)?;
     }
 
     Ok(())
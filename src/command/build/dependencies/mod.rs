@@ -0,0 +1,194 @@
+///
+/// Module to compute dependencies of base image and application image.
+///
+/// Release under MIT License.
+///
+use command::{CommandError, CommandExitCode};
+use config::package_manager::PackageManager;
+use config::{
+    get_application_base_name, get_application_filenames, get_base_package_manager,
+    get_config_application, get_config_base, Config, ConfigApplication, DependenciesLayer,
+};
+use io::InputOutputHelper;
+
+///
+/// Dependencies of applications of one base image.
+///
+struct ApplicationsDependencies {
+    /// Dependencies of each application
+    dependencies: Vec<Vec<String>>,
+    /// Application files that cannot be read
+    errors: Vec<String>,
+}
+
+///
+/// Return list without duplicate item. Keep order of first occurence.
+///
+fn dedup(list: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+
+    for item in list {
+        if !result.contains(&item) {
+            result.push(item);
+        }
+    }
+
+    result
+}
+
+///
+/// Return dependencies of application for a package manager.
+///
+fn get_dependencies_of_application(
+    config_application: &ConfigApplication,
+    package_manager: &PackageManager,
+) -> Vec<String> {
+    match config_application.dependencies {
+        Some(ref d) => dedup(d.get(package_manager)),
+        None => Vec::new(),
+    }
+}
+
+///
+/// Read dependencies of all applications that use base image `base_name`.
+///
+fn get_applications_dependencies(
+    io_helper: &InputOutputHelper,
+    config: &Config,
+    base_name: &str,
+    package_manager: &PackageManager,
+) -> Result<ApplicationsDependencies, CommandError> {
+    let mut list_applications_file;
+
+    match get_application_filenames(io_helper, config) {
+        Ok(r) => list_applications_file = r,
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![
+                    format!(
+                        "Cannot read list of applications in '{}'!",
+                        &config.applications_dir
+                    ),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotReadApplicationsFolder,
+            });
+        }
+    }
+
+    list_applications_file.sort();
+
+    let mut result = ApplicationsDependencies {
+        dependencies: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for filename in list_applications_file {
+        match get_config_application(io_helper, &filename) {
+            Ok(config_application) => {
+                if get_application_base_name(&config_application) == base_name {
+                    result.dependencies.push(get_dependencies_of_application(
+                        &config_application,
+                        package_manager,
+                    ));
+                }
+            }
+            Err(_) => result.errors.push(filename),
+        };
+    }
+
+    Ok(result)
+}
+
+///
+/// Return dependencies used by at least two applications.
+///
+fn get_shared_dependencies(dependencies: &[Vec<String>]) -> Vec<String> {
+    let all = dedup(
+        dependencies
+            .iter()
+            .flat_map(|d| d.iter().cloned())
+            .collect(),
+    );
+
+    all.into_iter()
+        .filter(|dep| dependencies.iter().filter(|d| d.contains(dep)).count() > 1)
+        .collect()
+}
+
+///
+/// Return where dependencies are installed.
+///
+fn get_dependencies_layer(config: &Config) -> DependenciesLayer {
+    match config.dependencies_layer {
+        Some(ref l) => l.clone(),
+        None => DependenciesLayer::Base,
+    }
+}
+
+///
+/// Get list of dependencies to install in base image `base_name`.
+///
+/// With `dependencies_layer: app`, only dependencies shared by many applications.
+///
+pub fn get_base_dependencies(
+    io_helper: &InputOutputHelper,
+    config: &Config,
+    base_name: &str,
+    package_manager: &PackageManager,
+) -> Result<Vec<String>, CommandError> {
+    let applications =
+        get_applications_dependencies(io_helper, config, base_name, package_manager)?;
+
+    for filename in &applications.errors {
+        // Non blocking error
+        io_helper.eprintln(&format!("Cannot read list of dependencies of '{}' application, please check right or file format!", filename))
+    }
+
+    match get_dependencies_layer(config) {
+        DependenciesLayer::Base => Ok(dedup(
+            applications
+                .dependencies
+                .into_iter()
+                .flat_map(|d| d.into_iter())
+                .collect(),
+        )),
+        DependenciesLayer::App => Ok(get_shared_dependencies(&applications.dependencies)),
+    }
+}
+
+///
+/// Get list of dependencies to install in application image.
+///
+/// With `dependencies_layer: base`, always empty.
+///
+pub fn get_application_dependencies(
+    io_helper: &InputOutputHelper,
+    config: &Config,
+    config_application: &ConfigApplication,
+) -> Vec<String> {
+    if get_dependencies_layer(config) == DependenciesLayer::Base {
+        return Vec::new();
+    }
+
+    let base_name = get_application_base_name(config_application);
+
+    let package_manager = match get_config_base(config, base_name) {
+        Some(base) => get_base_package_manager(base),
+        None => return Vec::new(),
+    };
+
+    let dependencies = get_dependencies_of_application(config_application, &package_manager);
+
+    // If list of applications cannot be read, base image cannot be build too
+    let shared = match get_applications_dependencies(io_helper, config, base_name, &package_manager)
+    {
+        Ok(r) => get_shared_dependencies(&r.dependencies),
+        Err(_) => Vec::new(),
+    };
+
+    dependencies
+        .into_iter()
+        .filter(|d| !shared.contains(d))
+        .collect()
+}
@@ -0,0 +1,137 @@
+///
+/// Module to compute hash of build.
+///
+/// Release under MIT License.
+///
+use command::build::base::get_base_dockerfile_data;
+use command::build::dependencies::get_application_dependencies;
+use command::build::{get_application_dockerfile_data, get_dockerfile_template, render_dockerfile};
+use command::CommandParameter;
+use config::dockerfile::ENTRYPOINT_FILENAME;
+use config::{create_config_filename_path, get_filename, Config, ConfigApplication, ConfigDocker};
+use io::{convert_path, InputOutputHelper};
+use sha2::{Digest, Sha256};
+use std::io::Error;
+use version::get_downloaded_version;
+
+/// Label of docker image where hash of build is stored.
+pub const HASH_LABEL: &str = "d-sh.hash";
+/// Label of application image where id of base image is stored.
+pub const BASE_ID_LABEL: &str = "d-sh.base-id";
+/// Label of application image where hash of base image config is stored.
+pub const BASE_HASH_LABEL: &str = "d-sh.base-hash";
+
+///
+/// Compute hash of all parts of a build.
+///
+pub fn compute_hash(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+
+    for part in parts {
+        hasher.input(part.as_bytes());
+        // Separator to not have same hash if a part move to next part
+        hasher.input(&[0]);
+    }
+
+    format!("{:x}", hasher.result())
+}
+
+///
+/// Compute hash of application build.
+///
+/// `app_filename` is filename of application config.
+/// `dockerfile` is content of generated dockerfile.
+///
+pub fn get_application_hash(
+    io_helper: &InputOutputHelper,
+    config: &Config,
+    config_application: &ConfigApplication,
+    app_filename: &str,
+    dockerfile: &str,
+) -> Result<String, Error> {
+    let application = io_helper.file_read_at_string(app_filename)?;
+
+    // In case of package, nothing is downloaded
+    let artifact = match config_application.url {
+        Some(_) => io_helper.file_sha256(&convert_path(&get_filename(
+            &config.download_dir,
+            &config_application.download_filename,
+            None,
+        )))?,
+        None => String::new(),
+    };
+
+    Ok(compute_hash(&[dockerfile, &application, &artifact]))
+}
+
+///
+/// Compute hash of application as if it's build now.
+///
+/// returning None if hash cannot be computed (template missing, binary not downloaded...).
+///
+pub fn get_expected_application_hash(
+    cmd_param: &CommandParameter,
+    config: &Config,
+    base: &ConfigDocker,
+    config_application: &ConfigApplication,
+    app_filename: &str,
+) -> Option<String> {
+    let version = get_downloaded_version(
+        cmd_param.io_helper,
+        cmd_param.dl_helper,
+        config,
+        config_application,
+    );
+
+    let dependencies =
+        get_application_dependencies(cmd_param.io_helper, config, config_application);
+
+    let data = get_application_dockerfile_data(base, config_application, &version, &dependencies);
+
+    match render_dockerfile(
+        cmd_param.io_helper,
+        cmd_param.config_dir,
+        get_dockerfile_template(base),
+        &data,
+    ) {
+        Ok(dockerfile) => get_application_hash(
+            cmd_param.io_helper,
+            config,
+            config_application,
+            app_filename,
+            &dockerfile,
+        )
+        .ok(),
+        Err(_) => None,
+    }
+}
+
+///
+/// Compute hash of base image as if it's build now, with image of config (not locked digest).
+///
+/// returning None if hash cannot be computed (template or entrypoint missing...).
+///
+pub fn get_expected_base_hash(
+    cmd_param: &CommandParameter,
+    config: &Config,
+    base_name: &str,
+    base: &ConfigDocker,
+) -> Option<String> {
+    let entrypoint_name = create_config_filename_path(cmd_param.config_dir, &ENTRYPOINT_FILENAME)?;
+    let entrypoint = cmd_param
+        .io_helper
+        .file_read_at_string(&entrypoint_name)
+        .ok()?;
+
+    let data = get_base_dockerfile_data(cmd_param, config, base_name, base, &base.from);
+
+    match render_dockerfile(
+        cmd_param.io_helper,
+        cmd_param.config_dir,
+        get_dockerfile_template(base),
+        &data,
+    ) {
+        Ok(dockerfile) => Some(compute_hash(&[&dockerfile, &entrypoint])),
+        Err(_) => None,
+    }
+}
@@ -0,0 +1,277 @@
+///
+/// Module to pin base images and downloaded files with lock file.
+///
+/// Release under MIT License.
+///
+use command::{get_image_command_error, CommandError, CommandExitCode, CommandParameter};
+use config::{create_config_filename_path, ConfigDocker};
+use docker::get_image_repository;
+use io::InputOutputHelper;
+use std::collections::BTreeMap;
+
+/// Lock file in config folder.
+pub const LOCK_FILENAME: &str = "d-sh.lock";
+/// Label of base image where image used to build it is stored, like `ubuntu@sha256:...`.
+pub const FROM_LABEL: &str = "d-sh.from";
+
+///
+/// Base image resolved.
+///
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockBase {
+    /// Image used to build base, like `ubuntu:18.04`
+    pub from: String,
+    /// Digest of `from` image, like `sha256:...`
+    pub digest: String,
+}
+
+///
+/// Application build.
+///
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockApplication {
+    /// Downloaded file
+    pub download_filename: Option<String>,
+    /// Sha256 of downloaded file
+    pub sha256: Option<String>,
+    /// Id of image build
+    pub image_id: Option<String>,
+}
+
+///
+/// Content of lock file.
+///
+#[derive(Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Lock {
+    /// Base images by name
+    #[serde(default)]
+    pub bases: BTreeMap<String, LockBase>,
+    /// Applications by name
+    #[serde(default)]
+    pub applications: BTreeMap<String, LockApplication>,
+}
+
+///
+/// Return filename of lock file.
+///
+fn get_lock_filename(config_dir: Option<&str>) -> Result<String, CommandError> {
+    match create_config_filename_path(config_dir, LOCK_FILENAME) {
+        Some(r) => Ok(r),
+        None => Err(CommandError {
+            msg: vec![String::from("Unable to get your home dir!")],
+            code: CommandExitCode::CannotGetHomeFolder,
+        }),
+    }
+}
+
+///
+/// Read lock file. If file not exists, lock is empty.
+///
+pub fn read_lock(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+) -> Result<Lock, CommandError> {
+    let lock_filename = get_lock_filename(config_dir)?;
+
+    if !io_helper.file_exits(&lock_filename) {
+        return Ok(Lock::default());
+    }
+
+    let data = match io_helper.file_read_at_string(&lock_filename) {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Unable to read file '{}'!", lock_filename),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::LockFileWrong,
+            });
+        }
+    };
+
+    match serde_yaml::from_str(&data) {
+        Ok(r) => Ok(r),
+        Err(err) => Err(CommandError {
+            msg: vec![
+                format!("Bad format of lock file '{}'!", lock_filename),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::LockFileWrong,
+        }),
+    }
+}
+
+///
+/// Write lock file.
+///
+fn write_lock(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    lock: &Lock,
+) -> Result<(), CommandError> {
+    let lock_filename = get_lock_filename(config_dir)?;
+
+    // Serialize of struct with string keys cannot fail
+    let data = serde_yaml::to_string(lock).unwrap();
+
+    match io_helper.file_write(&lock_filename, &data) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(CommandError {
+            msg: vec![
+                format!("Unable to write file '{}'!", lock_filename),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotWriteConfigFile,
+        }),
+    }
+}
+
+///
+/// Keep digest of image used to build base image.
+///
+/// Digest is known only if `from` image comes from a registry.
+///
+pub fn lock_base(
+    cmd_param: &CommandParameter,
+    base_name: &str,
+    base: &ConfigDocker,
+) -> Result<(), CommandError> {
+    let repo_digest = match cmd_param
+        .dck_helper
+        .inspect_image(&base.from)
+        .map_err(|err| get_image_command_error(&base.from, &err))?
+    {
+        Some(image) => image.repo_digests.into_iter().next(),
+        None => None,
+    };
+
+    let digest = match repo_digest {
+        Some(ref d) => match d.rfind('@') {
+            Some(index) => String::from(&d[index + 1..]),
+            None => d.clone(),
+        },
+        None => {
+            // Non blocking error
+            cmd_param.io_helper.eprintln(&format!(
+                "Cannot find digest of '{}', base image '{}' is not locked.",
+                base.from, base_name
+            ));
+            return Ok(());
+        }
+    };
+
+    let mut lock = read_lock(cmd_param.io_helper, cmd_param.config_dir)?;
+
+    lock.bases.insert(
+        String::from(base_name),
+        LockBase {
+            from: base.from.clone(),
+            digest: digest,
+        },
+    );
+
+    write_lock(cmd_param.io_helper, cmd_param.config_dir, &lock)
+}
+
+///
+/// Return `from` image pinned by digest, like `ubuntu@sha256:...`.
+///
+pub fn get_locked_from(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    base_name: &str,
+    base: &ConfigDocker,
+) -> Result<String, CommandError> {
+    let lock = read_lock(io_helper, config_dir)?;
+
+    match lock.bases.get(base_name) {
+        Some(l) if l.from == base.from => Ok(format!(
+            "{}@{}",
+            get_image_repository(&base.from),
+            l.digest
+        )),
+        Some(_) => Err(CommandError {
+            msg: vec![format!(
+                "Image of base image '{}' changed since lock file! Please build without '--locked' to update lock file.",
+                base_name
+            )],
+            code: CommandExitCode::LockMismatch,
+        }),
+        None => Err(CommandError {
+            msg: vec![format!(
+                "Base image '{}' not found in lock file! Please build without '--locked' first.",
+                base_name
+            )],
+            code: CommandExitCode::LockMismatch,
+        }),
+    }
+}
+
+///
+/// Keep sha256 of downloaded file and image id of application.
+///
+pub fn lock_application(
+    cmd_param: &CommandParameter,
+    app: &str,
+    download_filename: Option<&str>,
+    sha256: Option<String>,
+    image_name: &str,
+) -> Result<(), CommandError> {
+    let mut lock = read_lock(cmd_param.io_helper, cmd_param.config_dir)?;
+    let image = cmd_param
+        .dck_helper
+        .inspect_image(image_name)
+        .map_err(|err| get_image_command_error(image_name, &err))?;
+
+    lock.applications.insert(
+        String::from(app),
+        LockApplication {
+            download_filename: download_filename.map(String::from),
+            sha256: sha256,
+            image_id: image.map(|image| image.id),
+        },
+    );
+
+    write_lock(cmd_param.io_helper, cmd_param.config_dir, &lock)
+}
+
+///
+/// Check if downloaded file is same as file in lock file.
+///
+pub fn check_locked_application(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    app: &str,
+    download_filename: Option<&str>,
+    sha256: Option<&str>,
+) -> Result<(), CommandError> {
+    let lock = read_lock(io_helper, config_dir)?;
+
+    let locked = match lock.applications.get(app) {
+        Some(r) => r,
+        None => {
+            return Err(CommandError {
+                msg: vec![format!(
+                    "Application '{}' not found in lock file! Please build without '--locked' first.",
+                    app
+                )],
+                code: CommandExitCode::LockMismatch,
+            });
+        }
+    };
+
+    if locked.download_filename.as_deref() != download_filename
+        || locked.sha256.as_deref() != sha256
+    {
+        return Err(CommandError {
+            msg: vec![format!(
+                "Downloaded file of '{}' is different from lock file!",
+                app
+            )],
+            code: CommandExitCode::LockMismatch,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+///
+/// Module to keep output of build in log file.
+///
+/// Release under MIT License.
+///
+use command::{get_helper_command_error, CommandError, CommandExitCode, CommandParameter};
+use config::create_config_filename_path;
+use io::InputOutputHelper;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Folder of logs in config folder.
+pub const LOGS_DIR: &str = "logs";
+/// Number of lines of log display when build fail.
+pub const LOG_EXCERPT_LINES: usize = 10;
+
+///
+/// Return timestamp in milliseconds of log filename.
+///
+fn get_timestamp() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs() * 1000 + u64::from(d.subsec_millis()),
+        Err(_) => 0,
+    }
+}
+
+///
+/// Write log of build in logs folder.
+///
+/// returning filename of log.
+///
+fn write_log(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    log_name: &str,
+    lines: &[String],
+) -> Result<String, String> {
+    let log_dir = match create_config_filename_path(config_dir, LOGS_DIR) {
+        Some(r) => r,
+        None => return Err(String::from("Unable to get your home dir!")),
+    };
+
+    if let Err(err) = io_helper.create_dir_all(&log_dir) {
+        return Err(format!("Cannot create folder '{}'! {}", log_dir, err));
+    }
+
+    let mut timestamp = get_timestamp();
+    let mut log_filename = format!("{}/{}-{}.log", log_dir, log_name, timestamp);
+
+    // Two builds in same millisecond must not overwrite log
+    while io_helper.file_exits(&log_filename) {
+        timestamp += 1;
+        log_filename = format!("{}/{}-{}.log", log_dir, log_name, timestamp);
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+
+    match io_helper.file_write(&log_filename, &content) {
+        Ok(_) => Ok(log_filename),
+        Err(err) => Err(format!("Unable to write file '{}'! {}", log_filename, err)),
+    }
+}
+
+///
+/// Build image and keep output of build in log file `<log_name>-<timestamp>.log`.
+///
+/// If build fail, last lines of log are added to `error_msg`.
+///
+pub fn build_image_with_log(
+    cmd_param: &CommandParameter,
+    log_name: &str,
+    docker_filename: &str,
+    docker_context_path: &str,
+    docker_tag: &str,
+    build_options: &Vec<String>,
+    error_msg: String,
+) -> Result<(), CommandError> {
+    let mut lines: Vec<String> = Vec::new();
+
+    let result = {
+        let mut output = |line: &str| {
+            cmd_param.io_helper.println(line);
+            lines.push(String::from(line));
+        };
+
+        cmd_param.dck_helper.build_image(
+            docker_filename,
+            docker_context_path,
+            docker_tag,
+            Some(build_options),
+            &mut output,
+        )
+    };
+
+    let log_filename = match write_log(cmd_param.io_helper, cmd_param.config_dir, log_name, &lines)
+    {
+        Ok(r) => Some(r),
+        Err(err) => {
+            // Non blocking error
+            cmd_param.io_helper.eprintln(&err);
+            None
+        }
+    };
+
+    let err = match result {
+        Ok(_) => return Ok(()),
+        Err(err) => err,
+    };
+
+    let mut error = get_helper_command_error(error_msg, &err, CommandExitCode::DockerBuildFail);
+
+    if !lines.is_empty() {
+        let start = if lines.len() > LOG_EXCERPT_LINES {
+            lines.len() - LOG_EXCERPT_LINES
+        } else {
+            0
+        };
+
+        error.msg.push(String::from("Last lines of build:"));
+        error.msg.extend(lines[start..].iter().cloned());
+    }
+
+    if let Some(f) = log_filename {
+        error.msg.push(format!("Full log of build in '{}'.", f));
+    }
+
+    Err(error)
+}
+
+///
+/// Return filename of last log of build of `log_name`.
+///
+pub fn get_last_log_filename(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    log_name: &str,
+) -> Result<Option<String>, CommandError> {
+    let log_dir = match create_config_filename_path(config_dir, LOGS_DIR) {
+        Some(r) => r,
+        None => {
+            return Err(CommandError {
+                msg: vec![String::from("Unable to get your home dir!")],
+                code: CommandExitCode::CannotGetHomeFolder,
+            });
+        }
+    };
+
+    // Log folder can be not yet created
+    let list_logs: Vec<String> = io_helper
+        .dir_list_file(&log_dir, &format!("{}-*.log", log_name))
+        .unwrap_or_default();
+
+    let prefix = format!("{}-", log_name);
+
+    // `atom-*.log` match also `atom-beta-*.log`, keep only `atom-<timestamp>.log`
+    let mut logs: Vec<(u64, String)> = list_logs
+        .into_iter()
+        .filter_map(|filename| {
+            let timestamp = match Path::new(&filename).file_stem() {
+                Some(stem) => match stem.to_str() {
+                    Some(s) if s.starts_with(&prefix) => s[prefix.len()..].parse::<u64>().ok(),
+                    _ => None,
+                },
+                None => None,
+            };
+
+            timestamp.map(|t| (t, filename))
+        })
+        .collect();
+
+    logs.sort();
+
+    Ok(logs.pop().map(|(_, filename)| filename))
+}
@@ -1,5 +1,5 @@
 ///
-/// Module to build missing image.
+/// Module to build missing or outdated image.
 ///
 /// Release under MIT License.
 ///
@@ -8,7 +8,7 @@ use command::{CommandError, CommandParameter};
 use config::Config;
 
 ///
-/// Return missing applications (applications not build).
+/// Return missing applications (applications not build or outdated).
 ///
 pub fn get_missing_application(
     cmd_param: &CommandParameter,
@@ -24,7 +24,25 @@ pub fn get_missing_application(
 
     let list_app: Vec<String> = list_applications
         .into_iter()
-        .filter(|a| !a.is_error && !a.is_build)
+        .filter(|a| !a.is_error && (!a.is_build || a.is_outdated || a.is_base_outdated))
+        .map(|a| a.name)
+        .collect();
+
+    Ok(list_app)
+}
+
+///
+/// Return applications build on an old base image.
+///
+pub fn get_base_outdated_application(
+    cmd_param: &CommandParameter,
+    config: &Config,
+) -> Result<Vec<String>, CommandError> {
+    let list_applications = get_check_application(cmd_param, config)?;
+
+    let list_app: Vec<String> = list_applications
+        .into_iter()
+        .filter(|a| !a.is_error && a.is_build && a.is_base_outdated)
         .map(|a| a.name)
         .collect();
 
@@ -1,28 +1,38 @@
 use self::all::build_all;
-use self::base::build_base;
-use self::missing::get_missing_application;
+use self::base::build_bases;
+use self::missing::{get_base_outdated_application, get_missing_application};
 use self::one::build_one_application;
-use command::{Command, CommandError, CommandExitCode, CommandParameter};
+use cleanup::unregister_tmp_dir;
+use command::check::get_outdated_base_names;
+use command::outdated::get_outdated_application;
+use command::{
+    create_command_tmp_dir, remove_command_tmp_dir, Command, CommandError, CommandExitCode,
+    CommandParameter,
+};
 use config::dockerfile::DOCKERFILE_BASE_FILENAME;
-use config::{create_config_filename_path, Config};
+use config::{
+    create_config_filename_path, get_base_package_manager, Config, ConfigApplication, ConfigDocker,
+};
 use handlebars::TemplateRenderError;
 use io::{convert_path, InputOutputHelper};
-use rand::Rng;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::env::temp_dir;
 use std::error::Error;
 ///
 /// Module to build application.
 ///
 /// Release under MIT License.
 ///
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use template::Template;
 
 mod all;
 mod base;
+mod dependencies;
 mod dockerfile;
+pub mod hash;
+mod lock;
+pub mod log;
 mod missing;
 mod one;
 #[cfg(test)]
@@ -31,56 +41,146 @@ mod tests;
 ///
 /// Option for build command.
 ///
+#[derive(Clone)]
 pub struct BuildOptions {
     /// Build all image
     all: bool,
     /// Build base image
     base: bool,
+    /// Rebuild applications build on old base image
+    cascade: bool,
     /// Force build even if exists
     force: bool,
+    /// Keep temporary folder after build
+    keep_tmp: bool,
     /// Build missing image
     missing: bool,
+    /// Build applications with new version available
+    outdated: bool,
+    /// Use base image digest and check downloaded files of lock file
+    locked: bool,
     /// Never checl if binary are update
     skip_redownload: bool,
+    /// Only generate Dockerfile and files of build, never build image
+    render_only: bool,
+    /// With `render_only`, folder where files are generated. If None, Dockerfile is displayed
+    output: Option<String>,
+    /// Version of application to build instead of version in application file
+    version: Option<String>,
 }
 
 const UNKOWN_OPTIONS_MESSAGE: &'static str =
     "d-sh build: invalid option '{}'\nTry 'd-sh build --help' for more information.\n";
 
 ///
-/// Generate a random string.
+/// Remove temporary folder, or keep it with `--keep-tmp` or if build fail.
 ///
-fn random_string() -> String {
-    let mut rng = rand::thread_rng();
-    let letter: char = rng.gen_range(b'A', b'Z') as char;
-    let number: u32 = rng.gen_range(0, 999999);
+fn clean_tmp_dir(
+    io_helper: &InputOutputHelper,
+    tmp_dir: &Path,
+    options: &BuildOptions,
+    result: Result<(), CommandError>,
+) -> Result<(), CommandError> {
+    let tmp_dir_name = tmp_dir.to_str().unwrap();
+
+    unregister_tmp_dir(tmp_dir_name);
+
+    match result {
+        Err(mut err) => {
+            err.msg
+                .push(format!("Files of build are kept in '{}'.", tmp_dir_name));
+
+            Err(err)
+        }
+        Ok(_) if options.keep_tmp => {
+            io_helper.println(&format!("Files of build are kept in '{}'.", tmp_dir_name));
 
-    format!("{}{:06}", letter, number)
+            Ok(())
+        }
+        Ok(_) => {
+            remove_command_tmp_dir(io_helper, tmp_dir_name);
+
+            Ok(())
+        }
+    }
 }
 
 ///
-/// Remove folder.
+/// Return folder where Dockerfile and files of build are generated.
 ///
-fn remove_tmp_dir(io_helper: &InputOutputHelper, tmp_dir: &PathBuf) -> CommandExitCode {
-    match io_helper.remove_dir_all(tmp_dir.to_str().unwrap()) {
-        Ok(_) => CommandExitCode::Ok,
-        Err(_) => CommandExitCode::CannotDeleteTemporaryFolder,
+/// With `--render-only --output DIR`, folder is `DIR/<name>`, else temporary folder.
+///
+pub fn get_build_context_dir(
+    io_helper: &InputOutputHelper,
+    tmp_dir: &Path,
+    options: &BuildOptions,
+    name: &str,
+) -> Result<PathBuf, CommandError> {
+    let output = match options.output {
+        Some(ref o) if options.render_only => o,
+        _ => return Ok(tmp_dir.to_path_buf()),
+    };
+
+    let mut context_dir = PathBuf::from(convert_path(output));
+    context_dir.push(name);
+
+    if let Err(err) = io_helper.create_dir_all(context_dir.to_str().unwrap()) {
+        return Err(CommandError {
+            msg: vec![
+                format!(
+                    "Cannot create '{}' folder. Please check right!",
+                    context_dir.to_str().unwrap()
+                ),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotCreateFolder,
+        });
     }
+
+    Ok(context_dir)
 }
 
 ///
-/// Generate template of dockerfile.
+/// With `--render-only`, display Dockerfile if no output folder.
 ///
-fn generate_dockerfile(
+pub fn display_rendered_dockerfile(
     io_helper: &InputOutputHelper,
-    output_filename: &str,
+    options: &BuildOptions,
+    dockerfile_content: &str,
+) {
+    if options.output.is_none() {
+        io_helper.println(dockerfile_content);
+    }
+}
+
+///
+/// Return template filename of dockerfile for a base image.
+///
+pub fn get_dockerfile_template(base: &ConfigDocker) -> &str {
+    match base.template {
+        Some(ref t) => t,
+        None => DOCKERFILE_BASE_FILENAME,
+    }
+}
+
+///
+/// Render template of dockerfile.
+///
+/// `template` is filename of template in config folder.
+///
+/// returning content of dockerfile.
+///
+pub fn render_dockerfile(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    template: &str,
     data: &Value,
-) -> Result<(), CommandError> {
+) -> Result<String, CommandError> {
     let handlebars = Template::new();
 
     let dockerfile_name;
 
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
+    match create_config_filename_path(config_dir, template) {
         Some(r) => dockerfile_name = r,
         None => {
             return Err(CommandError {
@@ -115,10 +215,8 @@ fn generate_dockerfile(
         }
     }
 
-    let content;
-
     match handlebars.render_template(&source_template, &data) {
-        Ok(r) => content = r,
+        Ok(r) => Ok(r),
         Err(err) => {
             let err_msg;
 
@@ -130,15 +228,51 @@ fn generate_dockerfile(
                 TemplateRenderError::IOError(_, msg) => err_msg = msg,
             }
 
-            return Err(CommandError {
+            Err(CommandError {
                 msg: vec![
                     String::from("Something is wrong in Dockerfile template!"),
                     err_msg,
                 ],
                 code: CommandExitCode::DockerfileTemplateInvalid,
-            });
+            })
         }
     }
+}
+
+///
+/// Return data of template to generate dockerfile of application.
+///
+/// `dependencies` is list of dependencies to install in application image.
+///
+pub fn get_application_dockerfile_data(
+    base: &ConfigDocker,
+    config_application: &ConfigApplication,
+    version: &Option<String>,
+    dependencies: &[String],
+) -> Value {
+    json!({
+        "dockerfile_from": base.tag.to_owned(),
+        "dockerfile_base": false,
+        "package_manager": get_base_package_manager(base).name(),
+        "dependencies": dependencies.join(" "),
+        "application_filename": config_application.download_filename.to_owned(),
+        "version": version
+    })
+}
+
+///
+/// Generate template of dockerfile.
+///
+/// returning content of dockerfile.
+///
+fn generate_dockerfile(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    template: &str,
+    output_filename: &str,
+    data: &Value,
+) -> Result<String, CommandError> {
+    let content = render_dockerfile(io_helper, config_dir, template, data)?;
 
     if let Err(err) = io_helper.file_write(&output_filename, &content) {
         return Err(CommandError {
@@ -150,7 +284,7 @@ fn generate_dockerfile(
         });
     }
 
-    Ok(())
+    Ok(content)
 }
 
 ///
@@ -167,7 +301,13 @@ fn build_some_application(
     let mut app_build_fail = HashMap::new();
 
     for app in applications {
-        cmd_param.io_helper.println(&format!("Building {}...", app));
+        if options.render_only {
+            cmd_param
+                .io_helper
+                .println(&format!("Rendering {}...", app));
+        } else {
+            cmd_param.io_helper.println(&format!("Building {}...", app));
+        }
 
         if let Err(err) = build_one_application(cmd_param, &tmp_dir, &options, config, app) {
             app_build_fail.insert(app, err);
@@ -178,19 +318,83 @@ fn build_some_application(
         Ok(())
     } else {
         let mut err_msg = Vec::new();
+        let mut code = CommandExitCode::DockerBuildFail;
 
         for (app, err) in app_build_fail {
             err_msg.push(format!("Build {} failed!", &app));
             err_msg.extend(err.msg);
+
+            // Daemon, permission or network error is the real cause of failure
+            match err.code {
+                CommandExitCode::DaemonUnreachable
+                | CommandExitCode::PermissionDenied
+                | CommandExitCode::NetworkError => code = err.code,
+                _ => {}
+            }
         }
 
         return Err(CommandError {
             msg: err_msg,
-            code: CommandExitCode::DockerBuildFail,
+            code: code,
         });
     }
 }
 
+///
+/// Rebuild base images build with an old config, Dockerfile or entrypoint.
+///
+fn build_outdated_bases(
+    cmd_param: &CommandParameter,
+    tmp_dir: &PathBuf,
+    options: &BuildOptions,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let base_names = get_outdated_base_names(cmd_param, config)?;
+
+    // Empty list means all base images
+    if base_names.is_empty() {
+        Ok(())
+    } else {
+        build_bases(cmd_param, tmp_dir, options, config, &base_names)
+    }
+}
+
+///
+/// Build applications with new version available, with this new version.
+///
+fn build_outdated_application(
+    cmd_param: &CommandParameter,
+    tmp_dir: &PathBuf,
+    options: &BuildOptions,
+    config: &Config,
+) -> Result<(), CommandError> {
+    let mut err_msg = Vec::new();
+
+    for app in get_outdated_application(cmd_param, config)? {
+        if !app.is_outdated {
+            continue;
+        }
+
+        let mut app_options = options.clone();
+        app_options.version = app.latest_version;
+
+        if let Err(err) =
+            build_some_application(cmd_param, tmp_dir, &app_options, config, &vec![app.name])
+        {
+            err_msg.extend(err.msg);
+        }
+    }
+
+    if err_msg.is_empty() {
+        Ok(())
+    } else {
+        Err(CommandError {
+            msg: err_msg,
+            code: CommandExitCode::DockerBuildFail,
+        })
+    }
+}
+
 ///
 /// Function to implement build D-SH command.
 ///
@@ -202,26 +406,23 @@ fn build(cmd_param: CommandParameter) -> Result<(), CommandError> {
     let mut options: BuildOptions = BuildOptions {
         all: false,
         base: false,
+        cascade: false,
         force: false,
+        keep_tmp: false,
         missing: false,
+        outdated: false,
+        locked: false,
         skip_redownload: false,
+        render_only: false,
+        output: None,
+        version: None,
     };
 
-    // Just get options form command line
-    let opts: Vec<&String> = cmd_param
-        .args
-        .iter()
-        .filter(|a| a.starts_with("-"))
-        .collect();
     // Get applications list from command line
-    let applications: Vec<String> = cmd_param
-        .args
-        .iter()
-        .filter(|a| !a.starts_with("-"))
-        .map(|a| a.clone())
-        .collect();
-
-    for argument in opts {
+    let mut applications: Vec<String> = Vec::new();
+    let mut args = cmd_param.args.iter();
+
+    while let Some(argument) = args.next() {
         match argument.as_ref() {
             "-h" | "--help" => {
                 cmd_param.io_helper.println(cmd_param.command.usage);
@@ -229,55 +430,88 @@ fn build(cmd_param: CommandParameter) -> Result<(), CommandError> {
             }
             "-a" | "--all" => options.all = true,
             "-b" | "--base" => options.base = true,
+            "-c" | "--cascade" => options.cascade = true,
             "-f" | "--force" => options.force = true,
+            "-k" | "--keep-tmp" => options.keep_tmp = true,
             "-m" | "--missing" => options.missing = true,
+            "-u" | "--outdated" => options.outdated = true,
+            "-l" | "--locked" => options.locked = true,
             "-s" | "--skip-redownload" => options.skip_redownload = true,
-            other => {
+            "-r" | "--render-only" => options.render_only = true,
+            "-o" | "--output" => match args.next() {
+                Some(o) => options.output = Some(o.clone()),
+                None => {
+                    return Err(CommandError {
+                        msg: vec![String::from("d-sh build: option '--output' need a folder")],
+                        code: CommandExitCode::BadArgument,
+                    });
+                }
+            },
+            "-v" | "--version" => match args.next() {
+                Some(v) => options.version = Some(v.clone()),
+                None => {
+                    return Err(CommandError {
+                        msg: vec![String::from(
+                            "d-sh build: option '--version' need a version",
+                        )],
+                        code: CommandExitCode::BadArgument,
+                    });
+                }
+            },
+            other if other.starts_with("-") => {
                 return Err(CommandError {
                     msg: vec![UNKOWN_OPTIONS_MESSAGE.replace("{}", other)],
                     code: CommandExitCode::UnknowOption,
                 });
             }
+            app => applications.push(String::from(app)),
         }
     }
 
-    let config = cmd_param.config.unwrap();
-
-    // 1 - Create tmp folder for build
-    let mut tmp_dir;
-
-    match &config.tmp_dir {
-        Some(t) => tmp_dir = PathBuf::from(convert_path(t)),
-        None => tmp_dir = temp_dir(),
-    }
-
-    tmp_dir.push(random_string());
-
-    if let Err(err) = cmd_param
-        .io_helper
-        .create_dir_all(tmp_dir.to_str().unwrap())
+    // Version is for one application
+    if options.version.is_some()
+        && (options.all
+            || options.base
+            || options.missing
+            || options.outdated
+            || applications.len() != 1)
     {
         return Err(CommandError {
-            msg: vec![
-                format!(
-                    "Cannot create '{}' folder. Please check right!",
-                    &tmp_dir.to_str().unwrap()
-                ),
-                format!("{}", err),
-            ],
-            code: CommandExitCode::CannotCreateFolder,
+            msg: vec![String::from(
+                "d-sh build: option '--version' can be used only with one application",
+            )],
+            code: CommandExitCode::BadArgument,
         });
     }
 
+    let config = cmd_param.config.unwrap();
+
+    // 1 - Create tmp folder for build, removed if D-SH is interrupted
+    let tmp_dir = PathBuf::from(create_command_tmp_dir(cmd_param.io_helper, &config)?);
+
     let result;
 
     if options.base {
-        cmd_param.io_helper.println("Building base image...");
-        result = build_base(&cmd_param, &tmp_dir, &options, &config);
+        // With --base, arguments are name of base images
+        result = match build_bases(&cmd_param, &tmp_dir, &options, &config, &applications) {
+            Ok(_) if options.cascade => match get_base_outdated_application(&cmd_param, &config) {
+                Ok(list_applications) => build_some_application(
+                    &cmd_param,
+                    &tmp_dir,
+                    &options,
+                    &config,
+                    &list_applications,
+                ),
+                Err(err) => Err(err),
+            },
+            r => r,
+        };
     } else if options.all {
         result = build_all(&cmd_param, &options, &config, &tmp_dir);
     } else if options.missing {
-        match get_missing_application(&cmd_param, &config) {
+        match build_outdated_bases(&cmd_param, &tmp_dir, &options, &config)
+            .and_then(|_| get_missing_application(&cmd_param, &config))
+        {
             Ok(list_applications) => {
                 result = build_some_application(
                     &cmd_param,
@@ -289,14 +523,15 @@ fn build(cmd_param: CommandParameter) -> Result<(), CommandError> {
             }
             Err(err) => result = Err(err),
         }
+    } else if options.outdated {
+        // New release of application, base images build with an old config stay for --missing
+        result = build_outdated_application(&cmd_param, &tmp_dir, &options, &config);
     } else {
         result = build_some_application(&cmd_param, &tmp_dir, &options, &config, &applications);
     }
 
     // Remove tmp folder
-    remove_tmp_dir(cmd_param.io_helper, &tmp_dir);
-
-    result
+    clean_tmp_dir(cmd_param.io_helper, &tmp_dir, &options, result)
 }
 
 ///
@@ -315,15 +550,23 @@ pub const BUILD: Command = Command {
     /// `check` command have no help.
     usage: "
     Usage:	d-sh build [OPTIONS] PROGRAM1 PROGRAM2 ...
+    	d-sh build --base [BASE1 BASE2 ...]
 
     Build an image for a program
 
     Options:
       -a, --all                Build all image of program
-      -b, --base               Build base image
+      -b, --base               Build all base images or only base images given
+      -c, --cascade            With --base, rebuild images of applications build on old base image
       -f, --force              Remove existing image before build
+      -k, --keep-tmp           Keep temporary folder of build (always kept if build fail)
       -m, --missing            Build only missing image
-      -s, --skip-redownload    If binary is present, don't check if new version is available",
+      -u, --outdated           Build only applications with new version available
+      -l, --locked             Use base image and downloaded files of lock file
+      -s, --skip-redownload    If binary is present, don't check if new version is available
+      -r, --render-only        Generate Dockerfile and files of build without build image
+      -o, --output DIR         With --render-only, generate files in DIR instead of display Dockerfile
+      -v, --version VERSION    Build application with this version instead of version in application file",
     need_config_file: true,
     exec_cmd: build,
 };
@@ -1,16 +1,40 @@
 use command::build::base::build_base;
+use command::build::dependencies::get_application_dependencies;
 use command::build::dockerfile::DockerfileParameter;
-use command::build::{generate_dockerfile, BuildOptions};
-use command::{CommandError, CommandExitCode, CommandParameter};
-use config::{get_config_application, get_filename, Config, ConfigApplication};
+use command::build::hash::{
+    get_application_hash, get_expected_base_hash, BASE_HASH_LABEL, BASE_ID_LABEL, HASH_LABEL,
+};
+use command::build::lock::{
+    check_locked_application, get_locked_from, lock_application, FROM_LABEL,
+};
+use command::build::log::build_image_with_log;
+use command::build::{
+    display_rendered_dockerfile, generate_dockerfile, get_application_dockerfile_data,
+    get_build_context_dir, get_dockerfile_template, render_dockerfile, BuildOptions,
+};
+use command::{
+    get_helper_command_error, get_image_command_error, CommandError, CommandExitCode,
+    CommandParameter,
+};
+use config::{
+    get_application_base_name, get_application_filename, get_config_application_with_version,
+    get_config_base, get_filename, Config, ConfigApplication, ConfigDocker,
+};
+use docker::remote::get_application_endpoint;
 use io::convert_path;
-use std::error::Error;
+use registry::{is_registry_cache_enabled, pull_cache_image};
 ///
 /// Module to build one image.
 ///
 /// Release under MIT License.
 ///
+use serde_json::Value;
+use std::error::Error;
 use std::path::PathBuf;
+use version::{get_downloaded_version, VERSION_LABEL};
+
+/// Rendered in place of dependencies to know if template of application install them.
+const DEPENDENCIES_MARKER: &str = "d-sh-dependencies-marker";
 
 ///
 /// Download file with curl.
@@ -32,32 +56,90 @@ fn download_file(
 
     let url = config_application.url.as_ref().unwrap();
 
-    if cmd_param.io_helper.file_exits(&app_dwn_filename) {
-        // Download file with curl
-        if !options.skip_redownload
-            && !config_application.skip_redownload.unwrap_or(false)
-            && !cmd_param
-                .dl_helper
-                .download_if_update(url, &app_dwn_filename)
+    let result = if cmd_param.io_helper.file_exits(&app_dwn_filename) {
+        // With --locked, file is checked with lock file and not replaced by a new version
+        if options.skip_redownload
+            || options.locked
+            || config_application.skip_redownload.unwrap_or(false)
         {
-            return Err(CommandError {
-                msg: vec![format!("Unable to download application '{}'!", app)],
-                code: CommandExitCode::UnableDownloadApplication,
-            });
+            return Ok(());
         }
+
+        // Download file with curl
+        cmd_param
+            .dl_helper
+            .download_if_update(url, &app_dwn_filename)
     } else {
         // Download file with curl
-        if !cmd_param.dl_helper.download(url, &app_dwn_filename) {
-            return Err(CommandError {
-                msg: vec![format!("Unable to download application '{}'!", app)],
-                code: CommandExitCode::UnableDownloadApplication,
-            });
-        }
+        cmd_param.dl_helper.download(url, &app_dwn_filename)
+    };
+
+    if let Err(err) = result {
+        return Err(get_helper_command_error(
+            format!("Unable to download application '{}'!", app),
+            &err,
+            CommandExitCode::UnableDownloadApplication,
+        ));
     }
 
     Ok(())
 }
 
+///
+/// Return downloaded filename and its sha256, None if application have no url.
+///
+fn get_download_sha256<'a>(
+    cmd_param: &CommandParameter,
+    app: &str,
+    config_application: &'a ConfigApplication,
+    config: &Config,
+) -> Result<(Option<&'a str>, Option<String>), CommandError> {
+    if config_application.url.is_none() {
+        return Ok((None, None));
+    }
+
+    let app_dwn_filename = convert_path(&get_filename(
+        &config.download_dir,
+        &config_application.download_filename,
+        None,
+    ));
+
+    match cmd_param.io_helper.file_sha256(&app_dwn_filename) {
+        Ok(r) => Ok((Some(config_application.download_filename.as_str()), Some(r))),
+        Err(err) => Err(CommandError {
+            msg: vec![
+                format!("Unable to compute hash of application '{}'!", app),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotComputeHash,
+        }),
+    }
+}
+
+///
+/// Keep application in lock file, except with `--locked`.
+///
+fn update_lock(
+    cmd_param: &CommandParameter,
+    options: &BuildOptions,
+    app: &str,
+    download_filename: Option<&str>,
+    download_sha256: Option<String>,
+    image_name: &str,
+) -> Result<(), CommandError> {
+    if options.locked {
+        return Ok(());
+    }
+
+    lock_application(
+        cmd_param,
+        app,
+        download_filename,
+        download_sha256,
+        image_name,
+    )
+}
+
 ///
 /// Check if base image is builded.
 ///
@@ -66,16 +148,58 @@ fn check_base_image_builded(
     config: &Config,
     tmp_dir: &PathBuf,
     options: &BuildOptions,
+    base_name: &str,
+    base: &ConfigDocker,
 ) -> Result<(), CommandError> {
-    let images = cmd_param.dck_helper.list_image(&config.dockerfile.tag);
+    // With --locked, base image must be build from locked digest
+    if options.locked {
+        let from = get_locked_from(cmd_param.io_helper, cmd_param.config_dir, base_name, base)?;
+        let build_from = cmd_param
+            .dck_helper
+            .inspect_image(&base.tag)
+            .map_err(|err| get_image_command_error(&base.tag, &err))?
+            .and_then(|image| image.labels.get(FROM_LABEL).cloned());
+
+        if build_from != Some(from) {
+            return build_base(cmd_param, tmp_dir, options, config, base_name);
+        }
+
+        return Ok(());
+    }
+
+    let images = cmd_param
+        .dck_helper
+        .list_image(&base.tag)
+        .map_err(|err| get_image_command_error(&base.tag, &err))?;
 
     if images.len() == 0 {
-        return build_base(cmd_param, tmp_dir, options, config);
+        return build_base(cmd_param, tmp_dir, options, config, base_name);
     }
 
     Ok(())
 }
 
+///
+/// Return true if template install `{{dependencies}}` in application image.
+///
+fn is_template_install_dependencies(
+    cmd_param: &CommandParameter,
+    base: &ConfigDocker,
+    data: &Value,
+) -> Result<bool, CommandError> {
+    let mut data = data.clone();
+    data["dependencies"] = Value::from(DEPENDENCIES_MARKER);
+
+    let content = render_dockerfile(
+        cmd_param.io_helper,
+        cmd_param.config_dir,
+        get_dockerfile_template(base),
+        &data,
+    )?;
+
+    Ok(content.contains(DEPENDENCIES_MARKER))
+}
+
 ///
 /// Build one application.
 ///
@@ -88,17 +212,15 @@ pub fn build_one_application(
     config: &Config,
     app: &str,
 ) -> Result<(), CommandError> {
-    if let Err(err) = check_base_image_builded(cmd_param, config, tmp_dir, options) {
-        return Err(err);
-    }
-
-    let app_filename = convert_path(&get_filename(&config.applications_dir, app, Some(&".yml")));
-
-    let dockerfile = DockerfileParameter::new(tmp_dir);
+    let app_filename = convert_path(&get_application_filename(cmd_param.io_helper, config, app));
 
     let config_application;
 
-    match get_config_application(cmd_param.io_helper, &app_filename) {
+    match get_config_application_with_version(
+        cmd_param.io_helper,
+        &app_filename,
+        options.version.as_deref(),
+    ) {
         Ok(r) => config_application = r,
         Err(err) => {
             return Err(CommandError {
@@ -112,21 +234,156 @@ pub fn build_one_application(
         }
     }
 
+    // Application can be build on its own Docker daemon, with its base image
+    let app_helper = get_application_endpoint(&config_application)
+        .map(|endpoint| cmd_param.dck_helper.with_endpoint(&endpoint));
+    let app_param;
+    let cmd_param = match app_helper {
+        Some(ref h) => {
+            app_param = CommandParameter {
+                dck_helper: &**h,
+                ..*cmd_param
+            };
+            &app_param
+        }
+        None => cmd_param,
+    };
+
+    let base_name = get_application_base_name(&config_application);
+    let base;
+
+    match get_config_base(config, base_name) {
+        Some(r) => base = r,
+        None => {
+            return Err(CommandError {
+                msg: vec![format!(
+                    "Base image '{}' of application '{}' not found in config file!",
+                    base_name, app
+                )],
+                code: CommandExitCode::BaseImageNotFound,
+            });
+        }
+    }
+
+    // With --render-only, base image is never build
+    if !options.render_only {
+        check_base_image_builded(cmd_param, config, tmp_dir, options, base_name, base)?;
+    }
+
+    let context_dir = get_build_context_dir(cmd_param.io_helper, tmp_dir, options, app)?;
+    let dockerfile = DockerfileParameter::new(&context_dir);
+
     if config_application.url.is_some() {
         if let Err(err) = download_file(cmd_param, app, &config_application, options, config) {
             return Err(err);
         }
     }
 
+    let (download_filename, download_sha256) =
+        get_download_sha256(cmd_param, app, &config_application, config)?;
+
+    if options.locked {
+        check_locked_application(
+            cmd_param.io_helper,
+            cmd_param.config_dir,
+            app,
+            download_filename,
+            download_sha256.as_deref(),
+        )?;
+    }
+
+    let version = get_downloaded_version(
+        cmd_param.io_helper,
+        cmd_param.dl_helper,
+        config,
+        &config_application,
+    );
+
+    let dependencies =
+        get_application_dependencies(cmd_param.io_helper, config, &config_application);
+
     // Now build
-    let data = json!({
-        "dockerfile_from": config.dockerfile.tag.to_owned(),
-        "dockerfile_base": false,
-        "application_filename": config_application.download_filename.to_owned()
-    });
+    let data = get_application_dockerfile_data(base, &config_application, &version, &dependencies);
+
+    let dockerfile_content = generate_dockerfile(
+        cmd_param.io_helper,
+        cmd_param.config_dir,
+        get_dockerfile_template(base),
+        &dockerfile.docker_filename,
+        &data,
+    )?;
+
+    // Template created before `dependencies_layer` install dependencies only in base image
+    if !dependencies.is_empty() && !is_template_install_dependencies(cmd_param, base, &data)? {
+        return Err(CommandError {
+            msg: vec![
+                format!(
+                    "Template '{}' doesn't install dependencies of application '{}'!",
+                    get_dockerfile_template(base),
+                    app
+                ),
+                String::from("Add '{{dependencies}}' in application part of template, like default template, or use 'dependencies_layer: base'."),
+            ],
+            code: CommandExitCode::DockerfileTemplateInvalid,
+        });
+    }
+
+    let hash;
+
+    match get_application_hash(
+        cmd_param.io_helper,
+        config,
+        &config_application,
+        &app_filename,
+        &dockerfile_content,
+    ) {
+        Ok(r) => hash = r,
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Unable to compute hash of application '{}'!", app),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotComputeHash,
+            });
+        }
+    }
+
+    // Keep base image id to know if application must be rebuild when base image change
+    let base_id = cmd_param
+        .dck_helper
+        .inspect_image(&base.tag)
+        .map_err(|err| get_image_command_error(&base.tag, &err))?
+        .map(|i| i.id);
+    // Keep hash of base image config to share image with registry, id is different on each host
+    let base_hash = get_expected_base_hash(cmd_param, config, base_name, base);
+
+    // Image with same hash can be already build by someone else
+    if !options.render_only && !options.force {
+        if let Some(ref registry) = config.registry {
+            if is_registry_cache_enabled(registry)
+                && pull_cache_image(
+                    cmd_param.dck_helper,
+                    registry,
+                    &config_application.image_name,
+                    &hash,
+                    base_hash.as_deref(),
+                )
+            {
+                cmd_param
+                    .io_helper
+                    .println(&format!("Image of {} pulled from registry.", app));
 
-    if let Err(err) = generate_dockerfile(cmd_param.io_helper, &dockerfile.docker_filename, &data) {
-        return Err(err);
+                return update_lock(
+                    cmd_param,
+                    options,
+                    app,
+                    download_filename,
+                    download_sha256,
+                    &config_application.image_name,
+                );
+            }
+        }
     }
 
     // Copy file to temporary folder
@@ -165,17 +422,45 @@ pub fn build_one_application(
         build_args.push(String::from("--no-cache"));
     }
 
-    if !cmd_param.dck_helper.build_image(
+    if let Some(v) = version {
+        build_args.push(String::from("--label"));
+        build_args.push(format!("{}={}", VERSION_LABEL, v));
+    }
+
+    build_args.push(String::from("--label"));
+    build_args.push(format!("{}={}", HASH_LABEL, hash));
+
+    if let Some(id) = base_id {
+        build_args.push(String::from("--label"));
+        build_args.push(format!("{}={}", BASE_ID_LABEL, id));
+    }
+
+    if let Some(h) = base_hash {
+        build_args.push(String::from("--label"));
+        build_args.push(format!("{}={}", BASE_HASH_LABEL, h));
+    }
+
+    if options.render_only {
+        display_rendered_dockerfile(cmd_param.io_helper, options, &dockerfile_content);
+        return Ok(());
+    }
+
+    build_image_with_log(
+        cmd_param,
+        app,
         &dockerfile.docker_filename,
         &dockerfile.docker_context_path,
         &config_application.image_name,
-        Some(&build_args),
-    ) {
-        return Err(CommandError {
-            msg: vec![format!("Cannot build application {}!", app)],
-            code: CommandExitCode::DockerBuildFail,
-        });
-    }
+        &build_args,
+        format!("Cannot build application {}!", app),
+    )?;
 
-    return Ok(());
+    update_lock(
+        cmd_param,
+        options,
+        app,
+        download_filename,
+        download_sha256,
+        &config_application.image_name,
+    )
 }
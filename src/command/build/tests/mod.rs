@@ -3,14 +3,26 @@
 ///
 /// Release under MIT License.
 ///
+use super::hash::HASH_LABEL;
+use super::lock::{Lock, LOCK_FILENAME};
+use super::log::get_last_log_filename;
 use super::{build, BUILD, UNKOWN_OPTIONS_MESSAGE};
-use command::tests::{test_result_err, test_result_ok};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
 use command::{CommandExitCode, CommandParameter};
-use config::dockerfile::{DOCKERFILE_BASE_FILENAME, ENTRYPOINT, ENTRYPOINT_FILENAME};
-use config::{create_config_filename_path, Config, ConfigDocker};
+use config::dockerfile::{
+    DOCKERFILE_BASE, DOCKERFILE_BASE_FILENAME, ENTRYPOINT, ENTRYPOINT_FILENAME,
+};
+use config::package_manager::PackageManager;
+use config::{
+    create_config_filename_path, Config, ConfigApplicationsDir, ConfigDocker, ConfigRegistry,
+    DependenciesLayer,
+};
 use docker::tests::TestContainerHelper;
+use docker::ContainerHelper;
 use download::tests::TestDownloadHelper;
+use io::tests::found_item;
 use io::tests::TestInputOutputHelper;
+use std::collections::HashMap;
 
 #[test]
 fn build_display_help() {
@@ -23,12 +35,21 @@ fn build_display_help() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     let cmd_param = CommandParameter {
@@ -38,13 +59,14 @@ fn build_display_help() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(build(cmd_param));
 
     let stdout = io_helper.stdout.borrow();
 
-    assert_eq!(stdout.get(0).unwrap(), "\n    Usage:	d-sh build [OPTIONS] PROGRAM1 PROGRAM2 ...\n\n    Build an image for a program\n\n    Options:\n      -a, --all                Build all image of program\n      -b, --base               Build base image\n      -f, --force              Remove existing image before build\n      -m, --missing            Build only missing image\n      -s, --skip-redownload    If binary is present, don't check if new version is available");
+    assert_eq!(stdout.get(0).unwrap(), "\n    Usage:	d-sh build [OPTIONS] PROGRAM1 PROGRAM2 ...\n    	d-sh build --base [BASE1 BASE2 ...]\n\n    Build an image for a program\n\n    Options:\n      -a, --all                Build all image of program\n      -b, --base               Build all base images or only base images given\n      -c, --cascade            With --base, rebuild images of applications build on old base image\n      -f, --force              Remove existing image before build\n      -k, --keep-tmp           Keep temporary folder of build (always kept if build fail)\n      -m, --missing            Build only missing image\n      -u, --outdated           Build only applications with new version available\n      -l, --locked             Use base image and downloaded files of lock file\n      -s, --skip-redownload    If binary is present, don't check if new version is available\n      -r, --render-only        Generate Dockerfile and files of build without build image\n      -o, --output DIR         With --render-only, generate files in DIR instead of display Dockerfile\n      -v, --version VERSION    Build application with this version instead of version in application file");
 }
 
 #[test]
@@ -58,12 +80,21 @@ fn build_unknow_option() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     let cmd_param = CommandParameter {
@@ -73,6 +104,7 @@ fn build_unknow_option() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(build(cmd_param), CommandExitCode::UnknowOption);
@@ -88,7 +120,7 @@ fn build_base_with_args(args: &[String], dck_helper: &TestContainerHelper, confi
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
 
     // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
         Some(cfg_file) => {
             // Create file
             io_helper.files.borrow_mut().insert(
@@ -102,7 +134,7 @@ fn build_base_with_args(args: &[String], dck_helper: &TestContainerHelper, confi
     };
 
     // Create dockerfile
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
         Some(cfg_file) => {
             // Create file
             io_helper
@@ -124,6 +156,7 @@ fn build_base_with_args(args: &[String], dck_helper: &TestContainerHelper, confi
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(build(cmd_param));
@@ -172,12 +205,21 @@ fn build_base_short_option() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     build_base_with_args(&[String::from("-b")], dck_helper, config);
@@ -189,12 +231,21 @@ fn build_base_short_option_with_force() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     build_base_with_args(
@@ -210,49 +261,71 @@ fn build_base_short_option_with_force() {
 }
 
 #[test]
-fn build_base_short_option_dockerfile_template_not_found() {
+fn build_base_with_cascade() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
     let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
 
-    let args = [String::from("-b")];
+    let args = [String::from("-b"), String::from("--cascade")];
 
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
-    let dockerfile_name;
+    // Atom is build on old base image, filezilla is not build
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.base-id"), String::from("sha256:tutu"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), labels);
 
     // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            dockerfile_name = cfg_file;
-        }
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => io_helper
+            .files
+            .borrow_mut()
+            .insert(cfg_file, String::from("{{dockerfile_from}}")),
         None => panic!("Unable to create dockerfile for test"),
     };
 
     // Create entrypoint
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper
-                .files
-                .borrow_mut()
-                .insert(cfg_file, String::from(ENTRYPOINT))
-        }
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
+        Some(cfg_file) => io_helper
+            .files
+            .borrow_mut()
+            .insert(cfg_file, String::from(ENTRYPOINT)),
         None => panic!("Unable to create entrypoint for test"),
     };
 
-    // Add application with dependencies
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\""));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\""));
 
     let cmd_param = CommandParameter {
         command: &BUILD,
@@ -261,468 +334,519 @@ fn build_base_short_option_dockerfile_template_not_found() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    let stderr = test_result_err(build(cmd_param), CommandExitCode::TemplateNotFound);
+    test_result_ok(build(cmd_param));
+
+    let builds = dck_helper.builds.borrow();
+
+    assert_eq!(builds.len(), 2);
+    assert_eq!(builds.get(0).unwrap().tag, "tutu");
+    assert_eq!(builds.get(1).unwrap().tag, "run-atom:latest");
+
+    // New base image id is stored in application image
+    let labels = dck_helper.labels.borrow();
 
     assert_eq!(
-        stderr.get(0).unwrap(),
-        &format!(
-            "The file '{}' doesn't exits. Please run 'init' command first.",
-            dockerfile_name
-        )
+        labels
+            .get("run-atom:latest")
+            .unwrap()
+            .get("d-sh.base-id")
+            .unwrap(),
+        "sha256:tutu-0"
     );
 }
 
-#[test]
-fn build_base_short_option_entrypoint_template_not_found() {
-    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
-    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+fn create_config_with_bases() -> Config {
+    let mut bases = HashMap::new();
 
-    let args = [String::from("-b")];
+    bases.insert(
+        String::from("fedora"),
+        ConfigDocker {
+            from: String::from("fedora:29"),
+            tag: String::from("d-fedora-image"),
+            template: Some(String::from("Dockerfile-fedora.hbs")),
+            package_manager: Some(PackageManager::Dnf),
+        },
+    );
 
-    // Create configuration file
-    let config = Config {
+    Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: Some(bases),
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
-    };
-
-    let entrypoint_name;
-
-    // Create dockerfile
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
-        Some(cfg_file) => {
-            entrypoint_name = cfg_file;
-        }
-        None => panic!("Unable to create entrypoint for test"),
-    };
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    }
+}
 
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper
+fn create_files_for_bases(io_helper: &TestInputOutputHelper) {
+    for (template, content) in &[
+        (
+            DOCKERFILE_BASE_FILENAME,
+            "{{dockerfile_from}} {{#if dockerfile_base}}ubuntu {{dependencies}}{{/if}}",
+        ),
+        (
+            "Dockerfile-fedora.hbs",
+            "{{dockerfile_from}} {{#if dockerfile_base}}fedora {{dependencies}}{{else}}app{{/if}}",
+        ),
+        (ENTRYPOINT_FILENAME, ENTRYPOINT),
+    ] {
+        match create_config_filename_path(Some(TEST_CONFIG_DIR), template) {
+            Some(cfg_file) => io_helper
                 .files
                 .borrow_mut()
-                .insert(cfg_file, String::from("hello man!"))
-        }
-        None => panic!("Unable to create dockerfile for test"),
-    };
+                .insert(cfg_file, String::from(*content)),
+            None => panic!("Unable to create template for test"),
+        };
+    }
 
-    // Add application with dependencies
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.rpm\"\nurl: \"titi\"\nbase: fedora\ndependencies:\n  - d3"));
+}
+
+#[test]
+fn build_all_bases() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
+
+    create_files_for_bases(io_helper);
 
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &args,
+        args: &[String::from("-b")],
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    let stderr = test_result_err(build(cmd_param), CommandExitCode::TemplateNotFound);
+    test_result_ok(build(cmd_param));
 
-    assert_eq!(
-        stderr.get(0).unwrap(),
-        &format!(
-            "The file '{}' doesn't exits. Please run 'init' command first.",
-            entrypoint_name
-        )
-    );
+    let builds = dck_helper.builds.borrow();
+
+    assert_eq!(builds.len(), 2);
+    assert_eq!(builds.get(0).unwrap().tag, "tutu");
+    assert_eq!(builds.get(1).unwrap().tag, "d-fedora-image");
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Building base image...");
+    assert_eq!(stdout.get(1).unwrap(), "Building base image 'fedora'...");
 }
 
 #[test]
-fn build_base_short_option_application_file_format_bad() {
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+fn build_one_base_by_name() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
-    let args = [String::from("-b")];
-
-    // Create configuration file
-    let config = Config {
-        download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
-        dockerfile: ConfigDocker {
-            from: String::from("tata"),
-            tag: String::from("tutu"),
-        },
-        tmp_dir: None,
-    };
-
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper.files.borrow_mut().insert(
-                cfg_file,
-                String::from("{{dockerfile_from}} {{#if dockerfile_base}}coucou{{/if}}"),
-            )
-        }
-        None => panic!("Unable to create dockerfile for test"),
-    };
-
-    // Create dockerfile
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper
-                .files
-                .borrow_mut()
-                .insert(cfg_file, String::from(ENTRYPOINT))
-        }
-        None => panic!("Unable to create entrypoint for test"),
-    };
+    let config = create_config_with_bases();
 
-    // Add application with dependencies
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper.files.borrow_mut().insert(
-        String::from("app/filezilla.yml"),
-        String::from("---\nimage_name: \"run-filezilla:latest"),
-    );
+    create_files_for_bases(io_helper);
 
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &args,
+        args: &[String::from("-b"), String::from("fedora")],
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(build(cmd_param));
 
-    // Check if temporary folder was created and remove
-    let f = io_helper.files_delete.borrow();
+    let builds = dck_helper.builds.borrow();
 
-    let mut not_found_dockerfile = true;
-    let mut not_found_entrypoint = true;
-    let mut generate_dockerfile = String::new();
+    assert_eq!(builds.len(), 1);
+    assert_eq!(builds.get(0).unwrap().tag, "d-fedora-image");
+
+    // Only dependencies of applications that use this base
+    let f = io_helper.files_delete.borrow();
 
     for filename in f.keys() {
         if filename.ends_with("/Dockerfile") {
-            not_found_dockerfile = false;
-            generate_dockerfile = filename.to_string();
-            assert_eq!(f.get(filename).unwrap(), "tata coucou");
-        } else if filename.ends_with("/entrypoint.sh") {
-            not_found_entrypoint = false;
-            assert_eq!(f.get(filename).unwrap(), ENTRYPOINT);
+            assert_eq!(f.get(filename).unwrap(), "fedora:29 fedora d3");
         }
     }
+}
 
-    if not_found_dockerfile {
-        panic!("The temporary Dockerfile in '/tmp/xxx/' folder not found!");
-    }
+#[test]
+fn build_unknown_base() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
 
-    if not_found_entrypoint {
-        panic!("The temporary entrypoint.sh in '/tmp/xxx/' folder not found!");
-    }
+    create_files_for_bases(io_helper);
 
-    let builds = dck_helper.builds.borrow();
-    let base_build = builds.get(0).unwrap();
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("-b"), String::from("arch")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    assert_eq!(base_build.tag, "tutu");
-    assert_eq!(generate_dockerfile, base_build.dockerfile_name);
-    assert!(generate_dockerfile.starts_with(&base_build.base_dir));
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::BaseImageNotFound);
 
-    let stdout = io_helper.stdout.borrow();
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "Base image 'arch' not found in config file!"
+    );
+}
 
-    assert_eq!(stdout.get(0).unwrap(), "Building base image...");
+#[test]
+fn build_application_with_named_base() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
 
-    let stderr = io_helper.stderr.borrow();
+    create_files_for_bases(io_helper);
 
-    assert_eq!(stderr.get(0).unwrap(), "Cannot read list of dependencies of 'app/filezilla.yml' application, please check right or file format!");
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("filezilla")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    // Base image is missing, so build it before
+    let builds = dck_helper.builds.borrow();
+
+    assert_eq!(builds.len(), 2);
+    assert_eq!(builds.get(0).unwrap().tag, "d-fedora-image");
+    assert_eq!(builds.get(1).unwrap().tag, "run-filezilla:latest");
+    assert!(builds
+        .get(1)
+        .unwrap()
+        .build_options
+        .contains(&String::from("d-sh.base-id=sha256:d-fedora-image-0")));
 }
 
 #[test]
-fn build_base_short_option_dockerfile_template_format_bad() {
+fn build_missing_rebuild_outdated_base() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
     let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
+
+    create_files_for_bases(io_helper);
+    io_helper.files.borrow_mut().remove("app/atom.yml");
+
+    for _ in 0..2 {
+        let cmd_param = CommandParameter {
+            command: &BUILD,
+            args: &[String::from("-m")],
+            io_helper: io_helper,
+            dck_helper: dck_helper,
+            dl_helper: dl_helper,
+            config: Some(&config),
+            config_dir: Some(TEST_CONFIG_DIR),
+        };
+
+        test_result_ok(build(cmd_param));
+    }
 
-    let args = [String::from("-b")];
+    // Second time, base image and application are up to date
+    assert_eq!(dck_helper.builds.borrow().len(), 2);
 
-    // Create configuration file
-    let config = Config {
-        download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
-        dockerfile: ConfigDocker {
-            from: String::from("tata"),
-            tag: String::from("tutu"),
-        },
-        tmp_dir: None,
+    // Template of base image change
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), "Dockerfile-fedora.hbs") {
+        Some(cfg_file) => io_helper.files.borrow_mut().insert(
+            cfg_file,
+            String::from("{{dockerfile_from}} {{#if dockerfile_base}}fedora v2 {{dependencies}}{{else}}app{{/if}}"),
+        ),
+        None => panic!("Unable to create template for test"),
     };
 
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper
-                .files
-                .borrow_mut()
-                .insert(cfg_file, String::from("{{#if base}}dffdfd{{/iG}}"))
-        }
-        None => panic!("Unable to create dockerfile for test"),
+    // No new release of application, --outdated build nothing
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("--outdated")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    // Create entrypoint
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper
-                .files
-                .borrow_mut()
-                .insert(cfg_file, String::from(ENTRYPOINT))
-        }
-        None => panic!("Unable to create entrypoint for test"),
-    };
+    test_result_ok(build(cmd_param));
 
-    // Add application with dependencies
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
+    assert_eq!(dck_helper.builds.borrow().len(), 2);
 
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &args,
+        args: &[String::from("-m")],
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerfileTemplateInvalid);
+    test_result_ok(build(cmd_param));
 
-    assert_eq!(
-        stderr.get(0).unwrap(),
-        "Something is wrong in Dockerfile template!"
-    );
-    assert_eq!(stderr.get(1).unwrap(), "wrong name of closing helper");
+    let builds = dck_helper.builds.borrow();
+
+    assert_eq!(builds.len(), 4);
+    assert_eq!(builds.get(2).unwrap().tag, "d-fedora-image");
+    assert_eq!(builds.get(3).unwrap().tag, "run-filezilla:latest");
 }
 
 #[test]
-fn build_base_short_option_docker_build_fail() {
+fn build_base_with_dependencies_by_package_manager() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
     let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
 
-    let args = [String::from("-b")];
+    create_files_for_bases(io_helper);
 
-    // Create configuration file
-    let config = Config {
-        download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
-        dockerfile: ConfigDocker {
-            from: String::from("tata"),
-            tag: String::from("tutu"),
-        },
-        tmp_dir: None,
-    };
-
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper
-                .files
-                .borrow_mut()
-                .insert(cfg_file, String::from("{{#if base}}dffdfd{{/if}}"))
-        }
-        None => panic!("Unable to create dockerfile for test"),
-    };
-
-    // Create entrypoint
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper
-                .files
-                .borrow_mut()
-                .insert(cfg_file, String::from(ENTRYPOINT))
-        }
-        None => panic!("Unable to create entrypoint for test"),
+    // Use default template for fedora base
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), "Dockerfile-fedora.hbs") {
+        Some(cfg_file) => io_helper
+            .files
+            .borrow_mut()
+            .insert(cfg_file, String::from(DOCKERFILE_BASE)),
+        None => panic!("Unable to create template for test"),
     };
 
-    // Add application with dependencies
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
-
-    dck_helper
-        .builds_error
-        .borrow_mut()
-        .insert(config.dockerfile.tag.clone(), true);
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.rpm\"\nurl: \"titi\"\nbase: fedora\ndependencies:\n  apt:\n    - libgtk-3-0\n  dnf:\n    - gtk3"));
 
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &args,
+        args: &[String::from("-b"), String::from("fedora")],
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
+    test_result_ok(build(cmd_param));
 
-    assert_eq!(stderr.get(0).unwrap(), "Fail to build base image!");
+    let f = io_helper.files_delete.borrow();
+    let mut found = false;
+
+    for filename in f.keys() {
+        if filename.ends_with("/Dockerfile") {
+            let dockerfile = f.get(filename).unwrap();
+
+            assert!(dockerfile
+                .contains("RUN dnf install -y \\\n          gtk3 && \\\n        dnf clean all"));
+            assert!(!dockerfile.contains("apt-get"));
+            found = true;
+        }
+    }
+
+    assert!(found);
+}
+
+fn get_dockerfiles(io_helper: &TestInputOutputHelper) -> Vec<String> {
+    io_helper
+        .files_delete
+        .borrow()
+        .iter()
+        .filter(|(k, _)| k.ends_with("/Dockerfile"))
+        .map(|(_, v)| v.clone())
+        .collect()
 }
 
 #[test]
-fn build_base_short_option_with_specified_tmp_dir() {
+fn build_application_with_dependencies_in_application_layer() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
-    // Create configuration file
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
-        tmp_dir: Some(String::from("~/.tmp/")),
+        bases: None,
+        dependencies_layer: Some(DependenciesLayer::App),
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
-    build_base_with_args(&[String::from("-b")], dck_helper, config);
-}
+    for (template, content) in &[
+        (
+            DOCKERFILE_BASE_FILENAME,
+            "{{dockerfile_from}} {{#if dockerfile_base}}base{{else}}app{{/if}} {{dependencies}}",
+        ),
+        (ENTRYPOINT_FILENAME, ENTRYPOINT),
+    ] {
+        match create_config_filename_path(Some(TEST_CONFIG_DIR), template) {
+            Some(cfg_file) => io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from(*content)),
+            None => panic!("Unable to create template for test"),
+        };
+    }
 
-fn build_with_args(
-    args: &[String],
-    io_helper: &TestInputOutputHelper,
-    dck_helper: &TestContainerHelper,
-    dl_helper: &TestDownloadHelper,
-    config: Config,
-) -> String {
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
-        }
-        None => panic!("Unable to create dockerfile for test"),
-    };
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2\n  - d1"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\"\ndependencies:\n  - d2\n  - d3"));
 
+    // Shared dependency in base image
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &args,
+        args: &[String::from("-b")],
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(build(cmd_param));
 
-    // Check if temporary folder was created and remove
-    let f = io_helper.files_delete.borrow();
-
-    let mut not_found_dockerfile = true;
-    let mut generate_dockerfile = String::new();
+    found_item(&get_dockerfiles(io_helper), "tata base d2");
 
-    for filename in f.keys() {
-        if filename.ends_with("/Dockerfile") {
-            not_found_dockerfile = false;
-            generate_dockerfile = filename.to_string();
-            assert_eq!(f.get(filename).unwrap(), "tutu bisous atom.deb");
-        }
-    }
+    // Other dependencies in application image
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    if not_found_dockerfile {
-        panic!("The temporary Dockerfile in '/tmp/xxx/' folder not found!");
-    }
+    test_result_ok(build(cmd_param));
 
-    generate_dockerfile
+    found_item(&get_dockerfiles(io_helper), "tutu app d1");
 }
 
 #[test]
-fn build_application() {
+fn build_application_with_dependencies_and_old_template() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
 
-    // Create configuration file
-    let config = Config {
-        download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
-        dockerfile: ConfigDocker {
-            from: String::from("tata"),
-            tag: String::from("tutu"),
-        },
-        tmp_dir: None,
-    };
+    let mut config = create_config_with_bases();
+    config.dependencies_layer = Some(DependenciesLayer::App);
+
+    create_files_for_bases(io_helper);
 
+    // Template of old version install dependencies only in base image
     dck_helper
         .images
         .borrow_mut()
         .push(config.dockerfile.tag.clone());
 
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
-    // Add application with dependencies
-
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
 
-    let generate_dockerfile = build_with_args(
-        &[String::from("atom")],
-        io_helper,
-        dck_helper,
-        dl_helper,
-        config,
+    assert_eq!(
+        stderr.get(1).unwrap(),
+        "Template 'Dockerfile.hbs' doesn't install dependencies of application 'atom'!"
     );
+    assert!(dck_helper.builds.borrow().is_empty());
 
-    let downloads = dl_helper.dl.borrow();
-    let dl = downloads.get(0).unwrap();
-
-    assert_eq!(dl.output_filename, "dwn/atom.deb");
-    assert_eq!(dl.url, "toto");
-
-    let builds = dck_helper.builds.borrow();
-    let atom_build = builds.get(0).unwrap();
+    // Name of dependency in Dockerfile is not enough
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - tutu"));
 
-    assert_eq!(atom_build.tag, "run-atom:latest");
-    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
-    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    let stdout = io_helper.stdout.borrow();
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
 
-    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+    assert_eq!(
+        stderr.get(1).unwrap(),
+        "Template 'Dockerfile.hbs' doesn't install dependencies of application 'atom'!"
+    );
+    assert!(dck_helper.builds.borrow().is_empty());
 }
 
 #[test]
-fn build_application_docker_build_fail() {
+fn build_base_short_option_dockerfile_template_not_found() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
     let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
 
-    let args = [String::from("atom")];
+    let args = [String::from("-b")];
 
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
+    let dockerfile_name;
+
     // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
         Some(cfg_file) => {
-            // Create file
-            io_helper
-                .files
-                .borrow_mut()
-                .insert(cfg_file, String::from("{{#if base}}dffdfd{{/if}}"))
+            dockerfile_name = cfg_file;
         }
         None => panic!("Unable to create dockerfile for test"),
     };
 
     // Create entrypoint
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
         Some(cfg_file) => {
             // Create file
             io_helper
@@ -737,11 +861,6 @@ fn build_application_docker_build_fail() {
     io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
     io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
 
-    dck_helper
-        .builds_error
-        .borrow_mut()
-        .insert(String::from("run-atom:latest"), true);
-
     let cmd_param = CommandParameter {
         command: &BUILD,
         args: &args,
@@ -749,104 +868,1262 @@ fn build_application_docker_build_fail() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::TemplateNotFound);
 
-    assert_eq!(stderr.get(0).unwrap(), "Build atom failed!");
-    assert_eq!(stderr.get(1).unwrap(), "Cannot build application atom!");
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        &format!(
+            "The file '{}' doesn't exits. Please run 'init' command first.",
+            dockerfile_name
+        )
+    );
 }
 
 #[test]
-fn build_application_download_fail() {
+fn build_base_short_option_entrypoint_template_not_found() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("-b")];
 
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
-    dck_helper
-        .images
-        .borrow_mut()
-        .push(config.dockerfile.tag.clone());
-
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
-
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
-
-    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let entrypoint_name;
 
-    dl_helper
-        .urls_error
-        .borrow_mut()
-        .insert(String::from("toto"), true);
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
+        Some(cfg_file) => {
+            entrypoint_name = cfg_file;
+        }
+        None => panic!("Unable to create entrypoint for test"),
+    };
 
     // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
         Some(cfg_file) => {
             // Create file
-            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from("hello man!"))
         }
         None => panic!("Unable to create dockerfile for test"),
     };
 
+    // Add application with dependencies
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
+
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &[String::from("atom")],
+        args: &args,
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::TemplateNotFound);
 
-    assert_eq!(stderr.get(0).unwrap(), "Build atom failed!");
     assert_eq!(
-        stderr.get(1).unwrap(),
-        "Unable to download application 'atom'!"
+        stderr.get(0).unwrap(),
+        &format!(
+            "The file '{}' doesn't exits. Please run 'init' command first.",
+            entrypoint_name
+        )
     );
 }
 
 #[test]
-fn build_application_download_already_done() {
+fn build_base_short_option_application_file_format_bad() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let args = [String::from("-b")];
 
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
-    dck_helper
-        .images
-        .borrow_mut()
-        .push(config.dockerfile.tag.clone());
-
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
-
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper
-        .files
-        .borrow_mut()
-        .insert(String::from("dwn/atom.deb"), String::from("Go, go, go !"));
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(
+                cfg_file,
+                String::from("{{dockerfile_from}} {{#if dockerfile_base}}coucou{{/if}}"),
+            )
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from(ENTRYPOINT))
+        }
+        None => panic!("Unable to create entrypoint for test"),
+    };
+
+    // Add application with dependencies
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(
+        String::from("app/filezilla.yml"),
+        String::from("---\nimage_name: \"run-filezilla:latest"),
+    );
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    // Check if temporary folder was created and remove
+    let f = io_helper.files_delete.borrow();
+
+    let mut not_found_dockerfile = true;
+    let mut not_found_entrypoint = true;
+    let mut generate_dockerfile = String::new();
+
+    for filename in f.keys() {
+        if filename.ends_with("/Dockerfile") {
+            not_found_dockerfile = false;
+            generate_dockerfile = filename.to_string();
+            assert_eq!(f.get(filename).unwrap(), "tata coucou");
+        } else if filename.ends_with("/entrypoint.sh") {
+            not_found_entrypoint = false;
+            assert_eq!(f.get(filename).unwrap(), ENTRYPOINT);
+        }
+    }
+
+    if not_found_dockerfile {
+        panic!("The temporary Dockerfile in '/tmp/xxx/' folder not found!");
+    }
+
+    if not_found_entrypoint {
+        panic!("The temporary entrypoint.sh in '/tmp/xxx/' folder not found!");
+    }
+
+    let builds = dck_helper.builds.borrow();
+    let base_build = builds.get(0).unwrap();
+
+    assert_eq!(base_build.tag, "tutu");
+    assert_eq!(generate_dockerfile, base_build.dockerfile_name);
+    assert!(generate_dockerfile.starts_with(&base_build.base_dir));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Building base image...");
+
+    let stderr = io_helper.stderr.borrow();
+
+    assert_eq!(stderr.get(0).unwrap(), "Cannot read list of dependencies of 'app/filezilla.yml' application, please check right or file format!");
+}
+
+#[test]
+fn build_base_short_option_dockerfile_template_format_bad() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("-b")];
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from("{{#if base}}dffdfd{{/iG}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    // Create entrypoint
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from(ENTRYPOINT))
+        }
+        None => panic!("Unable to create entrypoint for test"),
+    };
+
+    // Add application with dependencies
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerfileTemplateInvalid);
+
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "Something is wrong in Dockerfile template!"
+    );
+    assert_eq!(stderr.get(1).unwrap(), "wrong name of closing helper");
+}
+
+#[test]
+fn build_base_short_option_docker_build_fail() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("-b")];
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from("{{#if base}}dffdfd{{/if}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    // Create entrypoint
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from(ENTRYPOINT))
+        }
+        None => panic!("Unable to create entrypoint for test"),
+    };
+
+    // Add application with dependencies
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
+
+    dck_helper
+        .builds_error
+        .borrow_mut()
+        .insert(config.dockerfile.tag.clone(), true);
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
+
+    assert_eq!(stderr.get(0).unwrap(), "Fail to build base image!");
+}
+
+#[test]
+fn build_base_short_option_with_specified_tmp_dir() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: Some(String::from("~/.tmp/")),
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    build_base_with_args(&[String::from("-b")], dck_helper, config);
+}
+
+fn build_with_args(
+    args: &[String],
+    io_helper: &TestInputOutputHelper,
+    dck_helper: &TestContainerHelper,
+    dl_helper: &TestDownloadHelper,
+    config: Config,
+) -> String {
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    // Check if temporary folder was created and remove
+    let f = io_helper.files_delete.borrow();
+
+    let mut not_found_dockerfile = true;
+    let mut generate_dockerfile = String::new();
+
+    for filename in f.keys() {
+        if filename.ends_with("/Dockerfile") {
+            not_found_dockerfile = false;
+            generate_dockerfile = filename.to_string();
+            assert_eq!(f.get(filename).unwrap(), "tutu bisous atom.deb");
+        }
+    }
+
+    if not_found_dockerfile {
+        panic!("The temporary Dockerfile in '/tmp/xxx/' folder not found!");
+    }
+
+    generate_dockerfile
+}
+
+#[test]
+fn build_application() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    // Add application with dependencies
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let generate_dockerfile = build_with_args(
+        &[String::from("atom")],
+        io_helper,
+        dck_helper,
+        dl_helper,
+        config,
+    );
+
+    let downloads = dl_helper.dl.borrow();
+    let dl = downloads.get(0).unwrap();
+
+    assert_eq!(dl.output_filename, "dwn/atom.deb");
+    assert_eq!(dl.url, "toto");
+
+    let builds = dck_helper.builds.borrow();
+    let atom_build = builds.get(0).unwrap();
+
+    assert_eq!(atom_build.tag, "run-atom:latest");
+    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
+    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+}
+
+#[test]
+fn build_application_docker_build_fail() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("atom")];
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from("{{#if base}}dffdfd{{/if}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    // Create entrypoint
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from(ENTRYPOINT))
+        }
+        None => panic!("Unable to create entrypoint for test"),
+    };
+
+    // Add application with dependencies
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndependencies:\n  - d3"));
+
+    dck_helper
+        .builds_error
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), true);
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
+
+    assert_eq!(stderr.get(0).unwrap(), "Build atom failed!");
+    assert_eq!(stderr.get(1).unwrap(), "Cannot build application atom!");
+}
+
+#[test]
+fn build_application_fail_with_log() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => io_helper
+            .files
+            .borrow_mut()
+            .insert(cfg_file, String::from("{{dockerfile_from}}")),
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\""));
+
+    let output: Vec<String> = (1..16).map(|i| format!("line {}", i)).collect();
+
+    dck_helper
+        .builds_output
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), output.clone());
+    dck_helper
+        .builds_error
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), true);
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
+
+    // Output of build is display
+    found_item(&io_helper.stdout.borrow(), "line 1");
+
+    // Only last lines in error
+    assert_eq!(stderr.get(0).unwrap(), "Build atom failed!");
+    assert_eq!(stderr.get(1).unwrap(), "Cannot build application atom!");
+    // Cause of error
+    assert_eq!(
+        stderr.get(2).unwrap(),
+        "The command returned a non-zero code: 1 (exit code 1)"
+    );
+    assert_eq!(stderr.get(3).unwrap(), "Last lines of build:");
+    assert_eq!(stderr.get(4).unwrap(), "line 6");
+    assert_eq!(stderr.get(13).unwrap(), "line 15");
+
+    let log_filename = match get_last_log_filename(io_helper, Some(TEST_CONFIG_DIR), "atom") {
+        Ok(Some(r)) => r,
+        _ => panic!("Log of build not found"),
+    };
+
+    assert_eq!(
+        stderr.get(14).unwrap(),
+        &format!("Full log of build in '{}'.", log_filename)
+    );
+
+    // Full log is kept
+    let mut content = output.join("\n");
+    content.push('\n');
+
+    assert_eq!(
+        io_helper.files.borrow().get(&log_filename).unwrap(),
+        &content
+    );
+
+    // Temporary folder is kept
+    let tmp_dir = io_helper.tmp_dirs.borrow().get(0).unwrap().clone();
+
+    assert_eq!(
+        stderr.get(15).unwrap(),
+        &format!("Files of build are kept in '{}'.", tmp_dir)
+    );
+    assert!(io_helper
+        .files
+        .borrow()
+        .contains_key(&format!("{}/Dockerfile", tmp_dir)));
+    assert!(io_helper.files_delete.borrow().is_empty());
+}
+
+#[test]
+fn build_application_twice_keep_each_log() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
+
+    create_files_for_render(io_helper);
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    for _ in 0..2 {
+        let cmd_param = CommandParameter {
+            command: &BUILD,
+            args: &[String::from("-f"), String::from("atom")],
+            io_helper: io_helper,
+            dck_helper: dck_helper,
+            dl_helper: dl_helper,
+            config: Some(&config),
+            config_dir: Some(TEST_CONFIG_DIR),
+        };
+
+        test_result_ok(build(cmd_param));
+    }
+
+    let log_dir = create_config_filename_path(Some(TEST_CONFIG_DIR), "logs").unwrap();
+    let logs: Vec<String> = io_helper
+        .files
+        .borrow()
+        .keys()
+        .filter(|f| f.starts_with(&format!("{}/atom-", log_dir)))
+        .cloned()
+        .collect();
+
+    assert_eq!(logs.len(), 2);
+}
+
+#[test]
+fn build_application_keep_tmp() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
+
+    create_files_for_render(io_helper);
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("--keep-tmp"), String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    let tmp_dir = io_helper.tmp_dirs.borrow().get(0).unwrap().clone();
+
+    found_item(
+        &io_helper.stdout.borrow(),
+        &format!("Files of build are kept in '{}'.", tmp_dir),
+    );
+    assert_eq!(
+        io_helper
+            .files
+            .borrow()
+            .get(&format!("{}/Dockerfile", tmp_dir))
+            .unwrap(),
+        "FROM tutu atom.deb"
+    );
+    assert!(io_helper.files_delete.borrow().is_empty());
+}
+
+#[test]
+fn build_cannot_create_tmp_dir() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let mut config = create_config_with_bases();
+
+    config.tmp_dir = Some(String::from("tmp"));
+
+    io_helper
+        .files_error
+        .borrow_mut()
+        .insert(String::from("tmp"), true);
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::CannotCreateFolder);
+
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "Cannot create temporary folder in 'tmp'. Please check right!"
+    );
+}
+
+fn create_files_for_render(io_helper: &TestInputOutputHelper) {
+    for (template, content) in &[
+        (DOCKERFILE_BASE_FILENAME, "FROM {{dockerfile_from}}{{#unless dockerfile_base}} {{application_filename}}{{/unless}}"),
+        (ENTRYPOINT_FILENAME, ENTRYPOINT),
+    ] {
+        match create_config_filename_path(Some(TEST_CONFIG_DIR), template) {
+            Some(cfg_file) => io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from(*content)),
+            None => panic!("Unable to create template for test"),
+        };
+    }
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\""));
+}
+
+#[test]
+fn build_render_only_display_dockerfile() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
+
+    create_files_for_render(io_helper);
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom"), String::from("--render-only")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    // Base image is missing but nothing is build
+    assert_eq!(dck_helper.builds.borrow().len(), 0);
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Rendering atom...");
+    assert_eq!(stdout.get(1).unwrap(), "FROM tutu atom.deb");
+}
+
+#[test]
+fn build_render_only_in_output_folder() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
+
+    create_files_for_render(io_helper);
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[
+            String::from("-r"),
+            String::from("-o"),
+            String::from("out"),
+            String::from("atom"),
+        ],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[
+            String::from("--base"),
+            String::from("--render-only"),
+            String::from("--output"),
+            String::from("out"),
+            String::from("default"),
+        ],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    assert_eq!(dck_helper.builds.borrow().len(), 0);
+
+    // Files of build are kept in output folder
+    let files = io_helper.files.borrow();
+
+    assert_eq!(
+        files.get("out/atom/Dockerfile").unwrap(),
+        "FROM tutu atom.deb"
+    );
+    assert_eq!(files.get("out/atom/atom.deb").unwrap(), "toto");
+    assert_eq!(
+        files.get("out/base-default/Dockerfile").unwrap(),
+        "FROM tata"
+    );
+    assert_eq!(
+        files.get("out/base-default/entrypoint.sh").unwrap(),
+        ENTRYPOINT
+    );
+}
+
+#[test]
+fn build_output_without_folder() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("-r"), String::from("-o")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::BadArgument);
+
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "d-sh build: option '--output' need a folder"
+    );
+}
+
+#[test]
+fn build_application_download_fail() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    dl_helper
+        .urls_error
+        .borrow_mut()
+        .insert(String::from("toto"), true);
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::NetworkError);
+
+    assert_eq!(stderr.get(0).unwrap(), "Build atom failed!");
+    assert_eq!(
+        stderr.get(1).unwrap(),
+        "Unable to download application 'atom'!"
+    );
+    assert_eq!(
+        stderr.get(2).unwrap(),
+        "Network error: Unable to download 'toto'"
+    );
+}
+
+#[test]
+fn build_application_download_already_done() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom.deb"), String::from("Go, go, go !"));
+
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let generate_dockerfile = build_with_args(
+        &[String::from("atom")],
+        io_helper,
+        dck_helper,
+        dl_helper,
+        config,
+    );
+
+    let downloads = dl_helper.dl.borrow();
+    let dl = downloads.get(0).unwrap();
+
+    assert_eq!(dl.output_filename, "dwn/atom.deb");
+    assert_eq!(dl.url, "toto");
+    assert_eq!(dl.update, false);
+
+    assert_eq!(
+        io_helper.files.borrow().get("dwn/atom.deb").unwrap(),
+        "Go, go, go !"
+    );
+
+    let builds = dck_helper.builds.borrow();
+    let atom_build = builds.get(0).unwrap();
+
+    assert_eq!(atom_build.tag, "run-atom:latest");
+    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
+    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+}
+
+#[test]
+fn build_application_skip_download() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom.deb"), String::from("Go, go, go !"));
+
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    dl_helper
+        .update_dl_files
+        .borrow_mut()
+        .insert(String::from("dwn/atom.deb"), true);
+
+    let generate_dockerfile = build_with_args(
+        &[String::from("-s"), String::from("atom")],
+        io_helper,
+        dck_helper,
+        dl_helper,
+        config,
+    );
+
+    let downloads = dl_helper.dl.borrow();
+
+    assert_eq!(downloads.len(), 0);
+
+    assert_eq!(
+        io_helper.files.borrow().get("dwn/atom.deb").unwrap(),
+        "Go, go, go !"
+    );
+
+    let builds = dck_helper.builds.borrow();
+    let atom_build = builds.get(0).unwrap();
+
+    assert_eq!(atom_build.tag, "run-atom:latest");
+    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
+    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+}
+
+#[test]
+fn build_application_skip_download_by_app_conf() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2\nskip_redownload: true"));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom.deb"), String::from("Go, go, go !"));
+
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    dl_helper
+        .update_dl_files
+        .borrow_mut()
+        .insert(String::from("dwn/atom.deb"), true);
+
+    let generate_dockerfile = build_with_args(
+        &[String::from("atom")],
+        io_helper,
+        dck_helper,
+        dl_helper,
+        config,
+    );
+
+    let downloads = dl_helper.dl.borrow();
+
+    assert_eq!(downloads.len(), 0);
+
+    assert_eq!(
+        io_helper.files.borrow().get("dwn/atom.deb").unwrap(),
+        "Go, go, go !"
+    );
+
+    let builds = dck_helper.builds.borrow();
+    let atom_build = builds.get(0).unwrap();
+
+    assert_eq!(atom_build.tag, "run-atom:latest");
+    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
+    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+}
+
+#[test]
+fn build_application_with_force() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
 
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
 
     let generate_dockerfile = build_with_args(
-        &[String::from("atom")],
+        &[String::from("-f"), String::from("atom")],
         io_helper,
         dck_helper,
         dl_helper,
@@ -858,38 +2135,301 @@ fn build_application_download_already_done() {
 
     assert_eq!(dl.output_filename, "dwn/atom.deb");
     assert_eq!(dl.url, "toto");
-    assert_eq!(dl.update, false);
 
+    let builds = dck_helper.builds.borrow();
+    let atom_build = builds.get(0).unwrap();
+
+    assert_eq!(atom_build.tag, "run-atom:latest");
+    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
+    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+
+    let builds = dck_helper.builds.borrow();
+    let app_build = builds.get(0).unwrap();
+
+    assert_eq!(app_build.build_options.get(0).unwrap(), "--no-cache");
+}
+
+#[test]
+fn build_application_with_version() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("atom")];
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\""));
+
+    dl_helper
+        .package_versions
+        .borrow_mut()
+        .insert(String::from("dwn/atom.deb"), String::from("1.34.0"));
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from("{{dockerfile_from}} {{version}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    let f = io_helper.files_delete.borrow();
+
+    for filename in f.keys() {
+        if filename.ends_with("/Dockerfile") {
+            assert_eq!(f.get(filename).unwrap(), "tutu 1.34.0");
+        }
+    }
+
+    let builds = dck_helper.builds.borrow();
+    let atom_build = builds.get(0).unwrap();
+
+    assert_eq!(atom_build.build_options.get(0).unwrap(), "--label");
     assert_eq!(
-        io_helper.files.borrow().get("dwn/atom.deb").unwrap(),
-        "Go, go, go !"
+        atom_build.build_options.get(1).unwrap(),
+        "d-sh.version=1.34.0"
     );
+}
+
+#[test]
+fn build_many_applications() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\"\ndependencies:\n  - d1\n  - d2"));
+
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom"), String::from("filezilla")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    let downloads = dl_helper.dl.borrow();
+    let dl = downloads.get(0).unwrap();
+
+    assert_eq!(dl.output_filename, "dwn/atom.deb");
+    assert_eq!(dl.url, "toto");
+
+    let dl = downloads.get(1).unwrap();
+
+    assert_eq!(dl.output_filename, "dwn/filezilla.deb");
+    assert_eq!(dl.url, "titi");
 
     let builds = dck_helper.builds.borrow();
     let atom_build = builds.get(0).unwrap();
 
     assert_eq!(atom_build.tag, "run-atom:latest");
-    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
-    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+
+    let filezilla_build = builds.get(1).unwrap();
+
+    assert_eq!(filezilla_build.tag, "run-filezilla:latest");
 
     let stdout = io_helper.stdout.borrow();
 
     assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+    assert_eq!(stdout.get(1).unwrap(), "Building filezilla...");
 }
 
 #[test]
-fn build_application_skip_download() {
+fn build_all_applications() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    // Add application with dependencies
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\"\ndependencies:\n  - d1\n  - d2"));
+
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("-a")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    let downloads = dl_helper.dl.borrow();
+    let dl = downloads.get(0).unwrap();
+
+    assert_eq!(dl.output_filename, "dwn/atom.deb");
+    assert_eq!(dl.url, "toto");
+
+    let dl = downloads.get(1).unwrap();
+
+    assert_eq!(dl.output_filename, "dwn/filezilla.deb");
+    assert_eq!(dl.url, "titi");
+
+    let builds = dck_helper.builds.borrow();
+    let atom_build = builds.get(0).unwrap();
+
+    assert_eq!(atom_build.tag, "run-atom:latest");
+
+    let filezilla_build = builds.get(1).unwrap();
+
+    assert_eq!(filezilla_build.tag, "run-filezilla:latest");
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+    assert_eq!(stdout.get(1).unwrap(), "Building filezilla...");
+}
+
+#[test]
+fn build_missings_applications() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
 
+    // Create list of images returned by docker
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     dck_helper
@@ -900,60 +2440,72 @@ fn build_application_skip_download() {
     let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
 
     io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper
-        .files
-        .borrow_mut()
-        .insert(String::from("dwn/atom.deb"), String::from("Go, go, go !"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\"\ndependencies:\n  - d1\n  - d2"));
 
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
 
-    dl_helper
-        .update_dl_files
-        .borrow_mut()
-        .insert(String::from("dwn/atom.deb"), true);
-
-    let generate_dockerfile = build_with_args(
-        &[String::from("-s"), String::from("atom")],
-        io_helper,
-        dck_helper,
-        dl_helper,
-        config,
-    );
-
-    let downloads = dl_helper.dl.borrow();
-
-    assert_eq!(downloads.len(), 0);
-
-    assert_eq!(
-        io_helper.files.borrow().get("dwn/atom.deb").unwrap(),
-        "Go, go, go !"
-    );
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
 
-    let builds = dck_helper.builds.borrow();
-    let atom_build = builds.get(0).unwrap();
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("-m")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    assert_eq!(atom_build.tag, "run-atom:latest");
-    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
-    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+    test_result_ok(build(cmd_param));
 
     let stdout = io_helper.stdout.borrow();
 
-    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+    assert_eq!(stdout.get(0).unwrap(), "Building filezilla...");
 }
 
 #[test]
-fn build_application_skip_download_by_app_conf() {
+fn build_missings_applications_with_outdated() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
 
+    // Create list of images returned by docker
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.hash"), String::from("old-hash"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), labels);
+
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     dck_helper
@@ -963,76 +2515,106 @@ fn build_application_skip_download_by_app_conf() {
 
     let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
 
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2\nskip_redownload: true"));
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\"\ndependencies:\n  - d1\n  - d2"));
     io_helper
         .files
         .borrow_mut()
-        .insert(String::from("dwn/atom.deb"), String::from("Go, go, go !"));
+        .insert(String::from("dwn/atom.deb"), String::from("toto"));
 
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
 
-    dl_helper
-        .update_dl_files
-        .borrow_mut()
-        .insert(String::from("dwn/atom.deb"), true);
-
-    let generate_dockerfile = build_with_args(
-        &[String::from("atom")],
-        io_helper,
-        dck_helper,
-        dl_helper,
-        config,
-    );
-
-    let downloads = dl_helper.dl.borrow();
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
 
-    assert_eq!(downloads.len(), 0);
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("-m")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    assert_eq!(
-        io_helper.files.borrow().get("dwn/atom.deb").unwrap(),
-        "Go, go, go !"
-    );
+    test_result_ok(build(cmd_param));
 
-    let builds = dck_helper.builds.borrow();
-    let atom_build = builds.get(0).unwrap();
+    let stdout = io_helper.stdout.borrow();
 
-    assert_eq!(atom_build.tag, "run-atom:latest");
-    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
-    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+    found_item(&stdout, "Building atom...");
+    found_item(&stdout, "Building filezilla...");
 
-    let stdout = io_helper.stdout.borrow();
+    // Hash label is updated
+    let labels = dck_helper.labels.borrow();
+    let atom_labels = labels.get("run-atom:latest").unwrap();
 
-    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+    assert_ne!(atom_labels.get("d-sh.hash").unwrap(), "old-hash");
 }
 
 #[test]
-fn build_application_with_force() {
+fn build_application_with_missing_base_image() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
 
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
-    dck_helper
-        .images
-        .borrow_mut()
-        .push(config.dockerfile.tag.clone());
-
     let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
 
     io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
 
     let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
 
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(
+                cfg_file,
+                String::from(
+                    "{{dockerfile_from}} {{#if dockerfile_base}}coucou {{dependencies}}{{/if}}",
+                ),
+            )
+        }
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &ENTRYPOINT_FILENAME) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from(ENTRYPOINT))
+        }
+        None => panic!("Unable to create entrypoint for test"),
+    };
+
     let generate_dockerfile = build_with_args(
-        &[String::from("-f"), String::from("atom")],
+        &[String::from("atom")],
         io_helper,
         dck_helper,
         dl_helper,
@@ -1046,7 +2628,12 @@ fn build_application_with_force() {
     assert_eq!(dl.url, "toto");
 
     let builds = dck_helper.builds.borrow();
-    let atom_build = builds.get(0).unwrap();
+
+    let base_build = builds.get(0).unwrap();
+
+    assert_eq!(base_build.tag, "tutu");
+
+    let atom_build = builds.get(1).unwrap();
 
     assert_eq!(atom_build.tag, "run-atom:latest");
     assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
@@ -1055,299 +2642,494 @@ fn build_application_with_force() {
     let stdout = io_helper.stdout.borrow();
 
     assert_eq!(stdout.get(0).unwrap(), "Building atom...");
+}
 
-    let builds = dck_helper.builds.borrow();
-    let app_build = builds.get(0).unwrap();
+// TODO These test need more better implementation of folder/file in test.
+// Create a real tree with hook when create, read, update, delete
+//  - test: build test with generate Dockerfile/entry.sh error caused by folder error
+//  - test: build test with delete folder error caused by folder error
 
-    assert_eq!(app_build.build_options.get(0).unwrap(), "--no-cache");
-}
+// TODO check if ctrl+c on curl
 
 #[test]
-fn build_many_applications() {
+fn build_application_with_version_in_url() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
 
-    // Create configuration file
-    let config = Config {
-        download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
-        dockerfile: ConfigDocker {
-            from: String::from("tata"),
-            tag: String::from("tutu"),
-        },
-        tmp_dir: None,
-    };
+    create_files_for_render(io_helper);
 
     dck_helper
         .images
         .borrow_mut()
         .push(config.dockerfile.tag.clone());
 
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\nversion: \"1.34.0\"\ndownload_filename: \"atom-{{version}}.tar.gz\"\nurl: \"https://atom.io/v{{version}}/atom-amd64.tar.gz\""));
 
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\"\ndependencies:\n  - d1\n  - d2"));
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    test_result_ok(build(cmd_param));
 
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
-        }
-        None => panic!("Unable to create dockerfile for test"),
+    // Override version of application file
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[
+            String::from("atom"),
+            String::from("--version"),
+            String::from("1.35.0"),
+        ],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
+    test_result_ok(build(cmd_param));
+
+    let dl = dl_helper.dl.borrow();
+
+    assert_eq!(
+        dl.get(0).unwrap().url,
+        "https://atom.io/v1.34.0/atom-amd64.tar.gz"
+    );
+    assert_eq!(dl.get(0).unwrap().output_filename, "dwn/atom-1.34.0.tar.gz");
+    assert_eq!(
+        dl.get(1).unwrap().url,
+        "https://atom.io/v1.35.0/atom-amd64.tar.gz"
+    );
+    assert_eq!(dl.get(1).unwrap().output_filename, "dwn/atom-1.35.0.tar.gz");
+
+    let builds = dck_helper.builds.borrow();
+
+    assert!(builds
+        .get(0)
+        .unwrap()
+        .build_options
+        .contains(&String::from("d-sh.version=1.34.0")));
+    assert!(builds
+        .get(1)
+        .unwrap()
+        .build_options
+        .contains(&String::from("d-sh.version=1.35.0")));
+}
+
+#[test]
+fn build_version_with_many_applications() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
+
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &[String::from("atom"), String::from("filezilla")],
+        args: &[
+            String::from("atom"),
+            String::from("filezilla"),
+            String::from("-v"),
+            String::from("1.35.0"),
+        ],
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    test_result_ok(build(cmd_param));
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::BadArgument);
 
-    let downloads = dl_helper.dl.borrow();
-    let dl = downloads.get(0).unwrap();
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "d-sh build: option '--version' can be used only with one application"
+    );
+}
 
-    assert_eq!(dl.output_filename, "dwn/atom.deb");
-    assert_eq!(dl.url, "toto");
+#[test]
+fn build_outdated_applications_with_last_version() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
 
-    let dl = downloads.get(1).unwrap();
+    create_files_for_render(io_helper);
 
-    assert_eq!(dl.output_filename, "dwn/filezilla.deb");
-    assert_eq!(dl.url, "titi");
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
 
-    let builds = dck_helper.builds.borrow();
-    let atom_build = builds.get(0).unwrap();
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\nversion: \"1.34.0\"\ndownload_filename: \"atom-{{version}}.tar.gz\"\nurl: \"https://atom.io/v{{version}}/atom-amd64.tar.gz\"\nupdate_check:\n  type: github\n  url: \"https://api.github.com/repos/atom/atom/releases/latest\""));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\nversion: \"3.40.0\"\ndownload_filename: \"filezilla.tar.bz2\"\nurl: \"https://filezilla-project.org/filezilla.tar.bz2\"\nupdate_check:\n  type: github\n  url: \"https://api.github.com/repos/filezilla/filezilla/releases/latest\""));
 
-    assert_eq!(atom_build.tag, "run-atom:latest");
+    dl_helper.contents.borrow_mut().insert(
+        String::from("https://api.github.com/repos/atom/atom/releases/latest"),
+        String::from("{\"tag_name\": \"v1.35.0\"}"),
+    );
+    dl_helper.contents.borrow_mut().insert(
+        String::from("https://api.github.com/repos/filezilla/filezilla/releases/latest"),
+        String::from("{\"tag_name\": \"v3.40.0\"}"),
+    );
 
-    let filezilla_build = builds.get(1).unwrap();
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("--outdated")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    assert_eq!(filezilla_build.tag, "run-filezilla:latest");
+    test_result_ok(build(cmd_param));
+
+    let dl = dl_helper.dl.borrow();
+
+    assert_eq!(dl.len(), 1);
+    assert_eq!(
+        dl.get(0).unwrap().url,
+        "https://atom.io/v1.35.0/atom-amd64.tar.gz"
+    );
 
-    let stdout = io_helper.stdout.borrow();
+    let builds = dck_helper.builds.borrow();
 
-    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
-    assert_eq!(stdout.get(1).unwrap(), "Building filezilla...");
+    assert_eq!(builds.len(), 1);
+    assert_eq!(builds.get(0).unwrap().tag, "run-atom:latest");
+    assert!(builds
+        .get(0)
+        .unwrap()
+        .build_options
+        .contains(&String::from("d-sh.version=1.35.0")));
 }
 
 #[test]
-fn build_all_applications() {
+fn build_application_pulled_from_registry_cache() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let mut config = create_config_with_bases();
 
-    // Create configuration file
-    let config = Config {
-        download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
-        dockerfile: ConfigDocker {
-            from: String::from("tata"),
-            tag: String::from("tutu"),
-        },
-        tmp_dir: None,
-    };
+    config.registry = Some(ConfigRegistry {
+        url: String::from("localhost:5000"),
+        cache: None,
+    });
+
+    create_files_for_render(io_helper);
 
     dck_helper
         .images
         .borrow_mut()
         .push(config.dockerfile.tag.clone());
 
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
-    // Add application with dependencies
+    // Nothing in registry, image is build
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\"\ndependencies:\n  - d1\n  - d2"));
+    test_result_ok(build(cmd_param));
 
-    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    assert_eq!(dck_helper.builds.borrow().len(), 1);
 
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
-        }
-        None => panic!("Unable to create dockerfile for test"),
-    };
+    // Someone push image, local image is removed
+    let hash = dck_helper
+        .labels
+        .borrow()
+        .get("run-atom:latest")
+        .unwrap()
+        .get(HASH_LABEL)
+        .unwrap()
+        .clone();
+    let cache_image = format!("localhost:5000/run-atom:{}", hash);
+
+    assert!(dck_helper
+        .tag_image("run-atom:latest", &cache_image)
+        .is_ok());
+    assert!(dck_helper.push_image(&cache_image).is_ok());
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .retain(|i| i != "run-atom:latest");
 
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &[String::from("-a")],
+        args: &[String::from("atom")],
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(build(cmd_param));
 
-    let downloads = dl_helper.dl.borrow();
-    let dl = downloads.get(0).unwrap();
+    assert_eq!(dck_helper.builds.borrow().len(), 1);
+    assert!(dck_helper
+        .images
+        .borrow()
+        .contains(&String::from("run-atom:latest")));
+    found_item(
+        &io_helper.stdout.borrow(),
+        "Image of atom pulled from registry.",
+    );
 
-    assert_eq!(dl.output_filename, "dwn/atom.deb");
-    assert_eq!(dl.url, "toto");
+    // With --force, registry is not used
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom"), String::from("--force")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    let dl = downloads.get(1).unwrap();
+    test_result_ok(build(cmd_param));
 
-    assert_eq!(dl.output_filename, "dwn/filezilla.deb");
-    assert_eq!(dl.url, "titi");
+    assert_eq!(dck_helper.builds.borrow().len(), 2);
 
-    let builds = dck_helper.builds.borrow();
-    let atom_build = builds.get(0).unwrap();
+    // Base image build on another host has another id but same config
+    dck_helper.image_ids.borrow_mut().insert(
+        config.dockerfile.tag.clone(),
+        String::from("sha256:other-host"),
+    );
+    dck_helper
+        .images
+        .borrow_mut()
+        .retain(|i| i != "run-atom:latest");
 
-    assert_eq!(atom_build.tag, "run-atom:latest");
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    let filezilla_build = builds.get(1).unwrap();
+    test_result_ok(build(cmd_param));
 
-    assert_eq!(filezilla_build.tag, "run-filezilla:latest");
+    assert_eq!(dck_helper.builds.borrow().len(), 2);
 
-    let stdout = io_helper.stdout.borrow();
+    // Config of base image change, image of registry is build on old base image
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), ENTRYPOINT_FILENAME) {
+        Some(cfg_file) => io_helper
+            .files
+            .borrow_mut()
+            .insert(cfg_file, String::from("#!/bin/sh\n")),
+        None => panic!("Unable to create entrypoint for test"),
+    };
+    dck_helper
+        .images
+        .borrow_mut()
+        .retain(|i| i != "run-atom:latest");
 
-    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
-    assert_eq!(stdout.get(1).unwrap(), "Building filezilla...");
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(build(cmd_param));
+
+    assert_eq!(dck_helper.builds.borrow().len(), 3);
+    assert!(!dck_helper.images.borrow().contains(&cache_image));
 }
 
 #[test]
-fn build_missings_applications() {
+fn build_locked_use_lock_file() {
     let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
 
-    // Create list of images returned by docker
+    create_files_for_render(io_helper);
+
+    // Image used by base image comes from registry
+    dck_helper.images.borrow_mut().push(String::from("tata"));
     dck_helper
-        .images
+        .repo_digests
         .borrow_mut()
-        .push(String::from("run-atom:latest"));
+        .insert(String::from("tata"), vec![String::from("tata@sha256:1234")]);
 
-    // Create configuration file
-    let config = Config {
-        download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
-        dockerfile: ConfigDocker {
-            from: String::from("tata"),
-            tag: String::from("tutu"),
-        },
-        tmp_dir: None,
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    dck_helper
-        .images
-        .borrow_mut()
-        .push(config.dockerfile.tag.clone());
+    test_result_ok(build(cmd_param));
 
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let lock_filename = create_config_filename_path(Some(TEST_CONFIG_DIR), LOCK_FILENAME).unwrap();
+    let lock: Lock =
+        serde_yaml::from_str(io_helper.files.borrow().get(&lock_filename).unwrap()).unwrap();
 
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
-    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.deb\"\nurl: \"titi\"\ndependencies:\n  - d1\n  - d2"));
+    assert_eq!(lock.bases.get("default").unwrap().from, "tata");
+    assert_eq!(lock.bases.get("default").unwrap().digest, "sha256:1234");
 
-    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let locked_atom = lock.applications.get("atom").unwrap();
 
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper.files.borrow_mut().insert(cfg_file, String::from("{{dockerfile_from}} {{#if (not dockerfile_base)}}bisous {{application_filename}}{{/if}}"))
-        }
-        None => panic!("Unable to create dockerfile for test"),
-    };
+    assert_eq!(
+        locked_atom.download_filename,
+        Some(String::from("atom.deb"))
+    );
+    assert!(locked_atom.sha256.is_some());
+    assert_eq!(
+        locked_atom.image_id,
+        Some(String::from("sha256:run-atom:latest-1"))
+    );
 
+    // Base image is pinned by digest
     let cmd_param = CommandParameter {
         command: &BUILD,
-        args: &[String::from("-m")],
+        args: &[
+            String::from("--base"),
+            String::from("--locked"),
+            String::from("--render-only"),
+            String::from("default"),
+        ],
         io_helper: io_helper,
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(build(cmd_param));
 
-    let stdout = io_helper.stdout.borrow();
-
-    assert_eq!(stdout.get(0).unwrap(), "Building filezilla...");
-}
+    assert_eq!(
+        io_helper.stdout.borrow().last().unwrap(),
+        "FROM tata@sha256:1234"
+    );
 
-#[test]
-fn build_application_with_missing_base_image() {
-    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    // Same downloaded file, not downloaded again
+    let downloads = dl_helper.dl.borrow().len();
+    let builds = dck_helper.builds.borrow().len();
 
-    // Create configuration file
-    let config = Config {
-        download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
-        dockerfile: ConfigDocker {
-            from: String::from("tata"),
-            tag: String::from("tutu"),
-        },
-        tmp_dir: None,
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom"), String::from("--locked")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    test_result_ok(build(cmd_param));
 
-    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\"\ndependencies:\n  - d1\n  - d2"));
+    assert_eq!(dl_helper.dl.borrow().len(), downloads);
 
-    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    // Base image build without lock is rebuild from locked digest before application
+    {
+        let all_builds = dck_helper.builds.borrow();
+        let new_builds = &all_builds[builds..];
 
-    // Create dockerfile
-    match create_config_filename_path(&DOCKERFILE_BASE_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper.files.borrow_mut().insert(
-                cfg_file,
-                String::from(
-                    "{{dockerfile_from}} {{#if dockerfile_base}}coucou {{dependencies}}{{/if}}",
-                ),
-            )
-        }
-        None => panic!("Unable to create dockerfile for test"),
-    };
+        assert_eq!(new_builds.len(), 2);
+        assert_eq!(new_builds[0].tag, "tutu");
+        assert!(new_builds[0]
+            .build_options
+            .contains(&String::from("d-sh.from=tata@sha256:1234")));
+        assert_eq!(new_builds[1].tag, "run-atom:latest");
+    }
 
-    // Create dockerfile
-    match create_config_filename_path(&ENTRYPOINT_FILENAME) {
-        Some(cfg_file) => {
-            // Create file
-            io_helper
-                .files
-                .borrow_mut()
-                .insert(cfg_file, String::from(ENTRYPOINT))
-        }
-        None => panic!("Unable to create entrypoint for test"),
+    // Base image already build from locked digest
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom"), String::from("--locked")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
-    let generate_dockerfile = build_with_args(
-        &[String::from("atom")],
-        io_helper,
-        dck_helper,
-        dl_helper,
-        config,
-    );
+    test_result_ok(build(cmd_param));
 
-    let downloads = dl_helper.dl.borrow();
-    let dl = downloads.get(0).unwrap();
+    assert_eq!(dck_helper.builds.borrow().len(), builds + 3);
 
-    assert_eq!(dl.output_filename, "dwn/atom.deb");
-    assert_eq!(dl.url, "toto");
+    // Lock file is not updated with --locked
+    assert_eq!(
+        serde_yaml::from_str::<Lock>(io_helper.files.borrow().get(&lock_filename).unwrap())
+            .unwrap(),
+        lock
+    );
 
-    let builds = dck_helper.builds.borrow();
+    // New version of downloaded file
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom.deb"), String::from("new atom"));
 
-    let base_build = builds.get(0).unwrap();
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[
+            String::from("atom"),
+            String::from("--locked"),
+            String::from("--skip-redownload"),
+        ],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-    assert_eq!(base_build.tag, "tutu");
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::DockerBuildFail);
 
-    let atom_build = builds.get(1).unwrap();
+    assert_eq!(
+        stderr.get(1).unwrap(),
+        "Downloaded file of 'atom' is different from lock file!"
+    );
+}
 
-    assert_eq!(atom_build.tag, "run-atom:latest");
-    assert_eq!(generate_dockerfile, atom_build.dockerfile_name);
-    assert!(generate_dockerfile.starts_with(&atom_build.base_dir));
+#[test]
+fn build_locked_without_lock_file() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config_with_bases();
 
-    let stdout = io_helper.stdout.borrow();
+    create_files_for_render(io_helper);
 
-    assert_eq!(stdout.get(0).unwrap(), "Building atom...");
-}
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("--base"), String::from("--locked")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
 
-// TODO These test need more better implementation of folder/file in test.
-// Create a real tree with hook when create, read, update, delete
-//  - test: build test with generate Dockerfile/entry.sh error caused by folder error
-//  - test: build test with delete folder error caused by folder error
+    let stderr = test_result_err(build(cmd_param), CommandExitCode::LockMismatch);
 
-// TODO check if ctrl+c on curl
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "Base image 'default' not found in lock file! Please build without '--locked' first."
+    );
+    assert_eq!(dck_helper.builds.borrow().len(), 0);
+}
@@ -1,11 +1,23 @@
-use command::{Command, CommandError, CommandExitCode, CommandParameter};
-use config::{get_config_application, Config};
+use command::build::hash::{
+    get_expected_application_hash, get_expected_base_hash, BASE_ID_LABEL, HASH_LABEL,
+};
+use command::list::{format_age, format_size};
+use command::{get_image_command_error, Command, CommandError, CommandExitCode, CommandParameter};
+use config::{
+    get_application_base_name, get_application_filenames, get_config_application, get_config_base,
+    get_config_base_names, Config, ConfigDocker,
+};
+use docker::remote::get_application_endpoint;
+use docker::ImageInfo;
 ///
 /// Module to check build container.
 ///
 /// Release under MIT License.
 ///
+use std::collections::HashMap;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use version::{get_downloaded_version, VERSION_LABEL};
 
 #[cfg(test)]
 mod tests;
@@ -20,10 +32,89 @@ pub struct CheckApplication {
     pub image_name: String,
     /// If image is already build.
     pub is_build: bool,
+    /// If image is build but application config, dockerfile or binary changed.
+    pub is_outdated: bool,
+    /// If image is build on an old base image.
+    pub is_base_outdated: bool,
     /// If cannot read application config file.
     pub is_error: bool,
     /// Config filename.
     pub config_filename: String,
+    /// Version of application in image.
+    pub installed_version: Option<String>,
+    /// Version of application downloaded.
+    pub downloaded_version: Option<String>,
+    /// Size of image in bytes.
+    pub size: Option<u64>,
+    /// Creation date of image in seconds since epoch.
+    pub created: Option<u64>,
+    /// Tags of image.
+    pub tags: Vec<String>,
+}
+
+impl CheckApplication {
+    ///
+    /// Return status of build.
+    ///
+    pub fn status(&self) -> &'static str {
+        if self.is_base_outdated {
+            "Base outdated"
+        } else if self.is_outdated {
+            "Build outdated"
+        } else if self.is_build {
+            "Build done"
+        } else {
+            "Build need"
+        }
+    }
+}
+
+///
+/// Return true if base image is build with an old config, Dockerfile or entrypoint.
+///
+fn is_base_image_outdated(
+    cmd_param: &CommandParameter,
+    config: &Config,
+    base_name: &str,
+    base: &ConfigDocker,
+    image: &ImageInfo,
+) -> bool {
+    // Image build before this label exists, cannot know
+    match image.labels.get(HASH_LABEL) {
+        Some(hash) => match get_expected_base_hash(cmd_param, config, base_name, base) {
+            Some(ref expected) => expected != hash,
+            // If hash cannot be computed, we cannot say if image is outdated
+            None => false,
+        },
+        None => false,
+    }
+}
+
+///
+/// Return base images build with an old config, Dockerfile or entrypoint.
+///
+pub fn get_outdated_base_names(
+    cmd_param: &CommandParameter,
+    config: &Config,
+) -> Result<Vec<String>, CommandError> {
+    let mut result = Vec::new();
+
+    for base_name in get_config_base_names(config) {
+        if let Some(base) = get_config_base(config, &base_name) {
+            let image = cmd_param
+                .dck_helper
+                .inspect_image(&base.tag)
+                .map_err(|err| get_image_command_error(&base.tag, &err))?;
+
+            if let Some(image) = image {
+                if is_base_image_outdated(cmd_param, config, &base_name, base, &image) {
+                    result.push(base_name);
+                }
+            }
+        }
+    }
+
+    Ok(result)
 }
 
 ///
@@ -36,10 +127,7 @@ pub fn get_check_application(
     let list_applications_file;
 
     // 1 - We have got configuration
-    match cmd_param
-        .io_helper
-        .dir_list_file(&config.applications_dir, "*.yml")
-    {
+    match get_application_filenames(cmd_param.io_helper, config) {
         Ok(r) => list_applications_file = r,
         Err(err) => {
             return Err(CommandError {
@@ -51,6 +139,27 @@ pub fn get_check_application(
 
     let mut result = Vec::new();
 
+    // Id of each base image, and base images to rebuild
+    let mut base_image_ids = HashMap::new();
+    let mut outdated_bases = Vec::new();
+
+    for base_name in get_config_base_names(config) {
+        if let Some(base) = get_config_base(config, &base_name) {
+            let image = cmd_param
+                .dck_helper
+                .inspect_image(&base.tag)
+                .map_err(|err| get_image_command_error(&base.tag, &err))?;
+
+            if let Some(image) = image {
+                if is_base_image_outdated(cmd_param, config, &base_name, base, &image) {
+                    outdated_bases.push(base_name.clone());
+                }
+
+                base_image_ids.insert(base_name, image.id);
+            }
+        }
+    }
+
     // 2 - We have list of application
     for filename in list_applications_file {
         let application_name = Path::new(&filename)
@@ -63,19 +172,83 @@ pub fn get_check_application(
             name: String::from(application_name),
             image_name: String::new(),
             is_build: false,
+            is_outdated: false,
+            is_base_outdated: false,
             is_error: true,
             config_filename: String::new(),
+            installed_version: None,
+            downloaded_version: None,
+            size: None,
+            created: None,
+            tags: Vec::new(),
         };
 
         if let Ok(config_application) = get_config_application(cmd_param.io_helper, &filename) {
-            let images = cmd_param
-                .dck_helper
-                .list_image(&config_application.image_name);
-
-            app.is_build = images.len() > 0;
             app.image_name = config_application.image_name.clone();
             app.config_filename = filename.clone();
             app.is_error = false;
+
+            // Application can be build on its own Docker daemon
+            let app_helper = get_application_endpoint(&config_application)
+                .map(|endpoint| cmd_param.dck_helper.with_endpoint(&endpoint));
+            let dck_helper = match app_helper {
+                Some(ref h) => &**h,
+                None => cmd_param.dck_helper,
+            };
+
+            let image = dck_helper
+                .inspect_image(&config_application.image_name)
+                .map_err(|err| get_image_command_error(&config_application.image_name, &err))?;
+
+            if let Some(image) = image {
+                app.is_build = true;
+                app.installed_version = image.labels.get(VERSION_LABEL).cloned();
+                app.size = Some(image.size);
+                app.created = image.created;
+                app.tags = image.tags.clone();
+
+                let base_name = get_application_base_name(&config_application);
+
+                // If base image is unknown, we cannot say if image is outdated
+                if let Some(base) = get_config_base(config, base_name) {
+                    // If hash cannot be computed, we cannot say if image is outdated
+                    if let Some(hash) = get_expected_application_hash(
+                        cmd_param,
+                        config,
+                        base,
+                        &config_application,
+                        &filename,
+                    ) {
+                        app.is_outdated = image.labels.get(HASH_LABEL) != Some(&hash);
+                    }
+
+                    // Base image of application on its own Docker daemon is on this daemon
+                    let base_id = match app_helper {
+                        Some(_) => dck_helper
+                            .inspect_image(&base.tag)
+                            .map_err(|err| get_image_command_error(&base.tag, &err))?
+                            .map(|i| i.id),
+                        None => base_image_ids.get(base_name).cloned(),
+                    };
+
+                    // Image build before this label exists, cannot know
+                    if let Some(id) = image.labels.get(BASE_ID_LABEL) {
+                        app.is_base_outdated = base_id.as_ref() != Some(id);
+                    }
+
+                    // Base image itself must be rebuild
+                    if outdated_bases.iter().any(|b| b == base_name) {
+                        app.is_base_outdated = true;
+                    }
+                }
+            }
+
+            app.downloaded_version = get_downloaded_version(
+                cmd_param.io_helper,
+                cmd_param.dl_helper,
+                config,
+                &config_application,
+            );
         }
 
         result.push(app);
@@ -112,23 +285,30 @@ fn check(cmd_param: CommandParameter) -> Result<(), CommandError> {
         .filter(|a| !a.is_error)
         .collect();
 
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
     // 2 - We have list of application
     for app in list_app {
-        let status;
-
-        if app.is_build {
-            status = "Build done"
-        } else {
-            status = "Build need";
-        }
+        let status = app.status();
 
         cmd_param.io_helper.println(&format!(
-            "{:<with_first$}{:<with_first$}{:<width_second$}",
+            "{:<with_first$}{:<with_first$}{:<width_second$}{:<width_third$}{:<width_third$}{:<width_size$}{}",
             app.name,
             app.image_name,
             status,
+            app.installed_version.unwrap_or(String::from("-")),
+            app.downloaded_version.unwrap_or(String::from("-")),
+            app.size.map(format_size).unwrap_or(String::from("-")),
+            app.created
+                .map(|c| format_age(c, now))
+                .unwrap_or(String::from("-")),
             with_first = 34,
-            width_second = 13
+            width_second = 16,
+            width_third = 15,
+            width_size = 10
         ));
     }
 
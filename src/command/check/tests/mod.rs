@@ -1,9 +1,13 @@
 use super::{check, CHECK};
-use command::tests::{test_result_err, test_result_ok};
+use command::build::BUILD;
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
 use command::{CommandExitCode, CommandParameter};
-use config::{Config, ConfigDocker};
+use config::create_config_filename_path;
+use config::dockerfile::{DOCKERFILE_BASE_FILENAME, ENTRYPOINT, ENTRYPOINT_FILENAME};
+use config::{self, Config, ConfigApplicationsDir, ConfigDocker};
 use docker::tests::TestContainerHelper;
 use download::tests::TestDownloadHelper;
+use helper::HelperError;
 use io::tests::found_item;
 ///
 /// Module to tests module check.
@@ -11,6 +15,8 @@ use io::tests::found_item;
 /// Release under MIT License.
 ///
 use io::tests::TestInputOutputHelper;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[test]
 fn check_if_image_found_and_not_found() {
@@ -37,12 +43,21 @@ fn check_if_image_found_and_not_found() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -57,6 +72,7 @@ fn check_if_image_found_and_not_found() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(check(cmd_param));
@@ -65,15 +81,395 @@ fn check_if_image_found_and_not_found() {
 
     found_item(
         &stdout,
-        "atom                              run-atom:latest                   Build done   ",
+        "atom                              run-atom:latest                   Build done      -              -              0B        -",
     );
     found_item(
         &stdout,
-        "filezilla                         run-filezilla:latest              Build done   ",
+        "filezilla                         run-filezilla:latest              Build done      -              -              0B        -",
     );
     found_item(
         &stdout,
-        "titi                              run-titi:latest                   Build need   ",
+        "titi                              run-titi:latest                   Build need      -              -              -         -",
+    );
+}
+
+#[test]
+fn check_if_daemon_unreachable() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [];
+
+    let config = config::tests::create_config();
+
+    // Create application file atom
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    *dck_helper.error.borrow_mut() = Some(HelperError::DaemonUnreachable(String::from(
+        "Cannot connect to the Docker daemon at unix:///var/run/docker.sock.",
+    )));
+
+    let cmd_param = CommandParameter {
+        command: &CHECK,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(check(cmd_param), CommandExitCode::DaemonUnreachable);
+
+    assert_eq!(stderr.get(0).unwrap(), "Unable to inspect image 'tutu'!");
+    assert_eq!(
+        stderr.get(1).unwrap(),
+        "Daemon unreachable: Cannot connect to the Docker daemon at unix:///var/run/docker.sock."
+    );
+}
+
+#[test]
+fn check_display_installed_and_downloaded_version() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [];
+
+    // Create list of images returned by docker
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.version"), String::from("1.33.0"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), labels);
+    dck_helper
+        .sizes
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), 512_000_000);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    dck_helper
+        .created
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), now - 3 * 86400);
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    // Create application file atom
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom-1.34.0.tar.gz\"\nurl: \"toto\""));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom-1.34.0.tar.gz"), String::from("toto"));
+
+    let cmd_param = CommandParameter {
+        command: &CHECK,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(check(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    found_item(
+        &stdout,
+        "atom                              run-atom:latest                   Build done      1.33.0         1.34.0         512.0MB   3 days ago",
+    );
+}
+
+#[test]
+fn check_if_image_outdated() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+
+    // Create dockerfile
+    match create_config_filename_path(Some(TEST_CONFIG_DIR), &DOCKERFILE_BASE_FILENAME) {
+        Some(cfg_file) => io_helper.files.borrow_mut().insert(
+            cfg_file,
+            String::from("{{dockerfile_from}} {{application_filename}}"),
+        ),
+        None => panic!("Unable to create dockerfile for test"),
+    };
+
+    // Create application file atom
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\""));
+
+    // Build image to have hash label
+    let cmd_param = CommandParameter {
+        command: &BUILD,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok((BUILD.exec_cmd)(cmd_param));
+
+    let cmd_param = CommandParameter {
+        command: &CHECK,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(check(cmd_param));
+
+    found_item(
+        &io_helper.stdout.borrow(),
+        "atom                              run-atom:latest                   Build done      -              -              0B        -",
+    );
+
+    // Application config change
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"atom\"\ndownload_filename: \"atom.deb\"\nurl: \"toto\""));
+
+    let cmd_param = CommandParameter {
+        command: &CHECK,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(check(cmd_param));
+
+    found_item(
+        &io_helper.stdout.borrow(),
+        "atom                              run-atom:latest                   Build outdated  -              -              0B        -",
+    );
+}
+
+#[test]
+fn check_if_base_image_outdated() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-filezilla:latest"));
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.base-id"), String::from("sha256:old"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), labels);
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.base-id"), String::from("sha256:tutu"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from("run-filezilla:latest"), labels);
+
+    // Create application file atom
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    let cmd_param = CommandParameter {
+        command: &CHECK,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(check(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    found_item(
+        &stdout,
+        "atom                              run-atom:latest                   Base outdated   -              -              0B        -",
+    );
+    found_item(
+        &stdout,
+        "filezilla                         run-filezilla:latest              Build done      -              -              0B        -",
+    );
+}
+
+#[test]
+fn check_if_base_image_build_with_old_template() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    for (template, content) in &[
+        (DOCKERFILE_BASE_FILENAME, "{{dockerfile_from}}"),
+        (ENTRYPOINT_FILENAME, ENTRYPOINT),
+    ] {
+        match create_config_filename_path(Some(TEST_CONFIG_DIR), template) {
+            Some(cfg_file) => io_helper
+                .files
+                .borrow_mut()
+                .insert(cfg_file, String::from(*content)),
+            None => panic!("Unable to create template for test"),
+        };
+    }
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(config.dockerfile.tag.clone());
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.hash"), String::from("old-hash"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(config.dockerfile.tag.clone(), labels);
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.base-id"), String::from("sha256:tutu"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), labels);
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    let cmd_param = CommandParameter {
+        command: &CHECK,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(check(cmd_param));
+
+    found_item(
+        &io_helper.stdout.borrow(),
+        "atom                              run-atom:latest                   Base outdated   -              -              0B        -",
     );
 }
 
@@ -94,12 +490,21 @@ fn check_if_application_format_has_an_error() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -112,6 +517,7 @@ fn check_if_application_format_has_an_error() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(check(cmd_param), CommandExitCode::BadApplicationFormat);
@@ -130,12 +536,21 @@ fn check_if_cannot_read_application_dir() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     io_helper
@@ -150,6 +565,7 @@ fn check_if_cannot_read_application_dir() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(
@@ -0,0 +1,481 @@
+///
+/// Module to show config of D-SH.
+///
+/// Release under MIT License.
+///
+use command::validate::format_yaml_error;
+use command::{Command, CommandError, CommandExitCode, CommandParameter};
+use config::layer::{
+    get_config_layers, merge_config_layers, parse_config_value, read_config_layer, remove_value,
+    set_value, ConfigLayer, ConfigOrigin, MergedConfig, SYSTEM_CONFIG_FILE,
+};
+use config::{get_config_filename, Config};
+use std::env;
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+const UNKOWN_OPTIONS_MESSAGE: &'static str =
+    "d-sh config: invalid option '{}'\nTry 'd-sh config --help' for more information.\n";
+
+///
+/// Return value of config as displayed by `show --origin`.
+///
+fn format_value(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        v => serde_json::to_string(v).unwrap_or_default(),
+    }
+}
+
+///
+/// Return all layers of config: config files and environment variables.
+///
+fn get_layers(cmd_param: &CommandParameter) -> Result<Vec<ConfigLayer>, CommandError> {
+    let dir = env::current_dir().ok();
+    let vars: Vec<(String, String)> = env::vars().collect();
+
+    match get_config_layers(
+        cmd_param.io_helper,
+        cmd_param.config_dir,
+        dir.as_deref(),
+        &vars,
+    ) {
+        Ok(r) => Ok(r),
+        Err(err) => Err(CommandError {
+            msg: vec![format!("{}", err)],
+            code: CommandExitCode::ConfigFileFormatWrong,
+        }),
+    }
+}
+
+///
+/// Return config of all layers: config files and environment variables.
+///
+fn get_merged_config(cmd_param: &CommandParameter) -> Result<MergedConfig, CommandError> {
+    let layers = get_layers(cmd_param)?;
+
+    if layers.is_empty() {
+        return Err(CommandError {
+            msg: vec![String::from(
+                "No config file found. Please run 'init' command first.",
+            )],
+            code: CommandExitCode::ConfigFileNotFound,
+        });
+    }
+
+    Ok(merge_config_layers(&layers))
+}
+
+///
+/// Display config, with `origin` each value is displayed with its config file or
+/// environment variable.
+///
+fn show(cmd_param: &CommandParameter, origin: bool) -> Result<(), CommandError> {
+    let merged = get_merged_config(cmd_param)?;
+
+    if origin {
+        for (key, o) in &merged.origins {
+            if let Some(value) = merged.get(key) {
+                cmd_param
+                    .io_helper
+                    .println(&format!("{}\t{}={}", o, key, format_value(value)));
+            }
+        }
+    } else {
+        match serde_yaml::to_string(&merged.value) {
+            Ok(data) => cmd_param.io_helper.println(&data),
+            Err(err) => {
+                return Err(CommandError {
+                    msg: vec![format!("Cannot display config! {}", err)],
+                    code: CommandExitCode::ConfigFileFormatWrong,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// Display value of key like `dockerfile.from`.
+///
+fn get(cmd_param: &CommandParameter, key: &str) -> Result<(), CommandError> {
+    let merged = get_merged_config(cmd_param)?;
+
+    match merged.get(key) {
+        Some(value) => {
+            cmd_param.io_helper.println(&format_value(value));
+            Ok(())
+        }
+        None => Err(CommandError {
+            msg: vec![format!("Key '{}' not found in config!", key)],
+            code: CommandExitCode::ConfigKeyNotFound,
+        }),
+    }
+}
+
+///
+/// Return filename of user config.
+///
+fn get_user_config_filename(cmd_param: &CommandParameter) -> Result<String, CommandError> {
+    match get_config_filename(cmd_param.config_dir) {
+        Some(r) => Ok(r),
+        None => Err(CommandError {
+            msg: vec![String::from("Unable to get your home dir!")],
+            code: CommandExitCode::CannotGetHomeFolder,
+        }),
+    }
+}
+
+///
+/// Read user config file, all keys are kept even if unknown. Missing file is empty.
+///
+fn read_user_config(
+    cmd_param: &CommandParameter,
+    filename: &str,
+) -> Result<serde_yaml::Value, CommandError> {
+    if !cmd_param.io_helper.file_exits(filename) {
+        return Ok(serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
+    }
+
+    match read_config_layer(cmd_param.io_helper, filename) {
+        Ok(layer) => Ok(layer.value),
+        Err(err) => Err(CommandError {
+            msg: vec![format!("{}", err)],
+            code: CommandExitCode::ConfigFileFormatWrong,
+        }),
+    }
+}
+
+///
+/// Check that config is valid if user config file is `value`.
+///
+fn check_user_config(
+    cmd_param: &CommandParameter,
+    filename: &str,
+    value: &serde_yaml::Value,
+) -> Result<(), CommandError> {
+    let mut layers = get_layers(cmd_param)?;
+    let origin = ConfigOrigin::File(String::from(filename));
+    let layer = ConfigLayer {
+        origin: origin.clone(),
+        value: value.clone(),
+    };
+
+    match layers.iter().position(|l| l.origin == origin) {
+        Some(index) => layers[index] = layer,
+        None => {
+            // User config is after system config
+            let index = layers
+                .iter()
+                .take_while(|l| l.origin == ConfigOrigin::File(String::from(SYSTEM_CONFIG_FILE)))
+                .count();
+
+            layers.insert(index, layer);
+        }
+    }
+
+    match serde_yaml::from_value::<Config>(merge_config_layers(&layers).value) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(CommandError {
+            msg: vec![format!("Config is wrong, {}!", err)],
+            code: CommandExitCode::ConfigFileFormatWrong,
+        }),
+    }
+}
+
+///
+/// Return true if YAML file has comments.
+///
+fn has_comments(data: &str) -> bool {
+    data.lines().any(|l| {
+        let l = l.trim_start();
+        l.starts_with('#') || l.contains(" #")
+    })
+}
+
+///
+/// Write user config file. Comments of file are not kept.
+///
+fn write_user_config(
+    cmd_param: &CommandParameter,
+    filename: &str,
+    value: &serde_yaml::Value,
+) -> Result<(), CommandError> {
+    let data = match serde_yaml::to_string(value) {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Unable to write file '{}'", filename),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotWriteConfigFile,
+            });
+        }
+    };
+
+    if let Ok(old_data) = cmd_param.io_helper.file_read_at_string(filename) {
+        if has_comments(&old_data) {
+            cmd_param.io_helper.eprintln(&format!(
+                "Warning: comments of '{}' are removed, use 'd-sh config edit' to keep them.",
+                filename
+            ));
+        }
+    }
+
+    if let Some(parent) = Path::new(filename).parent() {
+        if let Err(err) = cmd_param.io_helper.create_dir_all(parent.to_str().unwrap()) {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Cannot create folder '{}'!", parent.display()),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotCreateFolderForConfigFile,
+            });
+        }
+    }
+
+    match cmd_param
+        .io_helper
+        .file_write(filename, &format!("{}\n", data))
+    {
+        Ok(_) => Ok(()),
+        Err(err) => Err(CommandError {
+            msg: vec![
+                format!("Unable to write file '{}'", filename),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotWriteConfigFile,
+        }),
+    }
+}
+
+///
+/// Set value of key in user config file. Value is read as YAML, like `[app, team]`.
+///
+fn set(cmd_param: &CommandParameter, key: &str, data: &str) -> Result<(), CommandError> {
+    let filename = get_user_config_filename(cmd_param)?;
+    let mut value = read_user_config(cmd_param, &filename)?;
+
+    set_value(&mut value, key, parse_config_value(key, data));
+
+    check_user_config(cmd_param, &filename, &value)?;
+    write_user_config(cmd_param, &filename, &value)?;
+
+    // Value of a next layer is used
+    let merged = get_merged_config(cmd_param)?;
+
+    if let Some(origin) = merged.origins.get(key) {
+        if *origin != ConfigOrigin::File(filename) {
+            cmd_param
+                .io_helper
+                .eprintln(&format!("Warning: '{}' is overridden by {}", key, origin));
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// Remove key from user config file.
+///
+fn unset(cmd_param: &CommandParameter, key: &str) -> Result<(), CommandError> {
+    let filename = get_user_config_filename(cmd_param)?;
+    let mut value = read_user_config(cmd_param, &filename)?;
+
+    if remove_value(&mut value, key).is_none() {
+        return Err(CommandError {
+            msg: vec![format!("Key '{}' not found in '{}'!", key, filename)],
+            code: CommandExitCode::ConfigKeyNotFound,
+        });
+    }
+
+    check_user_config(cmd_param, &filename, &value)?;
+    write_user_config(cmd_param, &filename, &value)
+}
+
+///
+/// Check content of user config file after edition.
+///
+fn check_edited_config(cmd_param: &CommandParameter, filename: &str) -> Result<(), CommandError> {
+    let data = match cmd_param.io_helper.file_read_at_string(filename) {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![format!("{}: {}", filename, err)],
+                code: CommandExitCode::ConfigFileFormatWrong,
+            });
+        }
+    };
+
+    let value = match serde_yaml::from_str(&data) {
+        Ok(serde_yaml::Value::Null) => serde_yaml::Value::Mapping(serde_yaml::Mapping::new()),
+        Ok(r) => r,
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![format_yaml_error(filename, &err)],
+                code: CommandExitCode::ConfigFileFormatWrong,
+            });
+        }
+    };
+
+    check_user_config(cmd_param, filename, &value)
+}
+
+///
+/// Open user config file in editor. If config is wrong after edition, file is edited again
+/// or restored.
+///
+fn edit(cmd_param: &CommandParameter) -> Result<(), CommandError> {
+    let filename = get_user_config_filename(cmd_param)?;
+
+    if !cmd_param.io_helper.file_exits(&filename) {
+        return Err(CommandError {
+            msg: vec![format!(
+                "The file '{}' doesn't exits. Please run 'init' command first.",
+                filename
+            )],
+            code: CommandExitCode::ConfigFileNotFound,
+        });
+    }
+
+    let original = match cmd_param.io_helper.file_read_at_string(&filename) {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Unable to read '{}'!", filename),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::ConfigFileFormatWrong,
+            });
+        }
+    };
+
+    loop {
+        if let Err(err) = cmd_param.io_helper.edit_file(&filename) {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Unable to edit '{}'!", filename),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotEditFile,
+            });
+        }
+
+        let err = match check_edited_config(cmd_param, &filename) {
+            Ok(_) => return Ok(()),
+            Err(err) => err,
+        };
+
+        for msg in &err.msg {
+            cmd_param.io_helper.eprintln(msg);
+        }
+
+        cmd_param.io_helper.print("Edit again? [Y/n] ");
+
+        if cmd_param.io_helper.read_line().trim().to_lowercase() == "n" {
+            // Restore file
+            if let Err(e) = cmd_param.io_helper.file_write(&filename, &original) {
+                cmd_param.io_helper.eprintln(&format!("{}", e));
+            }
+
+            return Err(CommandError {
+                msg: vec![format!("Config file '{}' not changed.", filename)],
+                code: err.code,
+            });
+        }
+    }
+}
+
+///
+/// Function to implement config D-SH command.
+///
+/// `args` parameter is command line arguments of D-SH.
+///
+/// returning exit code of D-SH.
+///
+fn config(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    match cmd_param.args[0].as_ref() {
+        "-h" | "--help" => {
+            cmd_param.io_helper.println(cmd_param.command.usage);
+            Ok(())
+        }
+        "show" => {
+            let mut origin = false;
+
+            for arg in &cmd_param.args[1..] {
+                match arg.as_ref() {
+                    "--origin" => origin = true,
+                    other => {
+                        return Err(CommandError {
+                            msg: vec![UNKOWN_OPTIONS_MESSAGE.replace("{}", other)],
+                            code: CommandExitCode::UnknowOption,
+                        });
+                    }
+                }
+            }
+
+            show(&cmd_param, origin)
+        }
+        "get" if cmd_param.args.len() == 2 => get(&cmd_param, &cmd_param.args[1]),
+        "set" if cmd_param.args.len() == 3 => {
+            set(&cmd_param, &cmd_param.args[1], &cmd_param.args[2])
+        }
+        "unset" if cmd_param.args.len() == 2 => unset(&cmd_param, &cmd_param.args[1]),
+        "edit" if cmd_param.args.len() == 1 => edit(&cmd_param),
+        action @ "get" | action @ "set" | action @ "unset" | action @ "edit" => Err(CommandError {
+            msg: vec![
+                format!("\"d-sh config {}\" bad arguments number.", action),
+                String::from("See 'd-sh config --help'."),
+            ],
+            code: CommandExitCode::BadArgument,
+        }),
+        other => Err(CommandError {
+            msg: vec![
+                format!("d-sh config: unknown action '{}'", other),
+                String::from("Try 'd-sh config --help' for more information."),
+            ],
+            code: CommandExitCode::BadArgument,
+        }),
+    }
+}
+
+///
+/// The `config` command.
+///
+pub const CONFIG: Command = Command {
+    /// This command call by `config`.
+    name: "config",
+    /// description.
+    description: "Show or change configuration",
+    /// Short name.
+    short_name: "cfg",
+    /// `config` command have action and its arguments.
+    min_args: 1,
+    max_args: 3,
+    /// `config` command help.
+    usage: "
+    Usage:	d-sh config show [--origin]
+    	d-sh config get KEY
+    	d-sh config set KEY VALUE
+    	d-sh config unset KEY
+    	d-sh config edit
+
+    Show configuration, merged from config files and environment variables:
+      /etc/d-sh/config.yml, ~/.d-sh/config.yml, .d-sh.yml of current folder or its
+      parents, then D_SH_* variables (like D_SH_TMP_DIR)
+
+    KEY is like 'tmp_dir' or 'dockerfile.from'. VALUE is read as YAML, like '[app, team]'.
+    'set', 'unset' and 'edit' change ~/.d-sh/config.yml, config must stay valid.
+    'edit' opens file with $VISUAL or $EDITOR.
+
+    Options:
+      --origin                 Show where each value come from
+",
+    need_config_file: false,
+    exec_cmd: config,
+};
@@ -0,0 +1,420 @@
+///
+/// Module to tests module config.
+///
+/// Release under MIT License.
+///
+use super::{config, CONFIG};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::get_config_filename;
+use config::layer::SYSTEM_CONFIG_FILE;
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+use io::tests::{found_item, TestInputOutputHelper};
+
+#[test]
+fn config_show_origin() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("show"), String::from("--origin")];
+
+    io_helper.files.borrow_mut().insert(
+        String::from(SYSTEM_CONFIG_FILE),
+        String::from("---\ndownload_dir: \"/opt/dwn\"\napplications_dir:\n  - app\n  - team\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\n"),
+    );
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(config(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    found_item(
+        &stdout,
+        "file:/etc/d-sh/config.yml\tapplications_dir=[\"app\",\"team\"]",
+    );
+    found_item(
+        &stdout,
+        "file:/etc/d-sh/config.yml\tdockerfile.from=ubuntu:18.04",
+    );
+    found_item(&stdout, "file:/etc/d-sh/config.yml\tdownload_dir=/opt/dwn");
+}
+
+#[test]
+fn config_show_without_config_file() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("show")];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(config(cmd_param), CommandExitCode::ConfigFileNotFound);
+}
+
+#[test]
+fn config_unknown_action() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("list")];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(config(cmd_param), CommandExitCode::BadArgument);
+}
+
+const CONFIG_DATA: &'static str = "---\nformat_version: 1\ndownload_dir: \"dwn\"\napplications_dir: \"app\"\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\n";
+
+fn add_config_file(io_helper: &TestInputOutputHelper) -> String {
+    let filename = get_config_filename(Some(TEST_CONFIG_DIR)).unwrap();
+
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(filename.clone(), String::from(CONFIG_DATA));
+
+    filename
+}
+
+#[test]
+fn config_set_and_get_value() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let filename = add_config_file(io_helper);
+
+    let args = [
+        String::from("set"),
+        String::from("dockerfile.from"),
+        String::from("ubuntu:20.04"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(config(cmd_param));
+
+    assert_eq!(
+        io_helper.files.borrow().get(&filename).unwrap(),
+        "---\nformat_version: 1\ndownload_dir: dwn\napplications_dir: app\ndockerfile:\n  from: \"ubuntu:20.04\"\n  tag: \"d-base-image:v1.0.0\"\n"
+    );
+    assert!(io_helper.stderr.borrow().is_empty());
+
+    let args = [String::from("get"), String::from("dockerfile.from")];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(config(cmd_param));
+
+    found_item(&io_helper.stdout.borrow(), "ubuntu:20.04");
+}
+
+#[test]
+fn config_set_warn_if_comments_removed() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let filename = get_config_filename(Some(TEST_CONFIG_DIR)).unwrap();
+
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(filename.clone(), format!("# My config\n{}", CONFIG_DATA));
+
+    let args = [
+        String::from("set"),
+        String::from("tmp_dir"),
+        String::from("/var/tmp"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(config(cmd_param));
+
+    found_item(
+        &io_helper.stderr.borrow(),
+        &format!(
+            "Warning: comments of '{}' are removed, use 'd-sh config edit' to keep them.",
+            filename
+        ),
+    );
+    assert!(!io_helper
+        .files
+        .borrow()
+        .get(&filename)
+        .unwrap()
+        .contains("# My config"));
+}
+
+#[test]
+fn config_set_wrong_value() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let filename = add_config_file(io_helper);
+
+    let args = [
+        String::from("set"),
+        String::from("container_engine"),
+        String::from("rocket"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(config(cmd_param), CommandExitCode::ConfigFileFormatWrong);
+
+    // File not changed
+    assert_eq!(
+        io_helper.files.borrow().get(&filename).unwrap(),
+        CONFIG_DATA
+    );
+}
+
+#[test]
+fn config_set_string_like_number() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let filename = add_config_file(io_helper);
+
+    let args = [
+        String::from("set"),
+        String::from("dockerfile.tag"),
+        String::from("20.04"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(config(cmd_param));
+
+    assert_eq!(
+        io_helper.files.borrow().get(&filename).unwrap(),
+        "---\nformat_version: 1\ndownload_dir: dwn\napplications_dir: app\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"20.04\"\n"
+    );
+}
+
+#[test]
+fn config_set_keep_unknown_keys() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let filename = add_config_file(io_helper);
+
+    io_helper.files.borrow_mut().insert(
+        filename.clone(),
+        format!("{}old_note: \"keep me\"\n", CONFIG_DATA),
+    );
+
+    let args = [
+        String::from("set"),
+        String::from("my_note"),
+        String::from("x"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(config(cmd_param));
+
+    assert_eq!(
+        io_helper.files.borrow().get(&filename).unwrap(),
+        "---\nformat_version: 1\ndownload_dir: dwn\napplications_dir: app\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\nold_note: keep me\nmy_note: x\n"
+    );
+}
+
+#[test]
+fn config_unset_value() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let filename = add_config_file(io_helper);
+
+    io_helper.files.borrow_mut().insert(
+        filename.clone(),
+        format!("{}tmp_dir: \"/var/tmp\"\n", CONFIG_DATA),
+    );
+
+    let args = [String::from("unset"), String::from("tmp_dir")];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(config(cmd_param));
+
+    assert!(!io_helper
+        .files
+        .borrow()
+        .get(&filename)
+        .unwrap()
+        .contains("tmp_dir"));
+
+    let args = [String::from("unset"), String::from("tmp_dir")];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(config(cmd_param), CommandExitCode::ConfigKeyNotFound);
+}
+
+#[test]
+fn config_edit_again_after_error() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let filename = add_config_file(io_helper);
+    let new_data = format!("{}tmp_dir: \"/var/tmp\"\n", CONFIG_DATA);
+
+    io_helper
+        .edits
+        .borrow_mut()
+        .push(String::from("---\ndownload_dir: [\"dwn\"\n"));
+    io_helper.edits.borrow_mut().push(new_data.clone());
+    io_helper.stdin.borrow_mut().push(String::from(""));
+
+    let args = [String::from("edit")];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(config(cmd_param));
+
+    assert!(io_helper.stderr.borrow()[0].starts_with("/home/d-sh/.d-sh/config.yml:3:1: "));
+    assert_eq!(io_helper.files.borrow().get(&filename).unwrap(), &new_data);
+}
+
+#[test]
+fn config_edit_restore_file() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let filename = add_config_file(io_helper);
+
+    io_helper
+        .edits
+        .borrow_mut()
+        .push(format!("{}container_engine: \"rocket\"\n", CONFIG_DATA));
+    io_helper.stdin.borrow_mut().push(String::from("n"));
+
+    let args = [String::from("edit")];
+
+    let cmd_param = CommandParameter {
+        command: &CONFIG,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(config(cmd_param), CommandExitCode::ConfigFileFormatWrong);
+
+    assert_eq!(
+        io_helper.files.borrow().get(&filename).unwrap(),
+        CONFIG_DATA
+    );
+}
@@ -1,5 +1,5 @@
-use command::{Command, CommandError, CommandExitCode, CommandParameter};
-use config::{get_config_application, get_filename, Config};
+use command::{get_helper_command_error, Command, CommandError, CommandExitCode, CommandParameter};
+use config::{get_application_filename, get_application_filenames, get_config_application, Config};
 ///
 /// Module to delete image.
 ///
@@ -22,20 +22,20 @@ fn delete_one(
     config: &Config,
     app: &str,
 ) -> Result<(), CommandError> {
-    let application_filename_full_path = get_filename(&config.applications_dir, app, Some(&".yml"));
+    let application_filename_full_path = get_application_filename(cmd_param.io_helper, config, app);
 
     match get_config_application(cmd_param.io_helper, &application_filename_full_path) {
         Ok(config_application) => {
-            if cmd_param
+            match cmd_param
                 .dck_helper
                 .remove_image(&config_application.image_name)
             {
-                Ok(())
-            } else {
-                Err(CommandError {
-                    msg: vec![String::from("Docker image not found")],
-                    code: CommandExitCode::ContainerImageNotFound,
-                })
+                Ok(_) => Ok(()),
+                Err(err) => Err(get_helper_command_error(
+                    format!("Unable to remove image of '{}'!", app),
+                    &err,
+                    CommandExitCode::ContainerImageNotFound,
+                )),
             }
         }
         Err(err) => Err(CommandError {
@@ -55,10 +55,7 @@ fn delete_one(
 fn delete_all(cmd_param: &CommandParameter, config: &Config) -> Result<(), CommandError> {
     let mut list_applications_file;
 
-    match cmd_param
-        .io_helper
-        .dir_list_file(&config.applications_dir, "*.yml")
-    {
+    match get_application_filenames(cmd_param.io_helper, config) {
         Ok(r) => list_applications_file = r,
         Err(err) => {
             return Err(CommandError {
@@ -1,9 +1,10 @@
 use super::{delete, DELETE};
-use command::tests::{test_result_err, test_result_ok};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
 use command::{CommandExitCode, CommandParameter};
-use config::{Config, ConfigDocker};
+use config::{Config, ConfigApplicationsDir, ConfigDocker};
 use docker::tests::TestContainerHelper;
 use download::tests::TestDownloadHelper;
+use helper::HelperError;
 ///
 /// Module to tests module delete.
 ///
@@ -22,12 +23,21 @@ fn delete_display_help() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     let cmd_param = CommandParameter {
@@ -37,6 +47,7 @@ fn delete_display_help() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(delete(cmd_param));
@@ -57,12 +68,21 @@ fn delete_one_application_ok() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -91,6 +111,7 @@ fn delete_one_application_ok() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(delete(cmd_param));
@@ -117,12 +138,21 @@ fn delete_one_application_ko() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -150,6 +180,7 @@ fn delete_one_application_ko() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(delete(cmd_param), CommandExitCode::ApplicationFileNotFound);
@@ -157,6 +188,65 @@ fn delete_one_application_ko() {
     assert_eq!("Not found", stderr.get(0).unwrap());
 }
 
+#[test]
+fn delete_one_application_daemon_unreachable() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("atom")];
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    // Create application file atom
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    *dck_helper.error.borrow_mut() = Some(HelperError::DaemonUnreachable(String::from(
+        "Cannot connect to the Docker daemon at unix:///var/run/docker.sock.",
+    )));
+
+    let cmd_param = CommandParameter {
+        command: &DELETE,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(delete(cmd_param), CommandExitCode::DaemonUnreachable);
+
+    assert_eq!(stderr.get(0).unwrap(), "Unable to remove image of 'atom'!");
+    assert_eq!(
+        stderr.get(1).unwrap(),
+        "Daemon unreachable: Cannot connect to the Docker daemon at unix:///var/run/docker.sock."
+    );
+}
+
 #[test]
 fn delete_one_application_all() {
     let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
@@ -168,12 +258,21 @@ fn delete_one_application_all() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -202,6 +301,7 @@ fn delete_one_application_all() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(delete(cmd_param));
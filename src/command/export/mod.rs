@@ -0,0 +1,406 @@
+///
+/// Module to export images and files of applications in a bundle.
+///
+/// Release under MIT License.
+///
+use command::{
+    create_command_tmp_dir, get_helper_command_error, get_image_command_error,
+    remove_command_tmp_dir, Command, CommandError, CommandExitCode, CommandParameter,
+};
+use config::{
+    get_application_base_name, get_application_filename, get_config_application, get_config_base,
+    get_filename, Config,
+};
+use docker::remote::get_application_endpoint;
+use docker::ContainerHelper;
+use io::convert_path;
+
+#[cfg(test)]
+mod tests;
+
+/// Version of format of bundle.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+/// Manifest of bundle.
+pub const BUNDLE_MANIFEST_FILENAME: &str = "manifest.yml";
+/// Docker images of bundle.
+pub const BUNDLE_IMAGES_FILENAME: &str = "images.tar";
+/// Folder of application files in bundle.
+pub const BUNDLE_APPLICATIONS_DIR: &str = "applications";
+/// Folder of downloaded files in bundle.
+pub const BUNDLE_DOWNLOADS_DIR: &str = "downloads";
+
+const UNKOWN_OPTIONS_MESSAGE: &'static str =
+    "d-sh export: invalid option '{}'\nTry 'd-sh export --help' for more information.\n";
+
+///
+/// Application in bundle.
+///
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleApplication {
+    /// Name of application
+    pub name: String,
+    /// Image of application
+    pub image_name: String,
+    /// Name of base image
+    pub base: String,
+    /// Tag of base image
+    pub base_tag: String,
+    /// Downloaded file, if found
+    pub download_filename: Option<String>,
+    /// File of images if application has its own Docker daemon, else images are in
+    /// `images.tar`
+    pub images_filename: Option<String>,
+}
+
+///
+/// Manifest of bundle.
+///
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Version of format of bundle
+    pub format_version: u32,
+    /// Docker images saved in `images.tar`
+    pub images: Vec<String>,
+    /// Applications of bundle
+    pub applications: Vec<BundleApplication>,
+}
+
+///
+/// Save images in file of bundle folder.
+///
+fn save_bundle_images(
+    dck_helper: &ContainerHelper,
+    bundle_dir: &str,
+    images: &[String],
+    filename: &str,
+) -> Result<(), CommandError> {
+    match dck_helper.save_images(images, &format!("{}/{}", bundle_dir, filename)) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(get_helper_command_error(
+            format!("Unable to save images '{}'!", images.join("', '")),
+            &err,
+            CommandExitCode::CannotSaveImage,
+        )),
+    }
+}
+
+///
+/// Copy application file and downloaded file of one application in bundle folder.
+///
+/// Images of application with its own Docker daemon are saved in their own file.
+///
+fn export_application_files(
+    cmd_param: &CommandParameter,
+    config: &Config,
+    bundle_dir: &str,
+    app: &str,
+) -> Result<BundleApplication, CommandError> {
+    let application_filename = get_application_filename(cmd_param.io_helper, config, app);
+
+    let config_application =
+        match get_config_application(cmd_param.io_helper, &application_filename) {
+            Ok(r) => r,
+            Err(err) => {
+                return Err(CommandError {
+                    msg: vec![format!("{}", err)],
+                    code: CommandExitCode::ApplicationFileNotFound,
+                });
+            }
+        };
+
+    // Application can be build on its own Docker daemon
+    let app_helper = get_application_endpoint(&config_application)
+        .map(|endpoint| cmd_param.dck_helper.with_endpoint(&endpoint));
+    let dck_helper = match app_helper {
+        Some(ref h) => &**h,
+        None => cmd_param.dck_helper,
+    };
+
+    if dck_helper
+        .list_image(&config_application.image_name)
+        .map_err(|err| get_image_command_error(&config_application.image_name, &err))?
+        .is_empty()
+    {
+        return Err(CommandError {
+            msg: vec![format!(
+                "Image of '{}' not found! Please run 'd-sh build {}' first.",
+                app, app
+            )],
+            code: CommandExitCode::ContainerImageNotFound,
+        });
+    }
+
+    let base_name = get_application_base_name(&config_application);
+
+    let base_tag = match get_config_base(config, base_name) {
+        Some(b) => b.tag.clone(),
+        None => {
+            return Err(CommandError {
+                msg: vec![format!(
+                    "Base image '{}' of '{}' not found in config file!",
+                    base_name, app
+                )],
+                code: CommandExitCode::BaseImageNotFound,
+            });
+        }
+    };
+
+    let mut files = vec![(
+        application_filename,
+        get_filename(
+            &format!("{}/{}", bundle_dir, BUNDLE_APPLICATIONS_DIR),
+            app,
+            Some(&".yml"),
+        ),
+    )];
+
+    let app_dwn_filename = convert_path(&get_filename(
+        &config.download_dir,
+        &config_application.download_filename,
+        None,
+    ));
+
+    let download_filename = if !config_application.download_filename.is_empty()
+        && cmd_param.io_helper.file_exits(&app_dwn_filename)
+    {
+        files.push((
+            app_dwn_filename,
+            get_filename(
+                &format!("{}/{}", bundle_dir, BUNDLE_DOWNLOADS_DIR),
+                &config_application.download_filename,
+                None,
+            ),
+        ));
+
+        Some(config_application.download_filename.clone())
+    } else {
+        None
+    };
+
+    for (from, to) in files {
+        if let Err(err) = cmd_param.io_helper.hardlink_or_copy_file(&from, &to) {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Unable to copy file '{}' to '{}'!", from, to),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotCopyFile,
+            });
+        }
+    }
+
+    let images_filename = match app_helper {
+        Some(_) => {
+            let filename = format!("images-{}.tar", app);
+
+            cmd_param
+                .io_helper
+                .println(&format!("Saving images of {}...", app));
+
+            save_bundle_images(
+                dck_helper,
+                bundle_dir,
+                &[base_tag.clone(), config_application.image_name.clone()],
+                &filename,
+            )?;
+
+            Some(filename)
+        }
+        None => None,
+    };
+
+    Ok(BundleApplication {
+        name: String::from(app),
+        image_name: config_application.image_name.clone(),
+        base: String::from(base_name),
+        base_tag: base_tag,
+        download_filename: download_filename,
+        images_filename: images_filename,
+    })
+}
+
+///
+/// Generate bundle folder and create archive.
+///
+fn export_bundle(
+    cmd_param: &CommandParameter,
+    config: &Config,
+    bundle_dir: &str,
+    applications: &Vec<String>,
+    output: &str,
+) -> Result<(), CommandError> {
+    for dir in &[BUNDLE_APPLICATIONS_DIR, BUNDLE_DOWNLOADS_DIR] {
+        let dir = format!("{}/{}", bundle_dir, dir);
+
+        if let Err(err) = cmd_param.io_helper.create_dir_all(&dir) {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Cannot create folder '{}'!", dir),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotCreateFolder,
+            });
+        }
+    }
+
+    let mut manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        images: Vec::new(),
+        applications: Vec::new(),
+    };
+
+    for app in applications {
+        let bundle_application = export_application_files(cmd_param, config, bundle_dir, app)?;
+
+        // Base image is shared by many applications
+        if bundle_application.images_filename.is_none() {
+            for image in &[&bundle_application.base_tag, &bundle_application.image_name] {
+                if !manifest.images.contains(image) {
+                    manifest.images.push(image.to_string());
+                }
+            }
+        }
+
+        manifest.applications.push(bundle_application);
+    }
+
+    // All applications can have their own Docker daemon
+    if !manifest.images.is_empty() {
+        cmd_param.io_helper.println("Saving images...");
+
+        save_bundle_images(
+            cmd_param.dck_helper,
+            bundle_dir,
+            &manifest.images,
+            BUNDLE_IMAGES_FILENAME,
+        )?;
+    }
+
+    let manifest_filename = format!("{}/{}", bundle_dir, BUNDLE_MANIFEST_FILENAME);
+
+    // Serialize of struct without map cannot fail
+    let manifest_content = serde_yaml::to_string(&manifest).unwrap();
+
+    if let Err(err) = cmd_param
+        .io_helper
+        .file_write(&manifest_filename, &manifest_content)
+    {
+        return Err(CommandError {
+            msg: vec![
+                format!("Unable to write file '{}'!", manifest_filename),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotCreateArchive,
+        });
+    }
+
+    if let Err(err) = cmd_param.io_helper.archive_create(output, bundle_dir) {
+        return Err(CommandError {
+            msg: vec![
+                format!("Unable to create archive '{}'!", output),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotCreateArchive,
+        });
+    }
+
+    Ok(())
+}
+
+///
+/// Function to implement export D-SH command.
+///
+/// `args` parameter is command line arguments of D-SH.
+///
+/// returning exit code of D-SH.
+///
+fn export(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    let mut applications: Vec<String> = Vec::new();
+    let mut output: Option<String> = None;
+    let mut args = cmd_param.args.iter();
+
+    while let Some(argument) = args.next() {
+        match argument.as_ref() {
+            "-h" | "--help" => {
+                cmd_param.io_helper.println(cmd_param.command.usage);
+                return Ok(());
+            }
+            "-o" | "--output" => match args.next() {
+                Some(o) => output = Some(convert_path(o)),
+                None => {
+                    return Err(CommandError {
+                        msg: vec![String::from("d-sh export: option '--output' need a file")],
+                        code: CommandExitCode::BadArgument,
+                    });
+                }
+            },
+            other if other.starts_with("-") => {
+                return Err(CommandError {
+                    msg: vec![UNKOWN_OPTIONS_MESSAGE.replace("{}", other)],
+                    code: CommandExitCode::UnknowOption,
+                });
+            }
+            app => applications.push(String::from(app)),
+        }
+    }
+
+    let output = match output {
+        Some(o) => o,
+        None => {
+            return Err(CommandError {
+                msg: vec![String::from("d-sh export: option '--output' is required")],
+                code: CommandExitCode::BadArgument,
+            });
+        }
+    };
+
+    if applications.is_empty() {
+        return Err(CommandError {
+            msg: vec![String::from("d-sh export: no application to export")],
+            code: CommandExitCode::ApplicationNameMissing,
+        });
+    }
+
+    let config = cmd_param.config.unwrap();
+
+    let bundle_dir = create_command_tmp_dir(cmd_param.io_helper, &config)?;
+
+    let result = export_bundle(&cmd_param, &config, &bundle_dir, &applications, &output);
+
+    remove_command_tmp_dir(cmd_param.io_helper, &bundle_dir);
+
+    if result.is_ok() {
+        cmd_param
+            .io_helper
+            .println(&format!("Applications exported in '{}'.", output));
+    }
+
+    result
+}
+
+///
+/// The `export` command.
+///
+pub const EXPORT: Command = Command {
+    /// This command call by `export`.
+    name: "export",
+    /// description.
+    description: "Export images and files of applications in an archive",
+    /// Short name.
+    short_name: "exp",
+    /// `export` command need applications and output file.
+    min_args: 1,
+    max_args: usize::MAX,
+    /// `export` command help.
+    usage: "
+    Usage:	d-sh export PROGRAM1 PROGRAM2 ... -o FILE
+
+    Export images, application files and downloaded files in a tar archive, to import them with 'd-sh import' on another computer
+
+    Options:
+      -o, --output FILE        Archive to create
+      -h, --help               Display this help
+",
+    need_config_file: true,
+    exec_cmd: export,
+};
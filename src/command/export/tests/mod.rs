@@ -0,0 +1,263 @@
+///
+/// Module to tests module export.
+///
+/// Release under MIT License.
+///
+use super::{export, BundleManifest, EXPORT};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::{self, Config, ConfigDocker};
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+use io::tests::TestInputOutputHelper;
+use std::collections::HashMap;
+
+fn create_config() -> Config {
+    let mut bases = HashMap::new();
+
+    bases.insert(
+        String::from("fedora"),
+        ConfigDocker {
+            from: String::from("fedora:29"),
+            tag: String::from("d-base-fedora:latest"),
+            template: None,
+            package_manager: None,
+        },
+    );
+
+    Config {
+        bases: Some(bases),
+        tmp_dir: Some(String::from("tmp")),
+        ..config::tests::create_config()
+    }
+}
+
+///
+/// Create applications atom (default base) and filezilla (fedora base) with their images.
+///
+fn create_applications(io_helper: &TestInputOutputHelper, dck_helper: &TestContainerHelper) {
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"\""));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.tar.bz2\"\nurl: \"\"\nbase: fedora"));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom.deb"), String::from("atom"));
+
+    for image in &[
+        "tutu",
+        "d-base-fedora:latest",
+        "run-atom:latest",
+        "run-filezilla:latest",
+    ] {
+        dck_helper.images.borrow_mut().push(String::from(*image));
+    }
+}
+
+#[test]
+fn export_applications_in_archive() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    create_applications(io_helper, dck_helper);
+
+    let args = [
+        String::from("atom"),
+        String::from("filezilla"),
+        String::from("-o"),
+        String::from("bundle.tar"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &EXPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(export(cmd_param));
+
+    // Images
+    let saves = dck_helper.saves.borrow();
+
+    assert_eq!(
+        saves.get("tmp/d-sh-0/images.tar").unwrap(),
+        &vec![
+            String::from("tutu"),
+            String::from("run-atom:latest"),
+            String::from("d-base-fedora:latest"),
+            String::from("run-filezilla:latest"),
+        ]
+    );
+
+    // Files, filezilla is not downloaded
+    let archives = io_helper.archives.borrow();
+    let archive = archives.get("bundle.tar").unwrap();
+
+    assert_eq!(archive.len(), 4);
+    assert!(archive.contains_key("applications/atom.yml"));
+    assert!(archive.contains_key("applications/filezilla.yml"));
+    assert_eq!(archive.get("downloads/atom.deb").unwrap(), "atom");
+
+    let manifest: BundleManifest =
+        serde_yaml::from_str(archive.get("manifest.yml").unwrap()).unwrap();
+
+    assert_eq!(manifest.applications.len(), 2);
+    assert_eq!(manifest.applications[0].name, "atom");
+    assert_eq!(
+        manifest.applications[0].download_filename,
+        Some(String::from("atom.deb"))
+    );
+    assert_eq!(manifest.applications[1].name, "filezilla");
+    assert_eq!(manifest.applications[1].base, "fedora");
+    assert_eq!(manifest.applications[1].download_filename, None);
+
+    // Temporary folder is removed
+    assert!(io_helper
+        .files_delete
+        .borrow()
+        .contains_key("tmp/d-sh-0/manifest.yml"));
+    assert_eq!(
+        io_helper.stdout.borrow().last().unwrap(),
+        "Applications exported in 'bundle.tar'."
+    );
+}
+
+#[test]
+fn export_fail_if_image_not_build() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"\""));
+
+    let args = [
+        String::from("atom"),
+        String::from("--output"),
+        String::from("bundle.tar"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &EXPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(export(cmd_param), CommandExitCode::ContainerImageNotFound);
+
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "Image of 'atom' not found! Please run 'd-sh build atom' first."
+    );
+    assert!(io_helper.archives.borrow().is_empty());
+}
+
+#[test]
+fn export_fail_without_output() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    let args = [String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &EXPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(export(cmd_param), CommandExitCode::BadArgument);
+
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "d-sh export: option '--output' is required"
+    );
+}
+
+#[test]
+fn export_application_on_its_docker_daemon() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    create_applications(io_helper, dck_helper);
+
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.tar.bz2\"\nurl: \"\"\nbase: fedora\ndocker_host: \"ssh://me@workstation\""));
+
+    let args = [
+        String::from("atom"),
+        String::from("filezilla"),
+        String::from("-o"),
+        String::from("bundle.tar"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &EXPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(export(cmd_param));
+
+    // Images of filezilla are saved from its Docker daemon
+    let endpoints = dck_helper.endpoints.borrow();
+
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(
+        endpoints[0].host,
+        Some(String::from("ssh://me@workstation"))
+    );
+
+    let saves = dck_helper.saves.borrow();
+
+    assert_eq!(
+        saves.get("tmp/d-sh-0/images.tar").unwrap(),
+        &vec![String::from("tutu"), String::from("run-atom:latest")]
+    );
+    assert_eq!(
+        saves.get("tmp/d-sh-0/images-filezilla.tar").unwrap(),
+        &vec![
+            String::from("d-base-fedora:latest"),
+            String::from("run-filezilla:latest"),
+        ]
+    );
+
+    let archives = io_helper.archives.borrow();
+    let manifest: BundleManifest = serde_yaml::from_str(
+        archives
+            .get("bundle.tar")
+            .unwrap()
+            .get("manifest.yml")
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(manifest.applications[0].images_filename, None);
+    assert_eq!(
+        manifest.applications[1].images_filename,
+        Some(String::from("images-filezilla.tar"))
+    );
+}
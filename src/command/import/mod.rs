@@ -0,0 +1,372 @@
+///
+/// Module to import images and files of applications from a bundle.
+///
+/// Release under MIT License.
+///
+use command::export::{
+    BundleManifest, BUNDLE_APPLICATIONS_DIR, BUNDLE_DOWNLOADS_DIR, BUNDLE_FORMAT_VERSION,
+    BUNDLE_IMAGES_FILENAME, BUNDLE_MANIFEST_FILENAME,
+};
+use command::{
+    create_command_tmp_dir, get_helper_command_error, remove_command_tmp_dir, Command,
+    CommandError, CommandExitCode, CommandParameter,
+};
+use config::{
+    get_application_filename, get_config_application, get_config_base, get_filename, Config,
+};
+use docker::remote::get_application_endpoint;
+use docker::ContainerHelper;
+use io::convert_path;
+
+#[cfg(test)]
+mod tests;
+
+const UNKOWN_OPTIONS_MESSAGE: &'static str =
+    "d-sh import: invalid option '{}'\nTry 'd-sh import --help' for more information.\n";
+
+///
+/// Return true if name can be used as file name in a folder, without going out of folder.
+///
+fn is_safe_filename(name: &str) -> bool {
+    !(name.is_empty() || name == "." || name == ".." || name.contains('/') || name.contains('\\'))
+}
+
+///
+/// Read manifest of extracted bundle.
+///
+fn read_manifest(
+    cmd_param: &CommandParameter,
+    bundle_dir: &str,
+    archive: &str,
+) -> Result<BundleManifest, CommandError> {
+    let manifest_filename = format!("{}/{}", bundle_dir, BUNDLE_MANIFEST_FILENAME);
+
+    let manifest: BundleManifest = match cmd_param.io_helper.file_read_at_string(&manifest_filename)
+    {
+        Ok(data) => match serde_yaml::from_str(&data) {
+            Ok(m) => m,
+            Err(err) => {
+                return Err(CommandError {
+                    msg: vec![
+                        format!("Bad format of manifest in '{}'!", archive),
+                        format!("{}", err),
+                    ],
+                    code: CommandExitCode::CannotReadArchive,
+                });
+            }
+        },
+        Err(_) => {
+            return Err(CommandError {
+                msg: vec![format!("'{}' is not a D-SH archive!", archive)],
+                code: CommandExitCode::CannotReadArchive,
+            });
+        }
+    };
+
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(CommandError {
+            msg: vec![format!(
+                "Archive '{}' is created by a newer version of D-SH!",
+                archive
+            )],
+            code: CommandExitCode::CannotReadArchive,
+        });
+    }
+
+    // Files of bundle are copied in folders of user, a name must not go out of folder
+    for app in &manifest.applications {
+        let mut names = app
+            .download_filename
+            .iter()
+            .chain(app.images_filename.iter())
+            .chain(Some(&app.name));
+
+        if let Some(name) = names.find(|n| !is_safe_filename(n)) {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Bad format of manifest in '{}'!", archive),
+                    format!("File name '{}' is not allowed.", name),
+                ],
+                code: CommandExitCode::CannotReadArchive,
+            });
+        }
+    }
+
+    Ok(manifest)
+}
+
+///
+/// Copy file from bundle to target folder.
+///
+fn copy_bundle_file(
+    cmd_param: &CommandParameter,
+    from: &str,
+    to: &str,
+) -> Result<(), CommandError> {
+    match cmd_param.io_helper.hardlink_or_copy_file(from, to) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(CommandError {
+            msg: vec![
+                format!("Unable to copy file '{}' to '{}'!", from, to),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotCopyFile,
+        }),
+    }
+}
+
+///
+/// Load images of file of bundle folder.
+///
+fn load_bundle_images(
+    dck_helper: &ContainerHelper,
+    bundle_dir: &str,
+    filename: &str,
+    archive: &str,
+) -> Result<(), CommandError> {
+    match dck_helper.load_images(&format!("{}/{}", bundle_dir, filename)) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(get_helper_command_error(
+            format!("Unable to load images of archive '{}'!", archive),
+            &err,
+            CommandExitCode::CannotLoadImage,
+        )),
+    }
+}
+
+///
+/// Extract bundle, load images and copy files of applications.
+///
+fn import_bundle(
+    cmd_param: &CommandParameter,
+    config: &Config,
+    bundle_dir: &str,
+    archive: &str,
+    force: bool,
+) -> Result<(), CommandError> {
+    if let Err(err) = cmd_param.io_helper.archive_extract(archive, bundle_dir) {
+        return Err(CommandError {
+            msg: vec![
+                format!("Unable to extract archive '{}'!", archive),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotReadArchive,
+        });
+    }
+
+    let manifest = read_manifest(cmd_param, bundle_dir, archive)?;
+
+    // Check before load images to not import half of bundle
+    if !force {
+        let existing: Vec<&str> = manifest
+            .applications
+            .iter()
+            .filter(|app| {
+                cmd_param
+                    .io_helper
+                    .file_exits(&convert_path(&get_application_filename(
+                        cmd_param.io_helper,
+                        config,
+                        &app.name,
+                    )))
+            })
+            .map(|app| app.name.as_str())
+            .collect();
+
+        if !existing.is_empty() {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Applications '{}' already exist!", existing.join("', '")),
+                    String::from("Use '--force' to replace them."),
+                ],
+                code: CommandExitCode::ApplicationFileExits,
+            });
+        }
+    }
+
+    // Archive can have only applications with their own Docker daemon
+    if !manifest.images.is_empty() {
+        cmd_param.io_helper.println("Loading images...");
+
+        load_bundle_images(
+            cmd_param.dck_helper,
+            bundle_dir,
+            BUNDLE_IMAGES_FILENAME,
+            archive,
+        )?;
+    }
+
+    for app in &manifest.applications {
+        if let Some(ref images_filename) = app.images_filename {
+            let application_filename = get_filename(
+                &format!("{}/{}", bundle_dir, BUNDLE_APPLICATIONS_DIR),
+                &app.name,
+                Some(&".yml"),
+            );
+
+            let config_application =
+                match get_config_application(cmd_param.io_helper, &application_filename) {
+                    Ok(r) => r,
+                    Err(err) => {
+                        return Err(CommandError {
+                            msg: vec![
+                                format!("Bad format of application in '{}'!", archive),
+                                format!("{}", err),
+                            ],
+                            code: CommandExitCode::CannotReadArchive,
+                        });
+                    }
+                };
+
+            // Images are loaded on Docker daemon of application
+            let app_helper = get_application_endpoint(&config_application)
+                .map(|endpoint| cmd_param.dck_helper.with_endpoint(&endpoint));
+            let dck_helper = match app_helper {
+                Some(ref h) => &**h,
+                None => cmd_param.dck_helper,
+            };
+
+            cmd_param
+                .io_helper
+                .println(&format!("Loading images of {}...", app.name));
+
+            load_bundle_images(dck_helper, bundle_dir, images_filename, archive)?;
+        }
+    }
+
+    let download_dir = convert_path(&config.download_dir);
+    let applications_dir = convert_path(config.applications_dir.main_dir());
+
+    for dir in &[&download_dir, &applications_dir] {
+        if let Err(err) = cmd_param.io_helper.create_dir_all(dir) {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Cannot create folder '{}'!", dir),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotCreateFolder,
+            });
+        }
+    }
+
+    for app in &manifest.applications {
+        copy_bundle_file(
+            cmd_param,
+            &get_filename(
+                &format!("{}/{}", bundle_dir, BUNDLE_APPLICATIONS_DIR),
+                &app.name,
+                Some(&".yml"),
+            ),
+            &get_filename(&applications_dir, &app.name, Some(&".yml")),
+        )?;
+
+        if let Some(ref download_filename) = app.download_filename {
+            copy_bundle_file(
+                cmd_param,
+                &get_filename(
+                    &format!("{}/{}", bundle_dir, BUNDLE_DOWNLOADS_DIR),
+                    download_filename,
+                    None,
+                ),
+                &get_filename(&download_dir, download_filename, None),
+            )?;
+        }
+
+        if get_config_base(config, &app.base).is_none() {
+            // Non blocking error, image can be run but not rebuild
+            cmd_param.io_helper.eprintln(&format!(
+                "Base image '{}' of '{}' not found in config file, add it to rebuild application.",
+                app.base, app.name
+            ));
+        }
+
+        cmd_param
+            .io_helper
+            .println(&format!("Application '{}' imported.", app.name));
+    }
+
+    Ok(())
+}
+
+///
+/// Function to implement import D-SH command.
+///
+/// `args` parameter is command line arguments of D-SH.
+///
+/// returning exit code of D-SH.
+///
+fn import(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    let mut archive: Option<String> = None;
+    let mut force = false;
+
+    for argument in cmd_param.args {
+        match argument.as_ref() {
+            "-h" | "--help" => {
+                cmd_param.io_helper.println(cmd_param.command.usage);
+                return Ok(());
+            }
+            "-f" | "--force" => force = true,
+            other if other.starts_with("-") => {
+                return Err(CommandError {
+                    msg: vec![UNKOWN_OPTIONS_MESSAGE.replace("{}", other)],
+                    code: CommandExitCode::UnknowOption,
+                });
+            }
+            a => archive = Some(convert_path(a)),
+        }
+    }
+
+    let archive = match archive {
+        Some(a) => a,
+        None => {
+            return Err(CommandError {
+                msg: vec![String::from("d-sh import: archive file is missing")],
+                code: CommandExitCode::BadArgument,
+            });
+        }
+    };
+
+    if !cmd_param.io_helper.file_exits(&archive) {
+        return Err(CommandError {
+            msg: vec![format!("Archive '{}' not found!", archive)],
+            code: CommandExitCode::CannotReadArchive,
+        });
+    }
+
+    let config = cmd_param.config.unwrap();
+
+    let bundle_dir = create_command_tmp_dir(cmd_param.io_helper, &config)?;
+
+    let result = import_bundle(&cmd_param, &config, &bundle_dir, &archive, force);
+
+    remove_command_tmp_dir(cmd_param.io_helper, &bundle_dir);
+
+    result
+}
+
+///
+/// The `import` command.
+///
+pub const IMPORT: Command = Command {
+    /// This command call by `import`.
+    name: "import",
+    /// description.
+    description: "Import images and files of applications from an archive",
+    /// Short name.
+    short_name: "imp",
+    /// `import` command need archive file.
+    min_args: 1,
+    max_args: 2,
+    /// `import` command help.
+    usage: "
+    Usage:	d-sh import [OPTIONS] FILE
+
+    Import images, application files and downloaded files from an archive created by 'd-sh export'
+
+    Options:
+      -f, --force              Replace existing applications
+      -h, --help               Display this help
+",
+    need_config_file: true,
+    exec_cmd: import,
+};
@@ -0,0 +1,395 @@
+///
+/// Module to tests module import.
+///
+/// Release under MIT License.
+///
+use super::{import, IMPORT};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::{self, Config, ConfigApplicationsDir};
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+use io::convert_path;
+use io::tests::{found_item, TestInputOutputHelper};
+use std::collections::HashMap;
+
+fn create_config() -> Config {
+    Config {
+        tmp_dir: Some(String::from("tmp")),
+        ..config::tests::create_config()
+    }
+}
+
+///
+/// Create archive `bundle.tar` with atom (default base) and filezilla (fedora base).
+///
+fn create_bundle(io_helper: &TestInputOutputHelper) {
+    let mut content = HashMap::new();
+
+    content.insert(String::from("manifest.yml"), String::from("---\nformat_version: 1\nimages:\n  - tutu\n  - run-atom:latest\n  - d-base-fedora:latest\n  - run-filezilla:latest\napplications:\n  - name: atom\n    image_name: \"run-atom:latest\"\n    base: default\n    base_tag: tutu\n    download_filename: atom.deb\n  - name: filezilla\n    image_name: \"run-filezilla:latest\"\n    base: fedora\n    base_tag: \"d-base-fedora:latest\"\n    download_filename: ~\n"));
+    content.insert(String::from("applications/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"\""));
+    content.insert(String::from("applications/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.tar.bz2\"\nurl: \"\"\nbase: fedora"));
+    content.insert(String::from("downloads/atom.deb"), String::from("atom"));
+
+    io_helper
+        .archives
+        .borrow_mut()
+        .insert(String::from("bundle.tar"), content);
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("bundle.tar"), String::new());
+}
+
+#[test]
+fn import_applications_from_archive() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    create_bundle(io_helper);
+
+    let args = [String::from("bundle.tar")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(import(cmd_param));
+
+    assert_eq!(
+        dck_helper.loads.borrow().get(0).unwrap(),
+        "tmp/d-sh-0/images.tar"
+    );
+
+    let files = io_helper.files.borrow();
+
+    assert_eq!(
+        files.get("app/atom.yml").unwrap(),
+        "---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"\""
+    );
+    assert!(files.contains_key("app/filezilla.yml"));
+    assert_eq!(files.get("dwn/atom.deb").unwrap(), "atom");
+
+    // Temporary folder is removed
+    assert!(!files.keys().any(|k| k.starts_with("tmp/")));
+
+    let stdout = io_helper.stdout.borrow();
+
+    found_item(&stdout, "Application 'atom' imported.");
+    found_item(&stdout, "Application 'filezilla' imported.");
+
+    // Base image fedora is not in config
+    let stderr = io_helper.stderr.borrow();
+
+    found_item(
+        &stderr,
+        "Base image 'fedora' of 'filezilla' not found in config file, add it to rebuild application.",
+    );
+}
+
+#[test]
+fn import_fail_if_application_exists() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    create_bundle(io_helper);
+
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("app/atom.yml"), String::from("old"));
+
+    let args = [String::from("bundle.tar")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(import(cmd_param), CommandExitCode::ApplicationFileExits);
+
+    assert_eq!(stderr.get(0).unwrap(), "Applications 'atom' already exist!");
+    assert!(dck_helper.loads.borrow().is_empty());
+    assert_eq!(io_helper.files.borrow().get("app/atom.yml").unwrap(), "old");
+
+    // With --force, application is replaced
+    let args = [String::from("bundle.tar"), String::from("--force")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(import(cmd_param));
+
+    assert_ne!(io_helper.files.borrow().get("app/atom.yml").unwrap(), "old");
+}
+
+#[test]
+fn import_fail_if_application_exists_in_home_folder() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = Config {
+        applications_dir: ConfigApplicationsDir::One(String::from("~/app")),
+        ..create_config()
+    };
+
+    create_bundle(io_helper);
+
+    let app_filename = convert_path("~/app/atom.yml");
+
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(app_filename.clone(), String::from("old"));
+
+    let args = [String::from("bundle.tar")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(import(cmd_param), CommandExitCode::ApplicationFileExits);
+
+    assert_eq!(stderr.get(0).unwrap(), "Applications 'atom' already exist!");
+    assert!(dck_helper.loads.borrow().is_empty());
+    assert_eq!(io_helper.files.borrow().get(&app_filename).unwrap(), "old");
+}
+
+#[test]
+fn import_fail_if_images_cannot_be_loaded() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    create_bundle(io_helper);
+
+    dck_helper
+        .loads_error
+        .borrow_mut()
+        .insert(String::from("tmp/d-sh-0/images.tar"), true);
+
+    let args = [String::from("bundle.tar")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(import(cmd_param), CommandExitCode::CannotLoadImage);
+
+    assert!(!io_helper.files.borrow().contains_key("app/atom.yml"));
+}
+
+#[test]
+fn import_fail_if_not_an_archive_of_d_sh() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper
+        .archives
+        .borrow_mut()
+        .insert(String::from("other.tar"), HashMap::new());
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("other.tar"), String::new());
+
+    let args = [String::from("other.tar")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(import(cmd_param), CommandExitCode::CannotReadArchive);
+
+    assert_eq!(stderr.get(0).unwrap(), "'other.tar' is not a D-SH archive!");
+}
+
+#[test]
+fn import_fail_if_manifest_has_path_in_file_name() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    let mut content = HashMap::new();
+
+    content.insert(String::from("manifest.yml"), String::from("---\nformat_version: 1\nimages:\n  - run-atom:latest\napplications:\n  - name: atom\n    image_name: \"run-atom:latest\"\n    base: default\n    base_tag: tutu\n    download_filename: ../../.bashrc\n"));
+    content.insert(String::from("applications/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"\""));
+    content.insert(
+        String::from("downloads/../../.bashrc"),
+        String::from("rm -rf ~"),
+    );
+
+    io_helper
+        .archives
+        .borrow_mut()
+        .insert(String::from("evil.tar"), content);
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("evil.tar"), String::new());
+
+    let args = [String::from("evil.tar")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(import(cmd_param), CommandExitCode::CannotReadArchive);
+
+    found_item(&stderr, "File name '../../.bashrc' is not allowed.");
+
+    // Nothing is loaded or copied
+    assert!(dck_helper.loads.borrow().is_empty());
+    assert!(!io_helper.files.borrow().contains_key("app/atom.yml"));
+    assert!(!io_helper
+        .files
+        .borrow()
+        .keys()
+        .any(|k| k.ends_with(".bashrc") && !k.starts_with("tmp/")));
+}
+
+#[test]
+fn import_fail_if_manifest_has_absolute_application_name() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    let mut content = HashMap::new();
+
+    content.insert(String::from("manifest.yml"), String::from("---\nformat_version: 1\nimages: []\napplications:\n  - name: /etc/cron.d/atom\n    image_name: \"run-atom:latest\"\n    base: default\n    base_tag: tutu\n    download_filename: ~\n"));
+
+    io_helper
+        .archives
+        .borrow_mut()
+        .insert(String::from("evil.tar"), content);
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("evil.tar"), String::new());
+
+    let args = [String::from("evil.tar")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(import(cmd_param), CommandExitCode::CannotReadArchive);
+
+    found_item(&stderr, "File name '/etc/cron.d/atom' is not allowed.");
+}
+
+#[test]
+fn import_application_on_its_docker_daemon() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    let mut content = HashMap::new();
+
+    content.insert(String::from("manifest.yml"), String::from("---\nformat_version: 1\nimages: []\napplications:\n  - name: atom\n    image_name: \"run-atom:latest\"\n    base: default\n    base_tag: tutu\n    download_filename: ~\n    images_filename: images-atom.tar\n"));
+    content.insert(String::from("applications/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"\"\ndocker_host: \"ssh://me@workstation\""));
+
+    io_helper
+        .archives
+        .borrow_mut()
+        .insert(String::from("bundle.tar"), content);
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("bundle.tar"), String::new());
+
+    let args = [String::from("bundle.tar")];
+
+    let cmd_param = CommandParameter {
+        command: &IMPORT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(import(cmd_param));
+
+    // Only images of atom are loaded, on its Docker daemon
+    assert_eq!(
+        *dck_helper.loads.borrow(),
+        vec![String::from("tmp/d-sh-0/images-atom.tar")]
+    );
+
+    let endpoints = dck_helper.endpoints.borrow();
+
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(
+        endpoints[0].host,
+        Some(String::from("ssh://me@workstation"))
+    );
+    assert!(io_helper.files.borrow().contains_key("app/atom.yml"));
+}
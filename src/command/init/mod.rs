@@ -8,8 +8,10 @@ use config::dockerfile::{
     DOCKERFILE_BASE, DOCKERFILE_BASE_FILENAME, DOCKERFILE_DEFAULT_FROM, DOCKERFILE_DEFAULT_TAG,
     ENTRYPOINT, ENTRYPOINT_FILENAME,
 };
+use config::layer::read_config_layer;
+use config::migration::{CONFIG_VERSION, FORMAT_VERSION_KEY};
 use config::{create_config_filename_path, get_config_filename};
-use io::InputOutputHelper;
+use io::{convert_path, InputOutputHelper};
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -21,7 +23,32 @@ const DOWNLOAD_DIR: &str = "~/.d-sh/download";
 /// Default directory to store applications.
 const APPLICATIONS_DIR: &str = "~/.d-sh/applications";
 
-fn create_dockerfile(io_helper: &InputOutputHelper) -> Result<(), CommandError> {
+///
+/// Options of init command.
+///
+struct InitOptions<'a> {
+    download_dir: Option<&'a str>,
+    applications_dir: Option<&'a str>,
+    from: Option<&'a str>,
+    tag: Option<&'a str>,
+    tmp_dir: Option<&'a str>,
+    /// Use default value instead of asking
+    yes: bool,
+    /// Only create missing folders and files
+    repair: bool,
+}
+
+const UNKOWN_OPTIONS_MESSAGE: &'static str =
+    "d-sh init: invalid option '{}'\nTry 'd-sh init --help' for more information.\n";
+
+///
+/// Create templates of Dockerfile. With `overwrite` false, existing files are kept.
+///
+fn create_dockerfile(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    overwrite: bool,
+) -> Result<(), CommandError> {
     let dockerfile_list: HashMap<&str, &str> = [
         (DOCKERFILE_BASE_FILENAME, DOCKERFILE_BASE),
         (ENTRYPOINT_FILENAME, ENTRYPOINT),
@@ -32,8 +59,12 @@ fn create_dockerfile(io_helper: &InputOutputHelper) -> Result<(), CommandError>
 
     // Create all docker file
     for (k, v) in &dockerfile_list {
-        match create_config_filename_path(&k) {
+        match create_config_filename_path(config_dir, &k) {
             Some(dockerfile_name) => {
+                if !overwrite && io_helper.file_exits(&dockerfile_name) {
+                    continue;
+                }
+
                 if let Err(err) = io_helper.file_write(&dockerfile_name, &v) {
                     return Err(CommandError {
                         msg: vec![format!("Unable to write file '{}'", k), format!("{}", err)],
@@ -53,6 +84,54 @@ fn create_dockerfile(io_helper: &InputOutputHelper) -> Result<(), CommandError>
     Ok(())
 }
 
+///
+/// Return folders of config file: download folder, applications folders and temporary folder.
+///
+/// Only folders set in this file are returned, other layers are not read.
+///
+fn get_config_folders(value: &serde_yaml::Value) -> Vec<String> {
+    let mut dirs = Vec::new();
+
+    for key in &["download_dir", "applications_dir", "tmp_dir"] {
+        match value.get(key) {
+            Some(serde_yaml::Value::String(d)) => dirs.push(d.clone()),
+            Some(serde_yaml::Value::Sequence(list)) => {
+                dirs.extend(list.iter().filter_map(|d| d.as_str()).map(String::from))
+            }
+            _ => (),
+        }
+    }
+
+    dirs
+}
+
+///
+/// Create missing folders.
+///
+fn create_folders(io_helper: &InputOutputHelper, dirs: &[String]) -> Result<(), CommandError> {
+    for dir in dirs {
+        let path = convert_path(dir);
+
+        if io_helper.file_exits(&path) {
+            continue;
+        }
+
+        if let Err(err) = io_helper.create_dir_all(&path) {
+            return Err(CommandError {
+                msg: vec![
+                    format!("Cannot create folder '{}'!", path),
+                    format!("{}", err),
+                ],
+                code: CommandExitCode::CannotCreateFolder,
+            });
+        }
+
+        io_helper.println(&format!("Folder '{}' created.", path));
+    }
+
+    Ok(())
+}
+
 ///
 /// Read a line from stdin.
 ///
@@ -79,16 +158,85 @@ fn read_line_with_default_value(
 }
 
 ///
-/// Function to implement check D-SH command.
+/// Return value of option, default value with `yes` option, else ask value to user.
+///
+fn get_value(
+    io_helper: &InputOutputHelper,
+    options: &InitOptions,
+    value: Option<&str>,
+    prompt: &str,
+    default_value: &str,
+) -> String {
+    match value {
+        Some(v) => String::from(v),
+        None if options.yes => String::from(default_value),
+        None => read_line_with_default_value(io_helper, prompt, default_value),
+    }
+}
+
+///
+/// Return value of option that need a value.
+///
+fn get_option_value<'a>(
+    args: &mut Iterator<Item = &'a String>,
+    option: &str,
+) -> Result<&'a str, CommandError> {
+    match args.next() {
+        Some(v) => Ok(v),
+        None => Err(CommandError {
+            msg: vec![format!("d-sh init: option '{}' need a value", option)],
+            code: CommandExitCode::BadArgument,
+        }),
+    }
+}
+
+///
+/// Function to implement init D-SH command.
 ///
 /// `args` parameter is command line arguments of D-SH.
 ///
 /// returning exit code of D-SH.
 ///
 fn init(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    let mut options = InitOptions {
+        download_dir: None,
+        applications_dir: None,
+        from: None,
+        tag: None,
+        tmp_dir: None,
+        yes: false,
+        repair: false,
+    };
+
+    let mut args = cmd_param.args.iter();
+
+    while let Some(argument) = args.next() {
+        match argument.as_ref() {
+            "-h" | "--help" => {
+                cmd_param.io_helper.println(cmd_param.command.usage);
+                return Ok(());
+            }
+            "-y" | "--yes" => options.yes = true,
+            "-f" | "--force" | "-r" | "--repair" => options.repair = true,
+            "--download-dir" => options.download_dir = Some(get_option_value(&mut args, argument)?),
+            "--applications-dir" => {
+                options.applications_dir = Some(get_option_value(&mut args, argument)?)
+            }
+            "--from" => options.from = Some(get_option_value(&mut args, argument)?),
+            "--tag" => options.tag = Some(get_option_value(&mut args, argument)?),
+            "--tmp-dir" => options.tmp_dir = Some(get_option_value(&mut args, argument)?),
+            other => {
+                return Err(CommandError {
+                    msg: vec![UNKOWN_OPTIONS_MESSAGE.replace("{}", other)],
+                    code: CommandExitCode::UnknowOption,
+                });
+            }
+        }
+    }
+
     let config_file;
 
-    match get_config_filename() {
+    match get_config_filename(cmd_param.config_dir) {
         Some(r) => config_file = r,
         None => {
             return Err(CommandError {
@@ -99,38 +247,92 @@ fn init(cmd_param: CommandParameter) -> Result<(), CommandError> {
     }
 
     if cmd_param.io_helper.file_exits(&config_file) {
-        return Err(CommandError {
-            msg: vec![format!(
-                "The file '{}' exits. Please remove it (or rename) and rerun this command.",
-                config_file
-            )],
-            code: CommandExitCode::ConfigFileExits,
-        });
+        if !options.repair {
+            return Err(CommandError {
+                msg: vec![
+                    format!(
+                        "The file '{}' exits. Please remove it (or rename) and rerun this command.",
+                        config_file
+                    ),
+                    String::from(
+                        "Use 'd-sh init --repair' to only create missing folders and files.",
+                    ),
+                ],
+                code: CommandExitCode::ConfigFileExits,
+            });
+        }
+
+        // Existing config is never changed. System and project config are not read.
+        let layer = match read_config_layer(cmd_param.io_helper, &config_file) {
+            Ok(r) => r,
+            Err(err) => {
+                return Err(CommandError {
+                    msg: vec![format!("{}", err)],
+                    code: CommandExitCode::ConfigFileFormatWrong,
+                });
+            }
+        };
+
+        create_folders(cmd_param.io_helper, &get_config_folders(&layer.value))?;
+
+        return create_dockerfile(cmd_param.io_helper, cmd_param.config_dir, false);
     }
 
-    let download_dir = read_line_with_default_value(
+    let download_dir = get_value(
         cmd_param.io_helper,
+        &options,
+        options.download_dir,
         "Enter the path of download directory",
         DOWNLOAD_DIR,
     );
-    let applications_dir = read_line_with_default_value(
+    let applications_dir = get_value(
         cmd_param.io_helper,
+        &options,
+        options.applications_dir,
         "Enter the path of applications directory",
         APPLICATIONS_DIR,
     );
-    let docker_image_from = read_line_with_default_value(
+    let docker_image_from = get_value(
         cmd_param.io_helper,
+        &options,
+        options.from,
         "Enter the default docker image from",
         DOCKERFILE_DEFAULT_FROM,
     );
-    let docker_image_tag = read_line_with_default_value(
+    let docker_image_tag = get_value(
         cmd_param.io_helper,
+        &options,
+        options.tag,
         "Enter the base image docker tag",
         DOCKERFILE_DEFAULT_TAG,
     );
 
-    let data = format!("---\ndownload_dir: \"{}\"\napplications_dir: \"{}\"\ndockerfile:\n  from: \"{}\"\n  tag: \"{}\"\n",
-        download_dir, applications_dir, docker_image_from, docker_image_tag);
+    let mut dockerfile = serde_yaml::Mapping::new();
+
+    dockerfile.insert("from".into(), docker_image_from.into());
+    dockerfile.insert("tag".into(), docker_image_tag.into());
+
+    let mut config = serde_yaml::Mapping::new();
+
+    config.insert(FORMAT_VERSION_KEY.into(), CONFIG_VERSION.into());
+    config.insert("download_dir".into(), download_dir.as_str().into());
+    config.insert("applications_dir".into(), applications_dir.as_str().into());
+    config.insert("dockerfile".into(), dockerfile.into());
+
+    if let Some(tmp_dir) = options.tmp_dir {
+        config.insert("tmp_dir".into(), tmp_dir.into());
+    }
+
+    // Values are quoted if needed, like `tag: "1.0"`
+    let data = match serde_yaml::to_string(&config) {
+        Ok(r) => format!("{}\n", r),
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![format!("{}", err)],
+                code: CommandExitCode::CannotWriteConfigFile,
+            });
+        }
+    };
 
     // Create folder
     let path = Path::new(&config_file);
@@ -158,7 +360,12 @@ fn init(cmd_param: CommandParameter) -> Result<(), CommandError> {
         });
     }
 
-    create_dockerfile(cmd_param.io_helper)
+    let mut folders = vec![download_dir, applications_dir];
+    folders.extend(options.tmp_dir.map(String::from));
+
+    create_folders(cmd_param.io_helper, &folders)?;
+
+    create_dockerfile(cmd_param.io_helper, cmd_param.config_dir, !options.repair)
 }
 
 ///
@@ -171,11 +378,27 @@ pub const INIT: Command = Command {
     description: "Initialize config file if not exists",
     /// Short name.
     short_name: "i",
-    /// `check` command have no parameter.
+    /// `init` command have only options.
     min_args: 0,
-    max_args: 0,
-    /// `check` command have no help.
-    usage: "",
+    max_args: usize::MAX,
+    /// `init` command help.
+    usage: "
+    Usage:	d-sh init [OPTIONS]
+
+    Create config file and templates of Dockerfile. Values not given in options are asked.
+
+    Options:
+      --download-dir DIR       Folder of downloaded files
+      --applications-dir DIR   Folder of application files
+      --from IMAGE             Image of default base image, like ubuntu:18.04
+      --tag TAG                Tag of default base image
+      --tmp-dir DIR            Temporary folder of build
+      -y, --yes                Use default value of options not given, never ask
+      -f, --force, -r, --repair
+                               If config file exists, only create missing folders and
+                               templates, existing files are never changed
+      -h, --help               Display this help
+",
     need_config_file: false,
     exec_cmd: init,
 };
@@ -1,7 +1,8 @@
 use super::{init, INIT};
-use command::tests::{test_result_err, test_result_ok};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
 use command::{CommandExitCode, CommandParameter};
-use config::{create_config_filename_path, get_config_filename};
+use config::layer::SYSTEM_CONFIG_FILE;
+use config::{create_config_filename_path, get_config_filename, Config};
 use docker::tests::TestContainerHelper;
 use download::tests::TestDownloadHelper;
 ///
@@ -9,7 +10,7 @@ use download::tests::TestDownloadHelper;
 ///
 /// Release under MIT License.
 ///
-use io::tests::TestInputOutputHelper;
+use io::tests::{found_item, TestInputOutputHelper};
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -21,7 +22,7 @@ fn unable_to_create_configfile_if_exists() {
 
     let args = [];
 
-    match get_config_filename() {
+    match get_config_filename(Some(TEST_CONFIG_DIR)) {
         Some(cfg_file) => {
             // Create file
             io_helper
@@ -39,11 +40,12 @@ fn unable_to_create_configfile_if_exists() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(init(cmd_param), CommandExitCode::ConfigFileExits);
 
-    assert_eq!("The file '/home/emeric/.d-sh/config.yml' exits. Please remove it (or rename) and rerun this command.", stderr.get(0).unwrap());
+    assert_eq!("The file '/home/d-sh/.d-sh/config.yml' exits. Please remove it (or rename) and rerun this command.", stderr.get(0).unwrap());
 }
 
 #[test]
@@ -66,17 +68,18 @@ fn create_configfile_if_not_exists() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(init(cmd_param));
 
-    match get_config_filename() {
+    match get_config_filename(Some(TEST_CONFIG_DIR)) {
         Some(cfg_file) => {
             let f = io_helper.files.borrow_mut();
             let v = f.get(&cfg_file);
 
             match v {
-                Some(c) => assert_eq!(c, &format!("---\ndownload_dir: \"toto\"\napplications_dir: \"titi\"\ndockerfile:\n  from: \"tata\"\n  tag: \"tutu\"\n")),
+                Some(c) => assert_eq!(c, &format!("---\nformat_version: 1\ndownload_dir: toto\napplications_dir: titi\ndockerfile:\n  from: tata\n  tag: tutu\n")),
                 None => panic!("The config file was not created")
             };
         }
@@ -95,7 +98,7 @@ fn create_configfile_if_not_exists() {
 
     // Create all docker file
     for (filename, content) in &dockerfile_list {
-        match create_config_filename_path(filename) {
+        match create_config_filename_path(Some(TEST_CONFIG_DIR), filename) {
             Some(dockerfile_name) => {
                 let v = f.get(&dockerfile_name);
 
@@ -122,7 +125,7 @@ fn create_configfile_but_cannot_write() {
 
     let args = [];
 
-    match get_config_filename() {
+    match get_config_filename(Some(TEST_CONFIG_DIR)) {
         Some(cfg_file) => {
             io_helper.files_error.borrow_mut().insert(cfg_file, true);
         }
@@ -136,6 +139,7 @@ fn create_configfile_but_cannot_write() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(init(cmd_param), CommandExitCode::CannotWriteConfigFile);
@@ -157,7 +161,7 @@ fn create_configfile_but_cannot_create_parent_folder() {
 
     let args = [];
 
-    match get_config_filename() {
+    match get_config_filename(Some(TEST_CONFIG_DIR)) {
         Some(cfg_file) => {
             let path = Path::new(&cfg_file);
 
@@ -178,6 +182,7 @@ fn create_configfile_but_cannot_create_parent_folder() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(
@@ -188,3 +193,179 @@ fn create_configfile_but_cannot_create_parent_folder() {
     assert!(stderr.get(0).unwrap().starts_with("Cannot create folder '"));
     assert_eq!("Cannot write", stderr.get(1).unwrap())
 }
+
+#[test]
+fn create_configfile_without_prompt() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [
+        String::from("--yes"),
+        String::from("--applications-dir"),
+        String::from("/opt/apps"),
+        String::from("--tmp-dir"),
+        String::from("/var/tmp"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &INIT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(init(cmd_param));
+
+    let cfg_file = get_config_filename(Some(TEST_CONFIG_DIR)).unwrap();
+
+    assert_eq!(
+        io_helper.files.borrow().get(&cfg_file).unwrap(),
+        "---\nformat_version: 1\ndownload_dir: \"~/.d-sh/download\"\napplications_dir: /opt/apps\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\ntmp_dir: /var/tmp\n"
+    );
+
+    let stdout = io_helper.stdout.borrow();
+
+    found_item(&stdout, "Folder '/opt/apps' created.");
+    found_item(&stdout, "Folder '/var/tmp' created.");
+}
+
+#[test]
+fn repair_create_only_missing_files() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let cfg_file = get_config_filename(Some(TEST_CONFIG_DIR)).unwrap();
+    let cfg_data = "---\nformat_version: 1\ndownload_dir: \"/opt/dwn\"\napplications_dir: \"/opt/apps\"\ndockerfile:\n  from: \"tata\"\n  tag: \"tutu\"\n";
+    let template =
+        create_config_filename_path(Some(TEST_CONFIG_DIR), super::DOCKERFILE_BASE_FILENAME)
+            .unwrap();
+    let entrypoint =
+        create_config_filename_path(Some(TEST_CONFIG_DIR), super::ENTRYPOINT_FILENAME).unwrap();
+
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(cfg_file.clone(), String::from(cfg_data));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(template.clone(), String::from("FROM {{dockerfile_from}}"));
+    // Applications folder exists
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("/opt/apps"), String::new());
+
+    let args = [String::from("--repair")];
+
+    let cmd_param = CommandParameter {
+        command: &INIT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(init(cmd_param));
+
+    let files = io_helper.files.borrow();
+
+    assert_eq!(files.get(&cfg_file).unwrap(), cfg_data);
+    assert_eq!(files.get(&template).unwrap(), "FROM {{dockerfile_from}}");
+    assert_eq!(files.get(&entrypoint).unwrap(), super::ENTRYPOINT);
+    assert_eq!(
+        *io_helper.stdout.borrow(),
+        vec![String::from("Folder '/opt/dwn' created.")]
+    );
+}
+
+#[test]
+fn create_configfile_quote_values() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [
+        String::from("--yes"),
+        String::from("--download-dir"),
+        String::from("/opt/my \"dwn\""),
+        String::from("--tag"),
+        String::from("1.0"),
+    ];
+
+    let cmd_param = CommandParameter {
+        command: &INIT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(init(cmd_param));
+
+    let cfg_file = get_config_filename(Some(TEST_CONFIG_DIR)).unwrap();
+    let files = io_helper.files.borrow();
+    let data = files.get(&cfg_file).unwrap();
+
+    // Tag stay a string, quote of folder is escaped
+    assert!(data.contains("  tag: \"1.0\"\n"));
+
+    let config: Config = serde_yaml::from_str(data).unwrap();
+
+    assert_eq!(config.download_dir, "/opt/my \"dwn\"");
+    assert_eq!(config.dockerfile.tag, "1.0");
+}
+
+#[test]
+fn repair_read_only_user_config_file() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let cfg_file = get_config_filename(Some(TEST_CONFIG_DIR)).unwrap();
+
+    // User config is partial, other values come from system config
+    io_helper.files.borrow_mut().insert(
+        cfg_file.clone(),
+        String::from(
+            "---\nformat_version: 1\napplications_dir:\n  - \"/opt/apps\"\n  - \"/opt/team\"\n",
+        ),
+    );
+    io_helper.files.borrow_mut().insert(
+        String::from(SYSTEM_CONFIG_FILE),
+        String::from(
+            "---\ndownload_dir: \"/srv/dwn\"\ndockerfile:\n  from: \"tata\"\n  tag: \"tutu\"\n",
+        ),
+    );
+
+    let args = [String::from("--repair")];
+
+    let cmd_param = CommandParameter {
+        command: &INIT,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(init(cmd_param));
+
+    assert_eq!(
+        *io_helper.stdout.borrow(),
+        vec![
+            String::from("Folder '/opt/apps' created."),
+            String::from("Folder '/opt/team' created."),
+        ]
+    );
+}
@@ -1,5 +1,6 @@
+use command::check::get_check_application;
 use command::{Command, CommandError, CommandExitCode, CommandParameter};
-use config::Config;
+use config::{get_application_filenames, Config};
 use io::InputOutputHelper;
 ///
 /// Module to list all application avaible.
@@ -7,10 +8,17 @@ use io::InputOutputHelper;
 /// Release under MIT License.
 ///
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(test)]
 mod tests;
 
+const UNKOWN_OPTIONS_MESSAGE: &'static str =
+    "d-sh list: invalid option '{}'\nTry 'd-sh list --help' for more information.\n";
+
+/// Units of image size, like `docker images`.
+const SIZE_UNITS: [&str; 5] = ["B", "kB", "MB", "GB", "TB"];
+
 ///
 /// Function to get all applications list.
 ///
@@ -22,7 +30,7 @@ pub fn get_all(
 ) -> Result<Vec<String>, CommandError> {
     let mut list_applications_file;
 
-    match io_helper.dir_list_file(&config.applications_dir, "*.yml") {
+    match get_application_filenames(io_helper, config) {
         Ok(r) => list_applications_file = r,
         Err(err) => {
             return Err(CommandError {
@@ -50,6 +58,50 @@ pub fn get_all(
     Ok(app_list)
 }
 
+///
+/// Format size of image, like `245.3MB`.
+///
+pub fn format_size(size: u64) -> String {
+    let mut value = size as f64;
+    let mut unit = 0;
+
+    while value >= 1000.0 && unit < SIZE_UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{}{}", size, SIZE_UNITS[0])
+    } else {
+        format!("{:.1}{}", value, SIZE_UNITS[unit])
+    }
+}
+
+///
+/// Format age of image, like `3 days ago`.
+///
+/// `created` and `now` are seconds since epoch.
+///
+pub fn format_age(created: u64, now: u64) -> String {
+    let seconds = now.saturating_sub(created);
+
+    let (value, unit) = match seconds {
+        s if s < 60 => (s, "second"),
+        s if s < 3600 => (s / 60, "minute"),
+        s if s < 86400 => (s / 3600, "hour"),
+        s if s < 86400 * 7 => (s / 86400, "day"),
+        s if s < 86400 * 30 => (s / (86400 * 7), "week"),
+        s if s < 86400 * 365 => (s / (86400 * 30), "month"),
+        s => (s / (86400 * 365), "year"),
+    };
+
+    if value == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", value, unit)
+    }
+}
+
 ///
 /// Function to implement list D-SH command.
 ///
@@ -59,12 +111,68 @@ pub fn get_all(
 ///
 fn list(cmd_param: CommandParameter) -> Result<(), CommandError> {
     let config = cmd_param.config.unwrap();
+    let mut long = false;
 
-    match get_all(cmd_param.io_helper, &config) {
-        Ok(list_applications_file) => {
-            // 2 - We have list of application
-            for app in list_applications_file {
-                cmd_param.io_helper.println(&app);
+    for arg in cmd_param.args {
+        match arg.as_ref() {
+            "-h" | "--help" => {
+                cmd_param.io_helper.println(cmd_param.command.usage);
+                return Ok(());
+            }
+            "-l" | "--long" => long = true,
+            other => {
+                return Err(CommandError {
+                    msg: vec![UNKOWN_OPTIONS_MESSAGE.replace("{}", other)],
+                    code: CommandExitCode::UnknowOption,
+                });
+            }
+        }
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    match get_check_application(&cmd_param, &config) {
+        Ok(mut list_applications) => {
+            list_applications.sort_by(|a, b| a.name.cmp(&b.name));
+
+            if long {
+                cmd_param.io_helper.println(&format!(
+                    "{:<34}{:<15}{:<15}{:<10}{:<16}{}",
+                    "NAME", "INSTALLED", "DOWNLOADED", "SIZE", "CREATED", "STATUS"
+                ));
+            }
+
+            // 2 - We have list of application with installed and downloaded version
+            for app in list_applications {
+                let status = app.status();
+                let installed_version = app.installed_version.unwrap_or(String::from("-"));
+                let downloaded_version = app.downloaded_version.unwrap_or(String::from("-"));
+
+                if long {
+                    cmd_param.io_helper.println(&format!(
+                        "{:<34}{:<15}{:<15}{:<10}{:<16}{}",
+                        app.name,
+                        installed_version,
+                        downloaded_version,
+                        app.size.map(format_size).unwrap_or(String::from("-")),
+                        app.created
+                            .map(|c| format_age(c, now))
+                            .unwrap_or(String::from("-")),
+                        status
+                    ));
+                } else {
+                    cmd_param.io_helper.println(&format!(
+                        "{:<width_first$}{:<width_second$}{}",
+                        app.name,
+                        installed_version,
+                        downloaded_version,
+                        width_first = 34,
+                        width_second = 15
+                    ));
+                }
             }
 
             Ok(())
@@ -89,11 +197,18 @@ pub const LIST: Command = Command {
     description: "List all applications available",
     /// Short name.
     short_name: "ls",
-    /// `check` command have no parameter.
+    /// `list` command have optional parameter.
     min_args: 0,
-    max_args: 0,
-    /// `check` command have no help.
-    usage: "",
+    max_args: 1,
+    /// `list` command help.
+    usage: "
+    Usage:	d-sh list [OPTIONS]
+
+    List all applications available
+
+    Options:
+      -l, --long               Show image size, age and status
+",
     need_config_file: true,
     exec_cmd: list,
 };
@@ -1,7 +1,7 @@
-use super::{list, LIST};
-use command::tests::test_result_ok;
-use command::CommandParameter;
-use config::{Config, ConfigDocker};
+use super::{format_age, format_size, list, LIST};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::{Config, ConfigApplicationsDir, ConfigDocker};
 use docker::tests::TestContainerHelper;
 use download::tests::TestDownloadHelper;
 use io::tests::found_item;
@@ -11,6 +11,7 @@ use io::tests::found_item;
 /// Release under MIT License.
 ///
 use io::tests::TestInputOutputHelper;
+use std::collections::HashMap;
 
 #[test]
 fn list_all_applications() {
@@ -23,12 +24,21 @@ fn list_all_applications() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -43,13 +53,213 @@ fn list_all_applications() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(list(cmd_param));
 
     let stdout = io_helper.stdout.borrow();
 
-    found_item(&stdout, "atom");
-    found_item(&stdout, "filezilla");
-    found_item(&stdout, "titi");
+    found_item(
+        &stdout,
+        "atom                              -              -",
+    );
+    found_item(
+        &stdout,
+        "filezilla                         -              -",
+    );
+    found_item(
+        &stdout,
+        "titi                              -              -",
+    );
+}
+
+#[test]
+fn list_all_applications_long() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("--long")];
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    // Create application file atom
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+    io_helper.files.borrow_mut().insert(String::from("app/titi.yml"), String::from("---\nimage_name: \"run-titi:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    // Atom image is build
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+    dck_helper
+        .sizes
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), 245_312_000);
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.version"), String::from("1.33.0"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), labels);
+
+    let cmd_param = CommandParameter {
+        command: &LIST,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(list(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    found_item(
+        &stdout,
+        "NAME                              INSTALLED      DOWNLOADED     SIZE      CREATED         STATUS",
+    );
+    found_item(
+        &stdout,
+        "atom                              1.33.0         -              245.3MB   -               Build done",
+    );
+    found_item(
+        &stdout,
+        "titi                              -              -              -         -               Build need",
+    );
+}
+
+#[test]
+fn list_unknow_option() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("--all")];
+
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    let cmd_param = CommandParameter {
+        command: &LIST,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(list(cmd_param), CommandExitCode::UnknowOption);
+}
+
+#[test]
+fn format_size_and_age() {
+    assert_eq!(format_size(512), "512B");
+    assert_eq!(format_size(245_312_000), "245.3MB");
+    assert_eq!(format_size(1_500_000_000), "1.5GB");
+
+    assert_eq!(format_age(100, 130), "30 seconds ago");
+    assert_eq!(format_age(0, 3600), "1 hour ago");
+    assert_eq!(format_age(0, 3 * 86400 + 10), "3 days ago");
+    assert_eq!(format_age(0, 400 * 86400), "1 year ago");
+}
+
+#[test]
+fn list_applications_of_many_folders() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [];
+
+    // Application of first folder hides same application in next folders
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::List(vec![
+            String::from("app"),
+            String::from("team"),
+        ]),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+    io_helper.files.borrow_mut().insert(String::from("team/atom.yml"), String::from("---\nimage_name: \"run-team-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+    io_helper.files.borrow_mut().insert(String::from("team/titi.yml"), String::from("---\nimage_name: \"run-titi:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    let cmd_param = CommandParameter {
+        command: &LIST,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(list(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.len(), 2);
+    found_item(
+        &stdout,
+        "atom                              -              -",
+    );
+    found_item(
+        &stdout,
+        "titi                              -              -",
+    );
 }
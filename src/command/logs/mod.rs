@@ -0,0 +1,79 @@
+///
+/// Module to display log of build.
+///
+/// Release under MIT License.
+///
+use command::build::log::get_last_log_filename;
+use command::{Command, CommandError, CommandExitCode, CommandParameter};
+
+#[cfg(test)]
+mod tests;
+
+///
+/// Function to implement logs D-SH command.
+///
+/// `args` parameter is command line arguments of D-SH.
+///
+/// returning exit code of D-SH.
+///
+fn logs(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    let app = match cmd_param.args[0].as_ref() {
+        "-h" | "--help" => {
+            cmd_param.io_helper.println(cmd_param.command.usage);
+            return Ok(());
+        }
+        app => app,
+    };
+
+    let log_filename = match get_last_log_filename(cmd_param.io_helper, cmd_param.config_dir, app)?
+    {
+        Some(r) => r,
+        None => {
+            return Err(CommandError {
+                msg: vec![format!("No build log found for '{}'!", app)],
+                code: CommandExitCode::BuildLogNotFound,
+            });
+        }
+    };
+
+    match cmd_param.io_helper.file_read_at_string(&log_filename) {
+        Ok(content) => {
+            cmd_param.io_helper.print(&content);
+            Ok(())
+        }
+        Err(err) => Err(CommandError {
+            msg: vec![
+                format!("Unable to read '{}'!", log_filename),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::BuildLogNotFound,
+        }),
+    }
+}
+
+///
+/// The `logs` command.
+///
+pub const LOGS: Command = Command {
+    /// This command call by `logs`.
+    name: "logs",
+    /// description.
+    description: "Display last build log of application",
+    /// Short name.
+    short_name: "lg",
+    /// `logs` command have one parameter.
+    min_args: 1,
+    max_args: 1,
+    /// `logs` command help.
+    usage: "
+    Usage:	d-sh logs APPLICATION
+
+    Display last build log of an application. For base image, use 'base-default' or
+    'base-NAME'.
+
+    Options:
+      -h, --help               Display this help
+",
+    need_config_file: false,
+    exec_cmd: logs,
+};
@@ -0,0 +1,72 @@
+use super::{logs, LOGS};
+use command::build::log::LOGS_DIR;
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::create_config_filename_path;
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+///
+/// Module to tests module logs.
+///
+/// Release under MIT License.
+///
+use io::tests::TestInputOutputHelper;
+
+#[test]
+fn logs_display_last_build_log() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let log_dir = create_config_filename_path(Some(TEST_CONFIG_DIR), LOGS_DIR).unwrap();
+
+    for (filename, content) in &[
+        ("atom-1500000000.log", "old build\n"),
+        ("atom-1600000000.log", "last build\n"),
+        // Log of another application
+        ("atom-beta-1700000000.log", "beta build\n"),
+    ] {
+        io_helper
+            .files
+            .borrow_mut()
+            .insert(format!("{}/{}", log_dir, filename), String::from(*content));
+    }
+
+    let cmd_param = CommandParameter {
+        command: &LOGS,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(logs(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.len(), 1);
+    assert_eq!(stdout.get(0).unwrap(), "last build\n");
+}
+
+#[test]
+fn logs_not_found() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let cmd_param = CommandParameter {
+        command: &LOGS,
+        args: &[String::from("atom")],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(logs(cmd_param), CommandExitCode::BuildLogNotFound);
+
+    assert_eq!(stderr.get(0).unwrap(), "No build log found for 'atom'!");
+}
@@ -0,0 +1,154 @@
+///
+/// Module to migrate config and application files to current format.
+///
+/// Release under MIT License.
+///
+use command::{Command, CommandError, CommandExitCode, CommandParameter};
+use config::get_config;
+use config::layer::get_config_files;
+use config::migration::{get_files_to_migrate, migrate_file, SchemaKind};
+use std::env;
+
+#[cfg(test)]
+mod tests;
+
+const UNKOWN_OPTIONS_MESSAGE: &'static str =
+    "d-sh migrate: invalid option '{}'\nTry 'd-sh migrate --help' for more information.\n";
+
+///
+/// Migrate files, with `dry_run` only display files to migrate.
+///
+/// Return errors of files that cannot be migrated.
+///
+fn migrate_files(
+    cmd_param: &CommandParameter,
+    files: &[(String, SchemaKind)],
+    dry_run: bool,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for (filename, kind) in files {
+        if dry_run {
+            cmd_param.io_helper.println(&format!(
+                "'{}' must be migrated to version {}",
+                filename,
+                kind.current_version()
+            ));
+            continue;
+        }
+
+        match migrate_file(cmd_param.io_helper, filename, *kind) {
+            Ok(Some(backup_filename)) => cmd_param.io_helper.println(&format!(
+                "'{}' migrated to version {}, old file saved in '{}'",
+                filename,
+                kind.current_version(),
+                backup_filename
+            )),
+            Ok(None) => (),
+            Err(err) => errors.push(format!("Unable to migrate '{}', {}", filename, err)),
+        }
+    }
+
+    errors
+}
+
+///
+/// Function to implement migrate D-SH command.
+///
+/// `args` parameter is command line arguments of D-SH.
+///
+/// returning exit code of D-SH.
+///
+fn migrate(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    let mut dry_run = false;
+
+    for arg in cmd_param.args {
+        match arg.as_ref() {
+            "-h" | "--help" => {
+                cmd_param.io_helper.println(cmd_param.command.usage);
+                return Ok(());
+            }
+            "-n" | "--dry-run" => dry_run = true,
+            other => {
+                return Err(CommandError {
+                    msg: vec![UNKOWN_OPTIONS_MESSAGE.replace("{}", other)],
+                    code: CommandExitCode::UnknowOption,
+                });
+            }
+        }
+    }
+
+    let dir = env::current_dir().ok();
+    let config_files = get_config_files(cmd_param.io_helper, cmd_param.config_dir, dir.as_deref());
+
+    if config_files.is_empty() {
+        return Err(CommandError {
+            msg: vec![String::from(
+                "No config file found. Please run 'init' command first.",
+            )],
+            code: CommandExitCode::ConfigFileNotFound,
+        });
+    }
+
+    // Config files first, folders of applications are read in migrated config
+    let to_migrate = get_files_to_migrate(cmd_param.io_helper, &config_files, None);
+    let mut errors = migrate_files(&cmd_param, &to_migrate, dry_run);
+    let mut count = to_migrate.len();
+
+    let config = match get_config(cmd_param.io_helper, cmd_param.config_dir) {
+        Ok(r) => r,
+        Err(err) => {
+            errors.push(format!("{}", err));
+
+            return Err(CommandError {
+                msg: errors,
+                code: CommandExitCode::ConfigFileFormatWrong,
+            });
+        }
+    };
+
+    let to_migrate = get_files_to_migrate(cmd_param.io_helper, &[], Some(&config));
+    errors.extend(migrate_files(&cmd_param, &to_migrate, dry_run));
+    count += to_migrate.len();
+
+    if !errors.is_empty() {
+        return Err(CommandError {
+            msg: errors,
+            code: CommandExitCode::ConfigFileFormatWrong,
+        });
+    }
+
+    if count == 0 {
+        cmd_param.io_helper.println("All files are up to date.");
+    }
+
+    Ok(())
+}
+
+///
+/// The `migrate` command.
+///
+pub const MIGRATE: Command = Command {
+    /// This command call by `migrate`.
+    name: "migrate",
+    /// description.
+    description: "Upgrade config and application files to current format",
+    /// Short name.
+    short_name: "mig",
+    /// `migrate` command have one option.
+    min_args: 0,
+    max_args: 1,
+    /// `migrate` command help.
+    usage: "
+    Usage:	d-sh migrate [OPTIONS]
+
+    Upgrade config files and application files to current format. Old file is saved in
+    FILE.vVERSION.bak.
+
+    Options:
+      -n, --dry-run            Only display files to upgrade
+      -h, --help               Display this help
+",
+    need_config_file: false,
+    exec_cmd: migrate,
+};
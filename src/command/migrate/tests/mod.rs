@@ -0,0 +1,89 @@
+///
+/// Module to tests module migrate.
+///
+/// Release under MIT License.
+///
+use super::{migrate, MIGRATE};
+use command::tests::{test_result_ok, TEST_CONFIG_DIR};
+use command::CommandParameter;
+use config::get_config_filename;
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+use io::tests::{found_item, TestInputOutputHelper};
+
+const APPLICATION_DATA: &'static str =
+    "---\nformat_version: 0\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\n";
+
+#[test]
+fn migrate_config_and_applications() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config_filename = get_config_filename(Some(TEST_CONFIG_DIR)).unwrap();
+
+    io_helper.files.borrow_mut().insert(
+        config_filename.clone(),
+        String::from("---\nformat_version: 0\ndownload_dir: \"dwn\"\napplications_dir: \"app\"\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\n"),
+    );
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("app/atom.yml"), String::from(APPLICATION_DATA));
+
+    let cmd_param = CommandParameter {
+        command: &MIGRATE,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(migrate(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    found_item(&stdout, "'/home/d-sh/.d-sh/config.yml' migrated to version 1, old file saved in '/home/d-sh/.d-sh/config.yml.v0.bak'");
+    found_item(
+        &stdout,
+        "'app/atom.yml' migrated to version 1, old file saved in 'app/atom.yml.v0.bak'",
+    );
+
+    let files = io_helper.files.borrow();
+
+    assert!(files
+        .get(&config_filename)
+        .unwrap()
+        .starts_with("---\nformat_version: 1\n"));
+    assert_eq!(files.get("app/atom.yml.v0.bak").unwrap(), APPLICATION_DATA);
+}
+
+#[test]
+fn migrate_dry_run_when_up_to_date() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    io_helper.files.borrow_mut().insert(
+        get_config_filename(Some(TEST_CONFIG_DIR)).unwrap(),
+        String::from("---\nformat_version: 1\ndownload_dir: \"dwn\"\napplications_dir: \"app\"\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\n"),
+    );
+
+    let args = [String::from("--dry-run")];
+
+    let cmd_param = CommandParameter {
+        command: &MIGRATE,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(migrate(cmd_param));
+
+    found_item(&io_helper.stdout.borrow(), "All files are up to date.");
+}
@@ -5,17 +5,32 @@
 ///
 pub mod build;
 pub mod check;
+pub mod config;
 pub mod delete;
+pub mod export;
+pub mod import;
 pub mod init;
 pub mod list;
+pub mod logs;
+pub mod migrate;
+pub mod outdated;
+pub mod pull;
+pub mod push;
 pub mod run;
 #[cfg(test)]
 pub mod tests;
+pub mod validate;
 
-use config::{get_config, get_config_filename, Config};
+use cleanup::{register_tmp_dir, unregister_tmp_dir};
+use config::layer::{get_config_files, SYSTEM_CONFIG_FILE};
+use config::migration::get_files_to_migrate;
+use config::{get_config_filename, Config};
 use docker::ContainerHelper;
 use download::DownloadHelper;
-use io::InputOutputHelper;
+use helper::HelperError;
+use io::{convert_path, InputOutputHelper};
+use std::env::{current_dir, temp_dir};
+use std::io::Error;
 
 ///
 /// Exit code of command.
@@ -44,12 +59,33 @@ pub enum CommandExitCode {
     CannotGenerateDockerfile = 18,
     DockerfileTemplateInvalid = 19,
     CannotCreateFolder = 20,
+    /// Not used anymore, temporary folder is removed without error. Kept to not reuse code.
+    #[deprecated]
+    #[allow(dead_code)]
     CannotDeleteTemporaryFolder = 21,
     CannotCopyFile = 22,
     ConfigFileFormatWrong = 23,
     TemplateNotFound = 24,
     UnableDownloadApplication = 25,
     DockerBuildFail = 26,
+    CannotComputeHash = 27,
+    BaseImageNotFound = 28,
+    BuildLogNotFound = 29,
+    CannotSaveImage = 30,
+    CannotLoadImage = 31,
+    CannotCreateArchive = 32,
+    CannotReadArchive = 33,
+    ApplicationFileExits = 34,
+    RegistryNotConfigured = 35,
+    CannotPushImage = 36,
+    CannotPullImage = 37,
+    LockFileWrong = 38,
+    LockMismatch = 39,
+    DaemonUnreachable = 40,
+    PermissionDenied = 41,
+    NetworkError = 42,
+    ConfigKeyNotFound = 43,
+    CannotEditFile = 44,
 }
 
 ///
@@ -76,6 +112,89 @@ pub struct CommandParameter<'a> {
     pub dl_helper: &'a DownloadHelper,
     /// Config of D-SH.
     pub config: Option<&'a Config>,
+    /// Folder of config (`--config-dir`, `D_SH_HOME` or `~/.d-sh/`).
+    pub config_dir: Option<&'a str>,
+}
+
+///
+/// Return error of command from error of helper.
+///
+/// `code` is used if error is not caused by daemon, permission or network.
+///
+pub fn get_helper_command_error(
+    msg: String,
+    err: &HelperError,
+    code: CommandExitCode,
+) -> CommandError {
+    let code = match err {
+        HelperError::DaemonUnreachable(_) => CommandExitCode::DaemonUnreachable,
+        HelperError::PermissionDenied(_) => CommandExitCode::PermissionDenied,
+        HelperError::Network(_) => CommandExitCode::NetworkError,
+        _ => code,
+    };
+
+    CommandError {
+        msg: vec![msg, format!("{}", err)],
+        code: code,
+    }
+}
+
+///
+/// Return command error when image cannot be listed or inspected.
+///
+pub fn get_image_command_error(image_name: &str, err: &HelperError) -> CommandError {
+    get_helper_command_error(
+        format!("Unable to inspect image '{}'!", image_name),
+        err,
+        CommandExitCode::ContainerImageNotFound,
+    )
+}
+
+///
+/// Create temporary folder of command in `tmp_dir` of config (by default /tmp).
+///
+/// Folder is removed if D-SH is interrupted.
+///
+pub fn create_command_tmp_dir(
+    io_helper: &InputOutputHelper,
+    config: &Config,
+) -> Result<String, CommandError> {
+    let tmp_parent_dir = match &config.tmp_dir {
+        Some(t) => convert_path(t),
+        None => temp_dir().to_str().unwrap().to_string(),
+    };
+
+    match io_helper.create_tmp_dir(&tmp_parent_dir) {
+        Ok(r) => {
+            register_tmp_dir(&r);
+            Ok(r)
+        }
+        Err(err) => Err(CommandError {
+            msg: vec![
+                format!(
+                    "Cannot create temporary folder in '{}'. Please check right!",
+                    &tmp_parent_dir
+                ),
+                format!("{}", err),
+            ],
+            code: CommandExitCode::CannotCreateFolder,
+        }),
+    }
+}
+
+///
+/// Remove temporary folder of command.
+///
+pub fn remove_command_tmp_dir(io_helper: &InputOutputHelper, tmp_dir: &str) {
+    unregister_tmp_dir(tmp_dir);
+
+    if let Err(err) = io_helper.remove_dir_all(tmp_dir) {
+        // Non blocking error
+        io_helper.eprintln(&format!(
+            "Cannot delete temporary folder '{}'! {}",
+            tmp_dir, err
+        ));
+    }
 }
 
 ///
@@ -105,6 +224,8 @@ impl Command {
     /// Execute code of command
     ///
     /// `args` parameter is command line arguments of D-SH.
+    /// `config_dir` is folder of config, see `get_config_dir`.
+    /// `config` is config read in `config_dir`, read once by caller.
     ///
     /// returning exit code of D-SH
     ///
@@ -114,6 +235,8 @@ impl Command {
         io_helper: &InputOutputHelper,
         dck_helper: &ContainerHelper,
         dl_helper: &DownloadHelper,
+        config_dir: Option<&str>,
+        config: &Result<Config, Error>,
     ) -> CommandExitCode {
         // Check parameter
         if args.len() < self.min_args || args.len() > self.max_args {
@@ -126,7 +249,7 @@ impl Command {
         if self.need_config_file {
             let config_file;
 
-            match get_config_filename() {
+            match get_config_filename(config_dir) {
                 Some(r) => config_file = r,
                 None => {
                     io_helper.eprintln("Cannot access to folder where config must be.");
@@ -134,7 +257,12 @@ impl Command {
                 }
             }
 
-            if !io_helper.file_exits(&config_file) {
+            // Config can be in system, user or project config file
+            let dir = current_dir().ok();
+
+            let config_files = get_config_files(io_helper, config_dir, dir.as_deref());
+
+            if config_files.is_empty() {
                 io_helper.eprintln(&format!(
                     "The file '{}' doesn't exits. Please run 'init' command first.",
                     config_file
@@ -142,15 +270,29 @@ impl Command {
                 return CommandExitCode::ConfigFileNotFound;
             }
 
-            let config;
+            // System file cannot be migrated by user, applications are checked by `migrate`
+            let user_files: Vec<String> = config_files
+                .into_iter()
+                .filter(|f| f != SYSTEM_CONFIG_FILE)
+                .collect();
+            let to_migrate = get_files_to_migrate(io_helper, &user_files, None);
+
+            if !to_migrate.is_empty() {
+                io_helper.eprintln(&format!(
+                    "Warning: {} file(s) use an old format. Please run 'd-sh migrate' to upgrade them.",
+                    to_migrate.len()
+                ));
+            }
 
-            match get_config(io_helper) {
-                Ok(r) => config = r,
-                Err(_) => {
-                    io_helper.eprintln("Cannot read config file, please check rigts and format!");
+            let config = match config {
+                Ok(r) => r,
+                Err(err) => {
+                    io_helper.eprintln(&format!("{}", err));
+                    io_helper
+                        .eprintln("Run 'd-sh validate' to check config and application files.");
                     return CommandExitCode::ConfigFileFormatWrong;
                 }
-            }
+            };
 
             let cmd_param = CommandParameter {
                 command: self,
@@ -158,7 +300,8 @@ impl Command {
                 io_helper: io_helper,
                 dck_helper: dck_helper,
                 dl_helper: dl_helper,
-                config: Some(&config),
+                config: Some(config),
+                config_dir: config_dir,
             };
 
             if let Err(err) = (self.exec_cmd)(cmd_param) {
@@ -176,6 +319,7 @@ impl Command {
                 dck_helper: dck_helper,
                 dl_helper: dl_helper,
                 config: None,
+                config_dir: config_dir,
             };
 
             if let Err(err) = (self.exec_cmd)(cmd_param) {
@@ -0,0 +1,158 @@
+///
+/// Module to check if new version of applications are available.
+///
+/// Release under MIT License.
+///
+use command::check::get_check_application;
+use command::{Command, CommandError, CommandParameter};
+use config::{get_config_application, Config};
+use std::cmp::Ordering;
+use update::get_latest_version;
+use version::compare_version;
+
+#[cfg(test)]
+mod tests;
+
+///
+/// Structure of application with last version.
+///
+pub struct OutdatedApplication {
+    /// Name of application.
+    pub name: String,
+    /// Version in image, or version of application file if image not build.
+    pub current_version: Option<String>,
+    /// Last version available.
+    pub latest_version: Option<String>,
+    /// If last version is newer than current version.
+    pub is_outdated: bool,
+}
+
+///
+/// Return list of applications with `update_check` and their last version.
+///
+pub fn get_outdated_application(
+    cmd_param: &CommandParameter,
+    config: &Config,
+) -> Result<Vec<OutdatedApplication>, CommandError> {
+    let mut list_applications = get_check_application(cmd_param, config)?;
+
+    list_applications.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut result = Vec::new();
+
+    for app in list_applications {
+        if app.is_error {
+            continue;
+        }
+
+        let config_application =
+            match get_config_application(cmd_param.io_helper, &app.config_filename) {
+                Ok(r) => r,
+                Err(_) => continue,
+            };
+
+        if config_application.update_check.is_none() {
+            continue;
+        }
+
+        let current_version = app
+            .installed_version
+            .or_else(|| config_application.version.clone());
+
+        let latest_version = match get_latest_version(
+            cmd_param.dl_helper,
+            cmd_param.dck_helper,
+            config,
+            &config_application,
+        ) {
+            Ok(r) => r,
+            Err(err) => {
+                // Non blocking error
+                cmd_param.io_helper.eprintln(&format!(
+                    "Cannot find last version of '{}'! {}",
+                    app.name, err
+                ));
+                None
+            }
+        };
+
+        let is_outdated = match (&current_version, &latest_version) {
+            (Some(c), Some(l)) => compare_version(l, c) == Ordering::Greater,
+            (None, Some(_)) => true,
+            _ => false,
+        };
+
+        result.push(OutdatedApplication {
+            name: app.name,
+            current_version: current_version,
+            latest_version: latest_version,
+            is_outdated: is_outdated,
+        });
+    }
+
+    Ok(result)
+}
+
+///
+/// Function to implement outdated D-SH command.
+///
+/// `args` parameter is command line arguments of D-SH.
+///
+/// returning exit code of D-SH.
+///
+fn outdated(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    if !cmd_param.args.is_empty() {
+        cmd_param.io_helper.println(cmd_param.command.usage);
+        return Ok(());
+    }
+
+    let config = cmd_param.config.unwrap();
+
+    for app in get_outdated_application(&cmd_param, &config)? {
+        let status = if app.is_outdated {
+            "Outdated"
+        } else if app.latest_version.is_none() {
+            "Unknown"
+        } else {
+            "Up to date"
+        };
+
+        cmd_param.io_helper.println(&format!(
+            "{:<width_first$}{:<width_second$}{:<width_second$}{}",
+            app.name,
+            app.current_version.unwrap_or(String::from("-")),
+            app.latest_version.unwrap_or(String::from("-")),
+            status,
+            width_first = 34,
+            width_second = 15
+        ));
+    }
+
+    Ok(())
+}
+
+///
+/// The `outdated` command.
+///
+pub const OUTDATED: Command = Command {
+    /// This command call by `outdated`.
+    name: "outdated",
+    /// description.
+    description: "List applications with new version available",
+    /// Short name.
+    short_name: "out",
+    /// `outdated` command have no parameter.
+    min_args: 0,
+    max_args: 1,
+    /// `outdated` command help.
+    usage: "
+    Usage:	d-sh outdated
+
+    Display current and last version of applications with 'update_check'
+
+    Options:
+      -h, --help               Display this help
+",
+    need_config_file: true,
+    exec_cmd: outdated,
+};
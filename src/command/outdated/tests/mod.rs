@@ -0,0 +1,147 @@
+///
+/// Module to tests module outdated.
+///
+/// Release under MIT License.
+///
+use super::{outdated, OUTDATED};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::tests::create_config;
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+use io::tests::found_item;
+use io::tests::TestInputOutputHelper;
+use std::collections::HashMap;
+
+#[test]
+fn outdated_display_current_and_last_version() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [];
+
+    // Atom is build with version 1.33.0
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.version"), String::from("1.33.0"));
+
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from("run-atom:latest"), labels);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\nupdate_check:\n  type: github\n  url: \"https://api.github.com/repos/atom/atom/releases/latest\""));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\nversion: \"3.40.0\"\nupdate_check:\n  type: html\n  url: \"https://filezilla-project.org/download.php\"\n  regex: \"FileZilla_([0-9.]+)_x86_64\""));
+    io_helper.files.borrow_mut().insert(String::from("app/titi.yml"), String::from("---\nimage_name: \"run-titi:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\nupdate_check:\n  type: github\n  url: \"https://titi.org/releases\""));
+    io_helper.files.borrow_mut().insert(String::from("app/tutu.yml"), String::from("---\nimage_name: \"run-tutu:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    dl_helper.contents.borrow_mut().insert(
+        String::from("https://api.github.com/repos/atom/atom/releases/latest"),
+        String::from("{\"tag_name\": \"v1.34.0\", \"draft\": false, \"prerelease\": false}"),
+    );
+    dl_helper.contents.borrow_mut().insert(
+        String::from("https://filezilla-project.org/download.php"),
+        String::from("<a href=\"FileZilla_3.39.0_x86_64.tar.bz2\">old</a><a href=\"FileZilla_3.40.0_x86_64.tar.bz2\">new</a>"),
+    );
+
+    let cmd_param = CommandParameter {
+        command: &OUTDATED,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(outdated(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.len(), 3);
+
+    found_item(
+        &stdout,
+        "atom                              1.33.0         1.34.0         Outdated",
+    );
+    found_item(
+        &stdout,
+        "filezilla                         3.40.0         3.40.0         Up to date",
+    );
+    found_item(
+        &stdout,
+        "titi                              -              -              Unknown",
+    );
+
+    let stderr = io_helper.stderr.borrow();
+
+    found_item(
+        &stderr,
+        "Cannot find last version of 'titi'! Unable to read 'https://titi.org/releases'!",
+    );
+}
+
+#[test]
+fn outdated_display_help() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("-h")];
+
+    let config = create_config();
+
+    let cmd_param = CommandParameter {
+        command: &OUTDATED,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(outdated(cmd_param));
+
+    let stdout = io_helper.stdout.borrow();
+
+    assert_eq!(stdout.get(0).unwrap(), OUTDATED.usage);
+}
+
+#[test]
+fn outdated_fail_if_applications_folder_cannot_be_read() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [];
+
+    let config = create_config();
+
+    io_helper
+        .files_error
+        .borrow_mut()
+        .insert(String::from("app"), true);
+
+    let cmd_param = CommandParameter {
+        command: &OUTDATED,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(
+        outdated(cmd_param),
+        CommandExitCode::CannotReadApplicationsFolder,
+    );
+}
@@ -0,0 +1,161 @@
+///
+/// Module to tests module pull.
+///
+/// Release under MIT License.
+///
+use super::{pull, PULL};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::{self, Config, ConfigRegistry};
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+use io::tests::TestInputOutputHelper;
+
+fn create_config() -> Config {
+    Config {
+        registry: Some(ConfigRegistry {
+            url: String::from("localhost:5000"),
+            cache: None,
+        }),
+        ..config::tests::create_config()
+    }
+}
+
+#[test]
+fn pull_applications() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    dck_helper
+        .registry_images
+        .borrow_mut()
+        .push(String::from("localhost:5000/run-atom:latest"));
+
+    let args = [String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &PULL,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(pull(cmd_param));
+
+    assert!(dck_helper
+        .images
+        .borrow()
+        .contains(&String::from("run-atom:latest")));
+}
+
+#[test]
+fn pull_application_not_in_registry() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    let args = [String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &PULL,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(pull(cmd_param), CommandExitCode::CannotPullImage);
+
+    assert_eq!(
+        stderr.get(0).unwrap(),
+        "Pull atom failed! Unable to pull 'localhost:5000/run-atom:latest'! Not found: manifest for localhost:5000/run-atom:latest not found"
+    );
+}
+
+#[test]
+fn pull_unknown_application() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    dck_helper
+        .registry_images
+        .borrow_mut()
+        .push(String::from("localhost:5000/run-atom:latest"));
+
+    let args = [String::from("unknown"), String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &PULL,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(pull(cmd_param), CommandExitCode::ApplicationFileNotFound);
+
+    assert_eq!(stderr.len(), 1);
+    assert!(dck_helper
+        .images
+        .borrow()
+        .contains(&String::from("run-atom:latest")));
+}
+
+#[test]
+fn pull_application_on_its_docker_daemon() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndocker_host: \"ssh://me@workstation\""));
+
+    dck_helper
+        .registry_images
+        .borrow_mut()
+        .push(String::from("localhost:5000/run-atom:latest"));
+
+    let args = [String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &PULL,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(pull(cmd_param));
+
+    let endpoints = dck_helper.endpoints.borrow();
+
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(
+        endpoints[0].host,
+        Some(String::from("ssh://me@workstation"))
+    );
+}
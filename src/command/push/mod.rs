@@ -0,0 +1,100 @@
+///
+/// Module to push images of applications in registry.
+///
+/// Release under MIT License.
+///
+use command::{Command, CommandError, CommandExitCode, CommandParameter};
+use docker::remote::get_application_endpoint;
+use registry::{get_application, get_registry, push_image};
+
+#[cfg(test)]
+mod tests;
+
+///
+/// Function to implement push D-SH command.
+///
+/// `args` parameter is command line arguments of D-SH.
+///
+/// returning exit code of D-SH.
+///
+fn push(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    if cmd_param.args.iter().any(|a| a == "-h" || a == "--help") {
+        cmd_param.io_helper.println(cmd_param.command.usage);
+        return Ok(());
+    }
+
+    let config = cmd_param.config.unwrap();
+    let registry = match get_registry(config) {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(CommandError {
+                msg: vec![err],
+                code: CommandExitCode::RegistryNotConfigured,
+            });
+        }
+    };
+
+    let mut err_msg = Vec::new();
+    let mut err_code = None;
+
+    for app in cmd_param.args {
+        // Unknown application doesn't stop others
+        let config_application = match get_application(cmd_param.io_helper, config, app) {
+            Ok(r) => r,
+            Err(err) => {
+                err_msg.push(err);
+                err_code = err_code.or(Some(CommandExitCode::ApplicationFileNotFound));
+                continue;
+            }
+        };
+
+        // Application can be build on its own Docker daemon
+        let app_helper = get_application_endpoint(&config_application)
+            .map(|endpoint| cmd_param.dck_helper.with_endpoint(&endpoint));
+        let dck_helper = match app_helper {
+            Some(ref h) => &**h,
+            None => cmd_param.dck_helper,
+        };
+
+        cmd_param.io_helper.println(&format!("Pushing {}...", app));
+
+        if let Err(err) = push_image(dck_helper, registry, &config_application.image_name) {
+            err_msg.push(format!("Push {} failed! {}", app, err));
+            err_code = err_code.or(Some(CommandExitCode::CannotPushImage));
+        }
+    }
+
+    match err_code {
+        None => Ok(()),
+        Some(code) => Err(CommandError {
+            msg: err_msg,
+            code: code,
+        }),
+    }
+}
+
+///
+/// The `push` command.
+///
+pub const PUSH: Command = Command {
+    /// This command call by `push`.
+    name: "push",
+    /// description.
+    description: "Push images of applications in registry",
+    /// Short name.
+    short_name: "psh",
+    /// `push` command need applications.
+    min_args: 1,
+    max_args: usize::MAX,
+    /// `push` command help.
+    usage: "
+    Usage:	d-sh push PROGRAM1 PROGRAM2 ...
+
+    Push images of applications in registry of config file
+
+    Options:
+      -h, --help               Display this help
+",
+    need_config_file: true,
+    exec_cmd: push,
+};
@@ -0,0 +1,184 @@
+///
+/// Module to tests module push.
+///
+/// Release under MIT License.
+///
+use super::{push, PUSH};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::{self, Config, ConfigRegistry};
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+use io::tests::TestInputOutputHelper;
+
+fn create_config() -> Config {
+    Config {
+        registry: Some(ConfigRegistry {
+            url: String::from("localhost:5000"),
+            cache: None,
+        }),
+        ..config::tests::create_config()
+    }
+}
+
+#[test]
+fn push_applications() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    let args = [String::from("atom"), String::from("filezilla")];
+
+    let cmd_param = CommandParameter {
+        command: &PUSH,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(push(cmd_param), CommandExitCode::CannotPushImage);
+
+    assert_eq!(
+        stderr,
+        vec![String::from(
+            "Push filezilla failed! Image 'run-filezilla:latest' not found!"
+        )]
+    );
+    assert_eq!(
+        *dck_helper.registry_images.borrow(),
+        vec![String::from("localhost:5000/run-atom:latest")]
+    );
+}
+
+#[test]
+fn push_without_registry() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let mut config = create_config();
+    config.registry = None;
+
+    let args = [String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &PUSH,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_err(push(cmd_param), CommandExitCode::RegistryNotConfigured);
+}
+
+#[test]
+fn push_unknown_application() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    let args = [String::from("unknown"), String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &PUSH,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(push(cmd_param), CommandExitCode::ApplicationFileNotFound);
+
+    assert_eq!(stderr.len(), 1);
+    assert_eq!(
+        *dck_helper.registry_images.borrow(),
+        vec![String::from("localhost:5000/run-atom:latest")]
+    );
+}
+
+#[test]
+fn push_display_help() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    let args = [String::from("--help")];
+
+    let cmd_param = CommandParameter {
+        command: &PUSH,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(push(cmd_param));
+
+    assert_eq!(io_helper.stdout.borrow().get(0).unwrap(), PUSH.usage);
+}
+
+#[test]
+fn push_application_on_its_docker_daemon() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\ndocker_context: \"workstation\""));
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    let args = [String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &PUSH,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(push(cmd_param));
+
+    let endpoints = dck_helper.endpoints.borrow();
+
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(endpoints[0].context, Some(String::from("workstation")));
+}
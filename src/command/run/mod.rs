@@ -1,5 +1,9 @@
-use command::{Command, CommandError, CommandExitCode, CommandParameter};
-use config::{get_config_application, Config, ConfigApplication};
+use command::{
+    get_helper_command_error, get_image_command_error, Command, CommandError, CommandExitCode,
+    CommandParameter,
+};
+use config::{get_application_filename, get_config_application, Config, ConfigApplication};
+use docker::remote::get_application_endpoint;
 use docker::ContainerHelper;
 use io::{convert_path, InputOutputHelper};
 ///
@@ -7,7 +11,7 @@ use io::{convert_path, InputOutputHelper};
 ///
 /// Release under MIT License.
 ///
-use std::path::Path;
+use std::env;
 use users::{get_current_gid, get_current_uid, get_current_username};
 
 #[cfg(test)]
@@ -18,15 +22,20 @@ mod tests;
 ///
 /// `interactive` true if command line interactive
 /// `config_application` configuration of current application
+/// `remote` true if Docker host is remote, container is not detached to keep display forwarded
 ///
 /// returning vector of string
 ///
-fn get_extra_args(interactive: bool, config_application: &ConfigApplication) -> Vec<String> {
+fn get_extra_args(
+    interactive: bool,
+    config_application: &ConfigApplication,
+    remote: bool,
+) -> Vec<String> {
     let mut extra_args: Vec<String> = vec![];
 
     if interactive || config_application.interactive.unwrap_or(false) {
         extra_args.push(String::from("-it"));
-    } else {
+    } else if !remote {
         extra_args.push(String::from("-d"));
     }
 
@@ -69,6 +78,41 @@ fn get_run_args(extra_args: &mut Vec<String>, username: String) -> Vec<String> {
     run_opts
 }
 
+///
+/// Construct run args of run on remote Docker host
+///
+/// Local X11 socket and home cannot be mounted, display is forwarded to host network.
+///
+/// `extra_args` extra arguments
+/// `username` username
+/// `display` display forwarded on Docker host
+///
+/// returning vector of string
+///
+fn get_remote_run_args(
+    extra_args: &mut Vec<String>,
+    username: String,
+    display: &str,
+) -> Vec<String> {
+    let mut run_opts: Vec<String> = vec![
+        String::from("--network"),
+        String::from("host"),
+        String::from("-e"),
+        format!("DISPLAY={}", display),
+        String::from("-e"),
+        format!("USERNAME_TO_RUN={}", username),
+        String::from("-e"),
+        format!("USERNAME_TO_RUN_GID={}", get_current_gid()),
+        String::from("-e"),
+        format!("USERNAME_TO_RUN_UID={}", get_current_uid()),
+        String::from("--rm"),
+    ];
+
+    run_opts.append(extra_args);
+
+    run_opts
+}
+
 ///
 /// Convert args to cmd_args
 ///
@@ -111,12 +155,7 @@ fn run_application(
 ) -> Result<(), CommandError> {
     io_helper.println(&format!("Running {}...", app));
 
-    let mut application_filename = String::from(app);
-    application_filename.push_str(".yml");
-
-    let application_filename_path = Path::new(&config.applications_dir).join(&application_filename);
-
-    let application_filename_full_path = application_filename_path.to_str().unwrap();
+    let application_filename_full_path = get_application_filename(io_helper, config, app);
 
     let config_application;
 
@@ -133,8 +172,18 @@ fn run_application(
         }
     }
 
+    // Application can be run on its own Docker daemon
+    let app_helper = get_application_endpoint(&config_application)
+        .map(|endpoint| dck_helper.with_endpoint(&endpoint));
+    let dck_helper = match app_helper {
+        Some(ref h) => &**h,
+        None => dck_helper,
+    };
+
     // Check if image exists
-    let images = dck_helper.list_image(&config_application.image_name);
+    let images = dck_helper
+        .list_image(&config_application.image_name)
+        .map_err(|err| get_image_command_error(&config_application.image_name, &err))?;
 
     if images.len() > 0 {
         io_helper.println("Create container");
@@ -151,23 +200,40 @@ fn run_application(
             }
         }
 
-        let mut extra_args = get_extra_args(interactive, &config_application);
-        let run_opts = get_run_args(&mut extra_args, username);
+        // Display is forwarded if Docker host is remote, tunnel is closed at end of run
+        let display = env::var("DISPLAY").unwrap_or(String::from(":0"));
+        let remote_display = match dck_helper.forward_display(&display) {
+            Ok(r) => r,
+            Err(err) => {
+                return Err(get_helper_command_error(
+                    String::from("Unable to forward display to Docker host"),
+                    &err,
+                    CommandExitCode::ContainerRunError,
+                ));
+            }
+        };
+
+        let mut extra_args =
+            get_extra_args(interactive, &config_application, remote_display.is_some());
+        let run_opts = match remote_display {
+            Some(ref d) => get_remote_run_args(&mut extra_args, username, &d.display),
+            None => get_run_args(&mut extra_args, username),
+        };
 
         let cmd_args = get_cmd_args(&config_application.cmd_line_args, args);
 
-        if dck_helper.run_container(
+        match dck_helper.run_container(
             &config_application.image_name,
             Some(&run_opts),
             Some(&config_application.cmd_line),
             Some(&cmd_args),
         ) {
-            Ok(())
-        } else {
-            return Err(CommandError {
-                msg: vec![String::from("Error when running container")],
-                code: CommandExitCode::ContainerRunError,
-            });
+            Ok(_) => Ok(()),
+            Err(err) => Err(get_helper_command_error(
+                String::from("Error when running container"),
+                &err,
+                CommandExitCode::ContainerRunError,
+            )),
         }
     } else {
         Err(CommandError {
@@ -1,7 +1,7 @@
 use super::{run, RUN};
-use command::tests::{test_result_err, test_result_ok};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
 use command::{CommandExitCode, CommandParameter};
-use config::{Config, ConfigDocker};
+use config::{Config, ConfigApplicationsDir, ConfigDocker};
 use docker::tests::TestContainerHelper;
 use docker::tests::TestRunContainer;
 use download::tests::TestDownloadHelper;
@@ -26,12 +26,21 @@ fn run_display_help() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     let cmd_param = CommandParameter {
@@ -41,6 +50,7 @@ fn run_display_help() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(run(cmd_param));
@@ -61,12 +71,21 @@ fn run_image_application_not_found() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     let cmd_param = CommandParameter {
@@ -76,6 +95,7 @@ fn run_image_application_not_found() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(run(cmd_param), CommandExitCode::ApplicationFileNotFound);
@@ -98,12 +118,21 @@ fn run_image_not_found_not_interactive() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -116,6 +145,7 @@ fn run_image_not_found_not_interactive() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     let stderr = test_result_err(run(cmd_param), CommandExitCode::ContainerImageNotFound);
@@ -140,12 +170,21 @@ fn run_image_found_not_interactive() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -164,6 +203,7 @@ fn run_image_found_not_interactive() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(run(cmd_param));
@@ -234,12 +274,21 @@ fn run_image_found_not_interactive_with_args() {
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -258,6 +307,7 @@ fn run_image_found_not_interactive_with_args() {
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(run(cmd_param));
@@ -291,12 +341,21 @@ fn run_image_found_interactive(
     // Create configuration file
     let config = Config {
         download_dir: String::from("dwn"),
-        applications_dir: String::from("app"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
         dockerfile: ConfigDocker {
             from: String::from("tata"),
             tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
         },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
         tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
     };
 
     // Create application file atom
@@ -318,6 +377,7 @@ fn run_image_found_interactive(
         dck_helper: dck_helper,
         dl_helper: dl_helper,
         config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
     };
 
     test_result_ok(run(cmd_param));
@@ -407,3 +467,86 @@ fn run_image_found_interactive_by_config_and_with_argument() {
     assert_eq!(atom_container.cmd_options.get(0).unwrap(), "truc");
     assert_eq!(atom_container.cmd_options.get(1).unwrap(), "bidule");
 }
+
+#[test]
+fn run_image_found_on_remote_docker_host() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    let args = [String::from("atom")];
+
+    // Create configuration file
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    };
+
+    // Create application file atom, run on workstation
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"/usr/bin/atom -f\"\ndownload_filename: \"\"\nurl: \"\"\ndocker_host: \"ssh://me@workstation\""));
+
+    // Create list of images returned by docker
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from("run-atom:latest"));
+
+    // Display is forwarded by ssh tunnel
+    *dck_helper.remote_display.borrow_mut() = Some(String::from("localhost:35235"));
+
+    let cmd_param = CommandParameter {
+        command: &RUN,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: Some(&config),
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(run(cmd_param));
+
+    let endpoints = dck_helper.endpoints.borrow();
+
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(
+        endpoints[0].host,
+        Some(String::from("ssh://me@workstation"))
+    );
+
+    let containers = dck_helper.containers.borrow();
+    let atom_container = containers.get(0).unwrap();
+    let username = get_current_username().unwrap();
+
+    // No local mount and container is not detached
+    assert_eq!(
+        atom_container.run_options,
+        vec![
+            String::from("--network"),
+            String::from("host"),
+            String::from("-e"),
+            String::from("DISPLAY=localhost:35235"),
+            String::from("-e"),
+            format!("USERNAME_TO_RUN={}", username),
+            String::from("-e"),
+            format!("USERNAME_TO_RUN_GID={}", get_current_gid()),
+            String::from("-e"),
+            format!("USERNAME_TO_RUN_UID={}", get_current_uid()),
+            String::from("--rm"),
+        ]
+    );
+}
@@ -1,5 +1,6 @@
 use super::{Command, CommandError, CommandExitCode, CommandParameter};
-use config::get_config_filename;
+use config::layer::SYSTEM_CONFIG_FILE;
+use config::{get_config, get_config_filename};
 use docker::tests::TestContainerHelper;
 use download::tests::TestDownloadHelper;
 ///
@@ -9,6 +10,11 @@ use download::tests::TestDownloadHelper;
 ///
 use io::tests::TestInputOutputHelper;
 
+///
+/// Config folder used by tests.
+///
+pub const TEST_CONFIG_DIR: &'static str = "/home/d-sh/.d-sh/";
+
 pub fn test_result_ok(result: Result<(), CommandError>) {
     if let Err(err) = result {
         panic!(format!(
@@ -55,7 +61,14 @@ fn check_if_need_argument_but_not_provide() {
 
     let args = [];
 
-    let exit_code = commands[0].exec(&args, io_helper, dck_helper, dl_helper);
+    let exit_code = commands[0].exec(
+        &args,
+        io_helper,
+        dck_helper,
+        dl_helper,
+        Some(TEST_CONFIG_DIR),
+        &get_config(io_helper, Some(TEST_CONFIG_DIR)),
+    );
 
     assert_eq!(exit_code, CommandExitCode::BadArgument);
 }
@@ -81,7 +94,14 @@ fn check_if_too_many_argument() {
 
     let args = [String::from("eeee"), String::from("eeee")];
 
-    let exit_code = commands[0].exec(&args, io_helper, dck_helper, dl_helper);
+    let exit_code = commands[0].exec(
+        &args,
+        io_helper,
+        dck_helper,
+        dl_helper,
+        Some(TEST_CONFIG_DIR),
+        &get_config(io_helper, Some(TEST_CONFIG_DIR)),
+    );
 
     assert_eq!(exit_code, CommandExitCode::BadArgument);
 }
@@ -107,7 +127,14 @@ fn check_if_not_enough_many_argument() {
 
     let args = [String::from("eeee")];
 
-    let exit_code = commands[0].exec(&args, io_helper, dck_helper, dl_helper);
+    let exit_code = commands[0].exec(
+        &args,
+        io_helper,
+        dck_helper,
+        dl_helper,
+        Some(TEST_CONFIG_DIR),
+        &get_config(io_helper, Some(TEST_CONFIG_DIR)),
+    );
 
     assert_eq!(exit_code, CommandExitCode::BadArgument);
 }
@@ -133,7 +160,14 @@ fn check_if_need_config_file_and_not_found() {
 
     let args = [];
 
-    let exit_code = commands[0].exec(&args, io_helper, dck_helper, dl_helper);
+    let exit_code = commands[0].exec(
+        &args,
+        io_helper,
+        dck_helper,
+        dl_helper,
+        Some(TEST_CONFIG_DIR),
+        &get_config(io_helper, Some(TEST_CONFIG_DIR)),
+    );
 
     assert_eq!(exit_code, CommandExitCode::ConfigFileNotFound);
 }
@@ -159,7 +193,7 @@ fn check_if_need_config_file_and_found() {
 
     let args = [];
 
-    match get_config_filename() {
+    match get_config_filename(Some(TEST_CONFIG_DIR)) {
         Some(cfg_file) => {
             // Create file
             io_helper.files.borrow_mut().insert(cfg_file, String::from("---\ndownload_dir: \"dwn\"\napplications_dir: \"app\"\ndockerfile:\n  from: \"tata\"\n  tag: \"tutu\"\n"))
@@ -167,9 +201,71 @@ fn check_if_need_config_file_and_found() {
         None => panic!("Unable to get config filename for test"),
     };
 
-    let exit_code = commands[0].exec(&args, io_helper, dck_helper, dl_helper);
+    let exit_code = commands[0].exec(
+        &args,
+        io_helper,
+        dck_helper,
+        dl_helper,
+        Some(TEST_CONFIG_DIR),
+        &get_config(io_helper, Some(TEST_CONFIG_DIR)),
+    );
+
+    assert_eq!(exit_code, CommandExitCode::Ok);
+    // Config file without version is current format
+    assert!(io_helper.stderr.borrow().is_empty());
+}
+
+#[test]
+fn check_if_need_config_file_and_found_with_old_format() {
+    let io_helper = &TestInputOutputHelper::new();
+    let dck_helper = &TestContainerHelper::new();
+    let dl_helper = &TestDownloadHelper::new(io_helper);
+
+    let one_cmd = Command {
+        name: "test",
+        description: "It's a test",
+        short_name: "tst",
+        min_args: 0,
+        max_args: 0,
+        usage: "",
+        need_config_file: true,
+        exec_cmd: test_help,
+    };
+
+    let commands = &[one_cmd];
+
+    let args = [];
+
+    // System file cannot be migrated by user
+    io_helper.files.borrow_mut().insert(
+        String::from(SYSTEM_CONFIG_FILE),
+        String::from("---\nformat_version: 0\ndownload_dir: \"dwn\"\n"),
+    );
+
+    match get_config_filename(Some(TEST_CONFIG_DIR)) {
+        Some(cfg_file) => {
+            // Create file
+            io_helper.files.borrow_mut().insert(cfg_file, String::from("---\nformat_version: 0\napplications_dir: \"app\"\ndockerfile:\n  from: \"tata\"\n  tag: \"tutu\"\n"))
+        }
+        None => panic!("Unable to get config filename for test"),
+    };
+
+    let exit_code = commands[0].exec(
+        &args,
+        io_helper,
+        dck_helper,
+        dl_helper,
+        Some(TEST_CONFIG_DIR),
+        &get_config(io_helper, Some(TEST_CONFIG_DIR)),
+    );
 
     assert_eq!(exit_code, CommandExitCode::Ok);
+    assert_eq!(
+        *io_helper.stderr.borrow(),
+        vec![String::from(
+            "Warning: 1 file(s) use an old format. Please run 'd-sh migrate' to upgrade them."
+        )]
+    );
 }
 
 #[test]
@@ -193,7 +289,7 @@ fn check_if_need_config_file_and_found_but_wrong_format() {
 
     let args = [];
 
-    match get_config_filename() {
+    match get_config_filename(Some(TEST_CONFIG_DIR)) {
         Some(cfg_file) => {
             // Create file
             io_helper
@@ -204,7 +300,14 @@ fn check_if_need_config_file_and_found_but_wrong_format() {
         None => panic!("Unable to get config filename for test"),
     };
 
-    let exit_code = commands[0].exec(&args, io_helper, dck_helper, dl_helper);
+    let exit_code = commands[0].exec(
+        &args,
+        io_helper,
+        dck_helper,
+        dl_helper,
+        Some(TEST_CONFIG_DIR),
+        &get_config(io_helper, Some(TEST_CONFIG_DIR)),
+    );
 
     assert_eq!(exit_code, CommandExitCode::ConfigFileFormatWrong);
 }
@@ -0,0 +1,282 @@
+///
+/// Module to validate config and application files.
+///
+/// Release under MIT License.
+///
+use command::{Command, CommandError, CommandExitCode, CommandParameter};
+use config::layer::{get_config_files, get_config_layers, get_env_layers, merge_config_layers};
+use config::schema::{check_application_file, check_config_file, check_config_value};
+use config::{get_application_filename, get_application_filenames, Config, ConfigApplication};
+use io::{convert_path, InputOutputHelper};
+use std::env;
+
+#[cfg(test)]
+mod tests;
+
+const UNKOWN_OPTIONS_MESSAGE: &'static str =
+    "d-sh validate: invalid option '{}'\nTry 'd-sh validate --help' for more information.\n";
+
+/// Extensions of downloaded files that need an `url`.
+const ARCHIVE_EXTENSIONS: [&str; 7] = [
+    ".deb", ".rpm", ".apk", ".tar.gz", ".tgz", ".tar.bz2", ".tar.xz",
+];
+
+///
+/// Return error of YAML file like `file:line:column: message`.
+///
+pub fn format_yaml_error(filename: &str, err: &serde_yaml::Error) -> String {
+    let msg = format!("{}", err);
+
+    match err.location() {
+        Some(location) => {
+            // Location is already given before message
+            let msg = match msg.rfind(" at line ") {
+                Some(pos) => &msg[..pos],
+                None => &msg,
+            };
+
+            format!(
+                "{}:{}:{}: {}",
+                filename,
+                location.line(),
+                location.column(),
+                msg
+            )
+        }
+        None => format!("{}: {}", filename, msg),
+    }
+}
+
+///
+/// Validate config files and merged config.
+///
+/// Return config if valid and list of errors.
+///
+fn validate_config(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+) -> (Option<Config>, Vec<String>) {
+    let dir = env::current_dir().ok();
+    let vars: Vec<(String, String)> = env::vars().collect();
+    let files = get_config_files(io_helper, config_dir, dir.as_deref());
+
+    if files.is_empty() {
+        return (
+            None,
+            vec![String::from(
+                "No config file found. Please run 'init' command first.",
+            )],
+        );
+    }
+
+    let mut errors = Vec::new();
+
+    for filename in &files {
+        let data = match io_helper.file_read_at_string(filename) {
+            Ok(r) => r,
+            Err(err) => {
+                errors.push(format!("{}: {}", filename, err));
+                continue;
+            }
+        };
+
+        // Each file is a part of config, unknown keys and wrong types are errors
+        if let Err(err) = check_config_file(&data) {
+            errors.push(format_yaml_error(filename, &err));
+        }
+    }
+
+    for layer in get_env_layers(&vars) {
+        if let Err(err) = check_config_value(&layer.value) {
+            errors.push(format!("{}: {}", layer.origin, err));
+        }
+    }
+
+    if !errors.is_empty() {
+        return (None, errors);
+    }
+
+    let layers = match get_config_layers(io_helper, config_dir, dir.as_deref(), &vars) {
+        Ok(r) => r,
+        Err(err) => return (None, vec![format!("{}", err)]),
+    };
+
+    match serde_yaml::from_value(merge_config_layers(&layers).value) {
+        Ok(config) => (Some(config), errors),
+        Err(err) => (
+            None,
+            vec![format!(
+                "Config merged from {} and D_SH_* variables: {}",
+                files.join(", "),
+                err
+            )],
+        ),
+    }
+}
+
+///
+/// Validate that folders of applications can be read.
+///
+fn validate_applications_dir(io_helper: &InputOutputHelper, config: &Config) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for dir in config.applications_dir.dirs() {
+        match io_helper.dir_list_file(dir, "*.yml") {
+            Ok(ref files) if files.is_empty() && !io_helper.file_exits(&convert_path(dir)) => {
+                errors.push(format!("Folder of applications '{}' doesn't exist!", dir))
+            }
+            Ok(_) => (),
+            Err(err) => errors.push(format!(
+                "Folder of applications '{}' is unreachable, {}!",
+                dir, err
+            )),
+        }
+    }
+
+    errors
+}
+
+///
+/// Validate an application file.
+///
+fn validate_application(io_helper: &InputOutputHelper, filename: &str) -> Vec<String> {
+    let data = match io_helper.file_read_at_string(&convert_path(filename)) {
+        Ok(r) => r,
+        Err(err) => return vec![format!("{}: {}", filename, err)],
+    };
+
+    if let Err(err) = check_application_file(&data) {
+        return vec![format_yaml_error(filename, &err)];
+    }
+
+    let config_application: ConfigApplication = match serde_yaml::from_str(&data) {
+        Ok(r) => r,
+        Err(err) => return vec![format_yaml_error(filename, &err)],
+    };
+
+    let mut errors = Vec::new();
+
+    if config_application.url.is_none() {
+        let download_filename = &config_application.download_filename;
+
+        if ARCHIVE_EXTENSIONS
+            .iter()
+            .any(|ext| download_filename.ends_with(ext))
+        {
+            errors.push(format!(
+                "{}: missing field `url` to download '{}'",
+                filename, download_filename
+            ));
+        }
+    }
+
+    errors
+}
+
+///
+/// Function to implement validate D-SH command.
+///
+/// `args` parameter is command line arguments of D-SH.
+///
+/// returning exit code of D-SH.
+///
+fn validate(cmd_param: CommandParameter) -> Result<(), CommandError> {
+    let mut applications = Vec::new();
+
+    for arg in cmd_param.args {
+        match arg.as_ref() {
+            "-h" | "--help" => {
+                cmd_param.io_helper.println(cmd_param.command.usage);
+                return Ok(());
+            }
+            option if option.starts_with("-") => {
+                return Err(CommandError {
+                    msg: vec![UNKOWN_OPTIONS_MESSAGE.replace("{}", option)],
+                    code: CommandExitCode::UnknowOption,
+                });
+            }
+            app => applications.push(app),
+        }
+    }
+
+    let config = match validate_config(cmd_param.io_helper, cmd_param.config_dir) {
+        (Some(config), _) => config,
+        (None, errors) => {
+            return Err(CommandError {
+                msg: errors,
+                code: CommandExitCode::ConfigFileFormatWrong,
+            });
+        }
+    };
+
+    let mut errors = validate_applications_dir(cmd_param.io_helper, &config);
+
+    let filenames = if applications.is_empty() {
+        match get_application_filenames(cmd_param.io_helper, &config) {
+            Ok(r) => r,
+            Err(err) => {
+                errors.push(format!("{}", err));
+                Vec::new()
+            }
+        }
+    } else {
+        let mut filenames = Vec::new();
+
+        for app in applications {
+            let filename = get_application_filename(cmd_param.io_helper, &config, app);
+
+            if cmd_param.io_helper.file_exits(&convert_path(&filename)) {
+                filenames.push(filename);
+            } else {
+                errors.push(format!("Application '{}' not found!", app));
+            }
+        }
+
+        filenames
+    };
+
+    for filename in &filenames {
+        errors.extend(validate_application(cmd_param.io_helper, filename));
+    }
+
+    if errors.is_empty() {
+        cmd_param.io_helper.println(&format!(
+            "Config and {} application file(s) are valid.",
+            filenames.len()
+        ));
+
+        Ok(())
+    } else {
+        Err(CommandError {
+            msg: errors,
+            code: CommandExitCode::BadApplicationFormat,
+        })
+    }
+}
+
+///
+/// The `validate` command.
+///
+pub const VALIDATE: Command = Command {
+    /// This command call by `validate`.
+    name: "validate",
+    /// description.
+    description: "Check config and application files",
+    /// Short name.
+    short_name: "val",
+    /// `validate` command have optional list of applications.
+    min_args: 0,
+    max_args: usize::MAX,
+    /// `validate` command help.
+    usage: "
+    Usage:	d-sh validate [APPLICATION...]
+
+    Check config files and application files (all applications by default). Each error
+    is displayed with its file, line and column. Exit code is not 0 if a file is wrong.
+
+    Options:
+      -h, --help               Display this help
+",
+    need_config_file: false,
+    exec_cmd: validate,
+};
@@ -0,0 +1,219 @@
+///
+/// Module to tests module validate.
+///
+/// Release under MIT License.
+///
+use super::{validate, VALIDATE};
+use command::tests::{test_result_err, test_result_ok, TEST_CONFIG_DIR};
+use command::{CommandExitCode, CommandParameter};
+use config::get_config_filename;
+use config::layer::SYSTEM_CONFIG_FILE;
+use docker::tests::TestContainerHelper;
+use download::tests::TestDownloadHelper;
+use io::tests::{found_item, TestInputOutputHelper};
+
+const CONFIG_DATA: &'static str = "---\ndownload_dir: \"dwn\"\napplications_dir: \"app\"\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\n";
+
+fn add_config_file(io_helper: &TestInputOutputHelper, data: &str) {
+    io_helper.files.borrow_mut().insert(
+        get_config_filename(Some(TEST_CONFIG_DIR)).unwrap(),
+        String::from(data),
+    );
+}
+
+#[test]
+fn validate_all_applications() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    add_config_file(io_helper, CONFIG_DATA);
+
+    io_helper.files.borrow_mut().insert(
+        String::from("app/atom.yml"),
+        String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nurl: \"https://atom.io/download/deb\"\n"),
+    );
+    io_helper.files.borrow_mut().insert(
+        String::from("app/filezilla.yml"),
+        String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla\"\n"),
+    );
+
+    let cmd_param = CommandParameter {
+        command: &VALIDATE,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    test_result_ok(validate(cmd_param));
+
+    found_item(
+        &io_helper.stdout.borrow(),
+        "Config and 2 application file(s) are valid.",
+    );
+}
+
+#[test]
+fn validate_config_file_with_bad_yaml() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    add_config_file(
+        io_helper,
+        "---\ndownload_dir: \"dwn\"\napplications_dir: [\"app\"\n",
+    );
+
+    let cmd_param = CommandParameter {
+        command: &VALIDATE,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(validate(cmd_param), CommandExitCode::ConfigFileFormatWrong);
+
+    assert_eq!(stderr.len(), 1);
+    assert!(stderr[0].starts_with("/home/d-sh/.d-sh/config.yml:4:1: "));
+}
+
+#[test]
+fn validate_config_file_with_unknown_key() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    add_config_file(
+        io_helper,
+        "---\ndownload_dir: \"dwn\"\napplications_dir: \"app\"\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tags: \"d-base-image:v1.0.0\"\n",
+    );
+
+    let cmd_param = CommandParameter {
+        command: &VALIDATE,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(validate(cmd_param), CommandExitCode::ConfigFileFormatWrong);
+
+    assert_eq!(stderr.len(), 1);
+    assert!(stderr[0].starts_with(
+        "/home/d-sh/.d-sh/config.yml:6:3: dockerfile: unknown field `tags`, expected"
+    ));
+}
+
+#[test]
+fn validate_each_config_file_of_layers() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    add_config_file(
+        io_helper,
+        "---\ndownload_dir: \"dwn\"\napplications_dir: \"app\"\nmy_note: \"x\"\n",
+    );
+
+    io_helper.files.borrow_mut().insert(
+        String::from(SYSTEM_CONFIG_FILE),
+        String::from("---\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\ncontainer_engine: \"rocket\"\n"),
+    );
+
+    let cmd_param = CommandParameter {
+        command: &VALIDATE,
+        args: &[],
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(validate(cmd_param), CommandExitCode::ConfigFileFormatWrong);
+
+    assert_eq!(stderr.len(), 2);
+    assert!(stderr[0]
+        .starts_with("/etc/d-sh/config.yml:5:19: container_engine: unknown variant `rocket`"));
+    assert!(
+        stderr[1].starts_with("/home/d-sh/.d-sh/config.yml:4:1: unknown field `my_note`, expected")
+    );
+}
+
+#[test]
+fn validate_application_with_errors() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    add_config_file(io_helper, CONFIG_DATA);
+
+    io_helper.files.borrow_mut().insert(
+        String::from("app/atom.yml"),
+        String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.tar.gz\"\n"),
+    );
+    io_helper.files.borrow_mut().insert(
+        String::from("app/filezilla.yml"),
+        String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla\"\ninteractiv: true\n"),
+    );
+
+    let args = [String::from("atom"), String::from("filezilla")];
+
+    let cmd_param = CommandParameter {
+        command: &VALIDATE,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(validate(cmd_param), CommandExitCode::BadApplicationFormat);
+
+    assert_eq!(stderr.len(), 2);
+    assert_eq!(
+        stderr[0],
+        "app/atom.yml: missing field `url` to download 'atom.tar.gz'"
+    );
+    assert!(stderr[1].starts_with("app/filezilla.yml:5:1: unknown field `interactiv`, expected"));
+}
+
+#[test]
+fn validate_unreachable_applications_dir() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+
+    add_config_file(io_helper, CONFIG_DATA);
+
+    let args = [String::from("atom")];
+
+    let cmd_param = CommandParameter {
+        command: &VALIDATE,
+        args: &args,
+        io_helper: io_helper,
+        dck_helper: dck_helper,
+        dl_helper: dl_helper,
+        config: None,
+        config_dir: Some(TEST_CONFIG_DIR),
+    };
+
+    let stderr = test_result_err(validate(cmd_param), CommandExitCode::BadApplicationFormat);
+
+    assert_eq!(
+        stderr,
+        vec![
+            String::from("Folder of applications 'app' doesn't exist!"),
+            String::from("Application 'atom' not found!"),
+        ]
+    );
+}
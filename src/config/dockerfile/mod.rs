@@ -14,24 +14,28 @@ pub const DOCKERFILE_BASE_FILENAME: &str = "Dockerfile.hbs";
 pub const DOCKERFILE_BASE: &str = r#"FROM {{dockerfile_from}}
 
 {{#if dockerfile_base}}
-    RUN apt-get update && \
-        apt-get install -y \
-          {{dependencies}}
+    RUN {{pkg_install package_manager}} \
+          {{dependencies}} && \
+        {{pkg_clean package_manager}}
 
     COPY entrypoint.sh /entrypoint.sh
 
     ENTRYPOINT ["/bin/sh", "/entrypoint.sh"]
 {{else}}
 
-{{#if (ends_width application_filename  ".deb")}}
+{{#if dependencies}}
+    RUN {{pkg_install package_manager}} \
+          {{dependencies}} && \
+        {{pkg_clean package_manager}}
+{{/if}}
+
+{{#if (or (ends_width application_filename  ".deb") (or (ends_width application_filename  ".rpm") (ends_width application_filename  ".apk")))}}
     COPY {{application_filename}} /tmp/
 
-    RUN apt-get update && \
-        apt-get install -y \
+    RUN {{pkg_install_file package_manager}} \
           /tmp/{{application_filename}} && \
         rm -f /tmp/{{application_filename}} && \
-        apt-get clean && \
-        rm -rf /var/lib/apt/lists/*
+        {{pkg_clean package_manager}}
 {{else}}
 
 {{#if (ends_width application_filename  ".tar.bz2")}}
@@ -55,10 +59,8 @@ pub const DOCKERFILE_BASE: &str = r#"FROM {{dockerfile_from}}
         rm -f /tmp/{{application_filename}}
 {{else}}
 
-RUN apt-get update && \
-    apt-get install -y {{application_filename}} && \
-    apt-get clean && \
-    rm -rf /var/lib/apt/lists/*
+RUN {{pkg_install package_manager}} {{application_filename}} && \
+    {{pkg_clean package_manager}}
 
 {{/if}}
 
@@ -0,0 +1,438 @@
+///
+/// Module to merge config files of system, user and project, and environment variables.
+///
+/// Release under MIT License.
+///
+use config::get_config_filename;
+use config::schema::check_config_value;
+use config::serde_yaml::{Mapping, Value};
+use io::InputOutputHelper;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// Config file of system, shared by all users.
+pub const SYSTEM_CONFIG_FILE: &str = "/etc/d-sh/config.yml";
+/// Config file of project, searched from current folder to root.
+pub const PROJECT_CONFIG_FILE: &str = ".d-sh.yml";
+/// Prefix of environment variables overriding config.
+pub const ENV_PREFIX: &str = "D_SH_";
+/// Keys of config overridden by environment variable, like `D_SH_TMP_DIR`.
+const ENV_KEYS: [&str; 7] = [
+    "download_dir",
+    "applications_dir",
+    "tmp_dir",
+    "dependencies_layer",
+    "container_engine",
+    "docker_host",
+    "docker_context",
+];
+/// Keys of config with paths, relative paths of project config are relative to its folder.
+const PATH_KEYS: [&str; 3] = ["download_dir", "applications_dir", "tmp_dir"];
+
+///
+/// Where a value of config come from.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConfigOrigin {
+    /// Config file
+    File(String),
+    /// Environment variable
+    Env(String),
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigOrigin::File(filename) => write!(f, "file:{}", filename),
+            ConfigOrigin::Env(name) => write!(f, "env:{}", name),
+        }
+    }
+}
+
+///
+/// One layer of config, next layers override it.
+///
+#[derive(Debug, PartialEq)]
+pub struct ConfigLayer {
+    pub origin: ConfigOrigin,
+    /// Values of layer, always a mapping
+    pub value: Value,
+}
+
+///
+/// Config of all layers.
+///
+#[derive(Debug, PartialEq)]
+pub struct MergedConfig {
+    /// Values of config, always a mapping
+    pub value: Value,
+    /// Origin of each value, by key like `dockerfile.from`
+    pub origins: BTreeMap<String, ConfigOrigin>,
+}
+
+impl MergedConfig {
+    ///
+    /// Return value of key like `dockerfile.from`.
+    ///
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        let mut value = &self.value;
+
+        for name in key.split('.') {
+            value = value
+                .as_mapping()?
+                .get(&Value::String(String::from(name)))?;
+        }
+
+        Some(value)
+    }
+}
+
+///
+/// Return project config file, searched from `dir` to root.
+///
+pub fn get_project_config_filename(io_helper: &InputOutputHelper, dir: &Path) -> Option<String> {
+    for folder in dir.ancestors() {
+        if let Some(filename) = folder.join(PROJECT_CONFIG_FILE).to_str() {
+            if io_helper.file_exits(filename) {
+                return Some(String::from(filename));
+            }
+        }
+    }
+
+    None
+}
+
+///
+/// Return existing config files, in order of override.
+///
+/// `config_dir` is folder of user config, `dir` is folder where project config is searched.
+///
+pub fn get_config_files(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    dir: Option<&Path>,
+) -> Vec<String> {
+    let mut result = Vec::new();
+
+    if io_helper.file_exits(SYSTEM_CONFIG_FILE) {
+        result.push(String::from(SYSTEM_CONFIG_FILE));
+    }
+
+    if let Some(filename) = get_config_filename(config_dir) {
+        if io_helper.file_exits(&filename) {
+            result.push(filename);
+        }
+    }
+
+    if let Some(filename) = dir.and_then(|d| get_project_config_filename(io_helper, d)) {
+        if !result.contains(&filename) {
+            result.push(filename);
+        }
+    }
+
+    result
+}
+
+///
+/// Return path relative to `dir` if path is relative.
+///
+fn resolve_path(dir: &Path, path: &str) -> String {
+    if path.starts_with('/') || path.starts_with('~') {
+        String::from(path)
+    } else {
+        dir.join(path).to_str().unwrap_or(path).to_string()
+    }
+}
+
+///
+/// Make relative paths of project config relative to its folder.
+///
+fn resolve_project_paths(value: &mut Value, dir: &Path) {
+    if let Value::Mapping(ref mut mapping) = value {
+        for key in PATH_KEYS.iter() {
+            match mapping.get_mut(&Value::String(key.to_string())) {
+                Some(Value::String(ref mut path)) => *path = resolve_path(dir, path),
+                Some(Value::Sequence(ref mut paths)) => {
+                    for path in paths.iter_mut() {
+                        if let Value::String(ref mut p) = path {
+                            *p = resolve_path(dir, p);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+///
+/// Read layer of config file.
+///
+pub fn read_config_layer(
+    io_helper: &InputOutputHelper,
+    filename: &str,
+) -> Result<ConfigLayer, Error> {
+    let data = io_helper.file_read_at_string(filename)?;
+
+    let value = match serde_yaml::from_str(&data) {
+        Ok(Value::Mapping(m)) => Value::Mapping(m),
+        // Empty file
+        Ok(Value::Null) => Value::Mapping(Mapping::new()),
+        Ok(_) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("File format of config file '{}' is wrong!", filename),
+            ));
+        }
+        Err(err) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "File format of config file '{}' is wrong, {}!",
+                    filename, err
+                ),
+            ));
+        }
+    };
+
+    Ok(ConfigLayer {
+        origin: ConfigOrigin::File(String::from(filename)),
+        value: value,
+    })
+}
+
+///
+/// Return value read as YAML, like `[app, team]` or `true`. Mapping or wrong YAML is a string.
+///
+pub fn parse_value(data: &str) -> Value {
+    match serde_yaml::from_str(data) {
+        Ok(Value::Mapping(_)) | Ok(Value::Null) | Err(_) => Value::String(String::from(data)),
+        Ok(v) => v,
+    }
+}
+
+///
+/// Return value of key like `dockerfile.tag` read as YAML, keeping text if key need a string.
+///
+/// Like that, `20.04` is a string for `dockerfile.tag` and not a number.
+///
+pub fn parse_config_value(key: &str, data: &str) -> Value {
+    let value = parse_value(data);
+
+    if value.is_string() {
+        return value;
+    }
+
+    let mut config = Value::Mapping(Mapping::new());
+
+    set_value(&mut config, key, value.clone());
+
+    if check_config_value(&config).is_ok() {
+        return value;
+    }
+
+    set_value(&mut config, key, Value::String(String::from(data)));
+
+    if check_config_value(&config).is_ok() {
+        Value::String(String::from(data))
+    } else {
+        // Wrong value is kept to display error of type
+        value
+    }
+}
+
+///
+/// Set value of key like `dockerfile.from`. Missing mappings are created.
+///
+pub fn set_value(value: &mut Value, key: &str, new_value: Value) {
+    let mut names: Vec<&str> = key.split('.').collect();
+    let last = names.pop().unwrap_or(key);
+    let mut current = value;
+
+    for name in names {
+        let k = Value::String(String::from(name));
+
+        if !current.is_mapping() {
+            *current = Value::Mapping(Mapping::new());
+        }
+
+        let mapping = current.as_mapping_mut().unwrap();
+
+        if mapping.get(&k).is_none() {
+            mapping.insert(k.clone(), Value::Mapping(Mapping::new()));
+        }
+
+        // Value that is not a mapping is replaced in next loop
+        current = mapping.get_mut(&k).unwrap();
+    }
+
+    if !current.is_mapping() {
+        *current = Value::Mapping(Mapping::new());
+    }
+
+    let mapping = current.as_mapping_mut().unwrap();
+    let k = Value::String(String::from(last));
+
+    // Keep order of existing key
+    match mapping.get_mut(&k) {
+        Some(v) => *v = new_value,
+        None => {
+            mapping.insert(k, new_value);
+        }
+    }
+}
+
+///
+/// Remove key like `dockerfile.from`, returning old value.
+///
+pub fn remove_value(value: &mut Value, key: &str) -> Option<Value> {
+    let mut names: Vec<&str> = key.split('.').collect();
+    let last = names.pop()?;
+    let mut current = value;
+
+    for name in names {
+        current = current
+            .as_mapping_mut()?
+            .get_mut(&Value::String(String::from(name)))?;
+    }
+
+    current
+        .as_mapping_mut()?
+        .remove(&Value::String(String::from(last)))
+}
+
+///
+/// Return layers of environment variables, like `D_SH_TMP_DIR=/var/tmp`.
+///
+/// Value is read as YAML, so `D_SH_APPLICATIONS_DIR="[app, ~/team]"` is a list.
+///
+pub fn get_env_layers(vars: &[(String, String)]) -> Vec<ConfigLayer> {
+    let mut result = Vec::new();
+
+    for key in ENV_KEYS.iter() {
+        let name = format!("{}{}", ENV_PREFIX, key.to_uppercase());
+
+        if let Some((_, data)) = vars.iter().find(|(n, _)| *n == name) {
+            let value = parse_config_value(key, data);
+
+            let mut mapping = Mapping::new();
+            mapping.insert(Value::String(key.to_string()), value);
+
+            result.push(ConfigLayer {
+                origin: ConfigOrigin::Env(name),
+                value: Value::Mapping(mapping),
+            });
+        }
+    }
+
+    result
+}
+
+///
+/// Read all layers: system, user and project config files, then environment variables.
+///
+/// `config_dir` is folder of user config, `dir` is folder where project config is searched.
+///
+pub fn get_config_layers(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+    dir: Option<&Path>,
+    vars: &[(String, String)],
+) -> Result<Vec<ConfigLayer>, Error> {
+    let mut result = Vec::new();
+
+    for filename in get_config_files(io_helper, config_dir, dir) {
+        let mut layer = read_config_layer(io_helper, &filename)?;
+
+        if filename.ends_with(PROJECT_CONFIG_FILE) {
+            if let Some(project_dir) = Path::new(&filename).parent() {
+                resolve_project_paths(&mut layer.value, project_dir);
+            }
+        }
+
+        result.push(layer);
+    }
+
+    result.extend(get_env_layers(vars));
+
+    Ok(result)
+}
+
+///
+/// Remove origin of key and of its children.
+///
+fn remove_origins(origins: &mut BTreeMap<String, ConfigOrigin>, name: &str) {
+    let prefix = format!("{}.", name);
+    let keys: Vec<String> = origins
+        .keys()
+        .filter(|k| *k == name || k.starts_with(&prefix))
+        .cloned()
+        .collect();
+
+    for k in keys {
+        origins.remove(&k);
+    }
+}
+
+///
+/// Merge `value` in `target`, mappings are merged, other values are replaced.
+///
+fn merge_value(
+    target: &mut Mapping,
+    value: &Mapping,
+    prefix: &str,
+    origin: &ConfigOrigin,
+    origins: &mut BTreeMap<String, ConfigOrigin>,
+) {
+    for (key, v) in value.iter() {
+        let name = match key.as_str() {
+            Some(k) => format!("{}{}", prefix, k),
+            None => continue,
+        };
+
+        match v {
+            Value::Mapping(m) => {
+                let is_mapping = matches!(target.get(key), Some(Value::Mapping(_)));
+
+                // Value is replaced by mapping
+                if !is_mapping {
+                    remove_origins(origins, &name);
+                    target.insert(key.clone(), Value::Mapping(Mapping::new()));
+                }
+
+                if let Some(Value::Mapping(ref mut t)) = target.get_mut(key) {
+                    merge_value(t, m, &format!("{}.", name), origin, origins);
+                }
+            }
+            _ => {
+                remove_origins(origins, &name);
+                target.insert(key.clone(), v.clone());
+                origins.insert(name, origin.clone());
+            }
+        }
+    }
+}
+
+///
+/// Merge layers, next layers override previous.
+///
+pub fn merge_config_layers(layers: &[ConfigLayer]) -> MergedConfig {
+    let mut value = Mapping::new();
+    let mut origins = BTreeMap::new();
+
+    for layer in layers {
+        if let Value::Mapping(ref m) = layer.value {
+            merge_value(&mut value, m, "", &layer.origin, &mut origins);
+        }
+    }
+
+    MergedConfig {
+        value: Value::Mapping(value),
+        origins: origins,
+    }
+}
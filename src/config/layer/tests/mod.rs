@@ -0,0 +1,247 @@
+///
+/// Module to tests module layer.
+///
+/// Release under MIT License.
+///
+use super::{
+    get_config_layers, get_env_layers, get_project_config_filename, merge_config_layers,
+    parse_config_value, parse_value, remove_value, set_value, ConfigLayer, ConfigOrigin,
+    SYSTEM_CONFIG_FILE,
+};
+use config::get_config_dir;
+use config::serde_yaml::{from_str, Value};
+use io::tests::TestInputOutputHelper;
+use std::path::Path;
+
+fn file_layer(filename: &str, data: &str) -> ConfigLayer {
+    ConfigLayer {
+        origin: ConfigOrigin::File(String::from(filename)),
+        value: from_str(data).unwrap(),
+    }
+}
+
+#[test]
+fn merge_layers_override_values() {
+    let layers = vec![
+        file_layer(
+            "/etc/d-sh/config.yml",
+            "---\ndownload_dir: \"/opt/dwn\"\ndockerfile:\n  from: \"ubuntu:18.04\"\n  tag: \"d-base-image:v1.0.0\"\n",
+        ),
+        file_layer(
+            "/home/me/.d-sh/config.yml",
+            "---\napplications_dir: \"app\"\ndockerfile:\n  tag: \"my-base:v1\"\n",
+        ),
+    ];
+
+    let merged = merge_config_layers(&layers);
+
+    assert_eq!(
+        merged.get("download_dir"),
+        Some(&Value::String(String::from("/opt/dwn")))
+    );
+    assert_eq!(
+        merged.get("dockerfile.from"),
+        Some(&Value::String(String::from("ubuntu:18.04")))
+    );
+    assert_eq!(
+        merged.get("dockerfile.tag"),
+        Some(&Value::String(String::from("my-base:v1")))
+    );
+    assert_eq!(
+        merged.origins.get("dockerfile.from"),
+        Some(&ConfigOrigin::File(String::from("/etc/d-sh/config.yml")))
+    );
+    assert_eq!(
+        merged.origins.get("dockerfile.tag"),
+        Some(&ConfigOrigin::File(String::from(
+            "/home/me/.d-sh/config.yml"
+        )))
+    );
+    assert!(merged.get("dockerfile.template").is_none());
+}
+
+#[test]
+fn merge_layers_replace_list_and_mapping() {
+    let layers = vec![
+        file_layer(
+            "a.yml",
+            "---\napplications_dir:\n  - app\n  - team\nregistry:\n  url: \"registry:5000\"\n",
+        ),
+        file_layer("b.yml", "---\napplications_dir: other\nregistry: ~\n"),
+    ];
+
+    let merged = merge_config_layers(&layers);
+
+    assert_eq!(
+        merged.get("applications_dir"),
+        Some(&Value::String(String::from("other")))
+    );
+    assert_eq!(merged.get("registry"), Some(&Value::Null));
+    assert_eq!(
+        merged.origins.keys().cloned().collect::<Vec<String>>(),
+        vec![String::from("applications_dir"), String::from("registry")]
+    );
+}
+
+#[test]
+fn env_layers_override_known_keys() {
+    let vars = vec![
+        (String::from("D_SH_TMP_DIR"), String::from("/var/tmp")),
+        (
+            String::from("D_SH_APPLICATIONS_DIR"),
+            String::from("[app, ~/team]"),
+        ),
+        (String::from("D_SH_UNKNOWN"), String::from("1")),
+        (String::from("HOME"), String::from("/home/me")),
+    ];
+
+    let merged = merge_config_layers(&get_env_layers(&vars));
+
+    assert_eq!(
+        merged.get("tmp_dir"),
+        Some(&Value::String(String::from("/var/tmp")))
+    );
+    assert_eq!(
+        merged.get("applications_dir"),
+        Some(&Value::Sequence(vec![
+            Value::String(String::from("app")),
+            Value::String(String::from("~/team")),
+        ]))
+    );
+    assert_eq!(
+        merged.origins.get("tmp_dir"),
+        Some(&ConfigOrigin::Env(String::from("D_SH_TMP_DIR")))
+    );
+    assert_eq!(merged.origins.len(), 2);
+}
+
+#[test]
+fn project_config_found_in_parent_folder() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(
+        String::from("/home/me/project/.d-sh.yml"),
+        String::from("---\napplications_dir: \"apps\"\ntmp_dir: \"/tmp\"\n"),
+    );
+    io_helper.files.borrow_mut().insert(
+        String::from(SYSTEM_CONFIG_FILE),
+        String::from("---\ndownload_dir: \"/opt/dwn\"\n"),
+    );
+
+    let dir = Path::new("/home/me/project/src/module");
+
+    assert_eq!(
+        get_project_config_filename(io_helper, dir),
+        Some(String::from("/home/me/project/.d-sh.yml"))
+    );
+    assert_eq!(
+        get_project_config_filename(io_helper, Path::new("/home/me")),
+        None
+    );
+
+    let layers = get_config_layers(io_helper, Some("/home/d-sh/.d-sh/"), Some(dir), &[]).unwrap();
+    let merged = merge_config_layers(&layers);
+
+    assert_eq!(layers.len(), 2);
+    // Relative path of project config is relative to its folder
+    assert_eq!(
+        merged.get("applications_dir"),
+        Some(&Value::String(String::from("/home/me/project/apps")))
+    );
+    assert_eq!(
+        merged.get("tmp_dir"),
+        Some(&Value::String(String::from("/tmp")))
+    );
+    assert_eq!(
+        format!("{}", merged.origins.get("download_dir").unwrap()),
+        "file:/etc/d-sh/config.yml"
+    );
+}
+
+#[test]
+fn config_file_with_bad_format() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(
+        String::from(SYSTEM_CONFIG_FILE),
+        String::from("- download_dir"),
+    );
+
+    match get_config_layers(io_helper, Some("/home/d-sh/.d-sh/"), None, &[]) {
+        Err(err) => assert_eq!(
+            format!("{}", err),
+            "File format of config file '/etc/d-sh/config.yml' is wrong!"
+        ),
+        Ok(_) => panic!("Config file must be wrong"),
+    }
+}
+
+#[test]
+fn user_config_read_from_config_dir() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(
+        String::from("/opt/d-sh/config.yml"),
+        String::from("---\ndownload_dir: \"/opt/dwn\"\n"),
+    );
+
+    let config_dir = get_config_dir(Some("/opt/d-sh"));
+
+    assert_eq!(config_dir, Some(String::from("/opt/d-sh/")));
+
+    let layers = get_config_layers(io_helper, config_dir.as_deref(), None, &[]).unwrap();
+    let merged = merge_config_layers(&layers);
+
+    assert_eq!(layers.len(), 1);
+    assert_eq!(
+        format!("{}", merged.origins.get("download_dir").unwrap()),
+        "file:/opt/d-sh/config.yml"
+    );
+}
+
+#[test]
+fn set_and_remove_value_of_key() {
+    let mut value: Value = from_str("---\ndownload_dir: \"dwn\"\nregistry: \"old\"\n").unwrap();
+
+    set_value(&mut value, "dockerfile.from", parse_value("ubuntu:18.04"));
+    // Value replaced by mapping
+    set_value(&mut value, "registry.cache", parse_value("false"));
+    set_value(&mut value, "applications_dir", parse_value("[app, team]"));
+
+    assert_eq!(
+        value,
+        from_str::<Value>("---\ndownload_dir: \"dwn\"\nregistry:\n  cache: false\ndockerfile:\n  from: \"ubuntu:18.04\"\napplications_dir:\n  - app\n  - team\n").unwrap()
+    );
+
+    assert_eq!(
+        remove_value(&mut value, "dockerfile.from"),
+        Some(Value::String(String::from("ubuntu:18.04")))
+    );
+    assert_eq!(remove_value(&mut value, "dockerfile.tag"), None);
+    assert_eq!(remove_value(&mut value, "download_dir.tag"), None);
+}
+
+#[test]
+fn parse_config_value_keep_string_of_key() {
+    assert_eq!(
+        parse_config_value("dockerfile.tag", "20.04"),
+        Value::String(String::from("20.04"))
+    );
+    assert_eq!(
+        parse_config_value("tmp_dir", "true"),
+        Value::String(String::from("true"))
+    );
+    assert_eq!(
+        parse_config_value("registry.cache", "false"),
+        Value::Bool(false)
+    );
+    assert_eq!(
+        parse_config_value("applications_dir", "[app, team]"),
+        from_str::<Value>("[app, team]").unwrap()
+    );
+    // Unknown key is read as YAML
+    assert_eq!(
+        parse_config_value("my_note", "12"),
+        from_str::<Value>("12").unwrap()
+    );
+}
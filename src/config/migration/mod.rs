@@ -0,0 +1,260 @@
+///
+/// Module to migrate config and application files to current format.
+///
+/// Release under MIT License.
+///
+use config::serde_yaml::{Mapping, Value};
+use config::{get_application_filenames, Config};
+use io::InputOutputHelper;
+use std::io::{Error, ErrorKind};
+
+#[cfg(test)]
+mod tests;
+
+/// Current version of config file format.
+pub const CONFIG_VERSION: u64 = 1;
+/// Current version of application file format.
+pub const APPLICATION_VERSION: u64 = 1;
+
+/// Key of format version in config and application files.
+pub const FORMAT_VERSION_KEY: &str = "format_version";
+/// Version of files without format version, written before version exists.
+const UNVERSIONED_VERSION: u64 = 1;
+
+/// Kind of file with a format version
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SchemaKind {
+    Config,
+    Application,
+}
+
+/// Migration of a file from version `from` to next version
+// No migration yet, files without version have format of version 1
+#[allow(dead_code)]
+struct Migration {
+    from: u64,
+    migrate: fn(&mut Mapping),
+}
+
+/// Migrations of config file, in order.
+const CONFIG_MIGRATIONS: &'static [Migration] = &[];
+
+/// Migrations of application file, in order.
+const APPLICATION_MIGRATIONS: &'static [Migration] = &[];
+
+impl SchemaKind {
+    ///
+    /// Return current version of format.
+    ///
+    pub fn current_version(&self) -> u64 {
+        match self {
+            SchemaKind::Config => CONFIG_VERSION,
+            SchemaKind::Application => APPLICATION_VERSION,
+        }
+    }
+
+    fn migrations(&self) -> &'static [Migration] {
+        match self {
+            SchemaKind::Config => CONFIG_MIGRATIONS,
+            SchemaKind::Application => APPLICATION_MIGRATIONS,
+        }
+    }
+}
+
+///
+/// Return format version of file content, version 1 if not set.
+///
+pub fn get_version(value: &Value) -> u64 {
+    match value {
+        Value::Mapping(m) => m
+            .get(&Value::from(FORMAT_VERSION_KEY))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(UNVERSIONED_VERSION),
+        _ => UNVERSIONED_VERSION,
+    }
+}
+
+///
+/// Migrate file content to current version.
+///
+/// Return version before migration.
+///
+pub fn migrate_value(value: &mut Value, kind: SchemaKind) -> Result<u64, Error> {
+    let version = get_version(value);
+
+    if version > kind.current_version() {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "Format version {} is not supported, please update D-SH (supported version {})!",
+                version,
+                kind.current_version()
+            ),
+        ));
+    }
+
+    if let Value::Mapping(ref mut m) = value {
+        for migration in kind.migrations().iter().filter(|m| m.from >= version) {
+            (migration.migrate)(m);
+        }
+
+        if version < kind.current_version() {
+            // Version is the first key of file
+            let mut new_mapping = Mapping::new();
+            let key = Value::from(FORMAT_VERSION_KEY);
+
+            new_mapping.insert(key.clone(), Value::from(kind.current_version()));
+
+            for (k, v) in m.iter() {
+                if *k != key {
+                    new_mapping.insert(k.clone(), v.clone());
+                }
+            }
+
+            *m = new_mapping;
+        }
+    }
+
+    Ok(version)
+}
+
+///
+/// Read and parse file, `None` if file is empty.
+///
+fn read_value(io_helper: &InputOutputHelper, filename: &str) -> Result<Option<Value>, Error> {
+    let data = io_helper.file_read_at_string(filename)?;
+
+    match serde_yaml::from_str(&data) {
+        Ok(Value::Null) => Ok(None),
+        Ok(r) => Ok(Some(r)),
+        Err(err) => Err(Error::new(
+            ErrorKind::Other,
+            format!("File format of '{}' is wrong, {}!", filename, err),
+        )),
+    }
+}
+
+///
+/// Return true if file must be migrated. File that cannot be read is not migrated.
+///
+pub fn need_migration(io_helper: &InputOutputHelper, filename: &str, kind: SchemaKind) -> bool {
+    match read_value(io_helper, filename) {
+        Ok(Some(value)) => get_version(&value) < kind.current_version(),
+        _ => false,
+    }
+}
+
+///
+/// Migrate file in place. Old file is kept in `FILENAME.vVERSION.bak`.
+///
+/// Return filename of old file if file is migrated.
+///
+pub fn migrate_file(
+    io_helper: &InputOutputHelper,
+    filename: &str,
+    kind: SchemaKind,
+) -> Result<Option<String>, Error> {
+    let mut value = match read_value(io_helper, filename)? {
+        Some(r) => r,
+        None => return Ok(None),
+    };
+    let old_value = value.clone();
+
+    let version = migrate_value(&mut value, kind)?;
+
+    if version == kind.current_version() {
+        return Ok(None);
+    }
+
+    let old_data = io_helper.file_read_at_string(filename)?;
+
+    // If only format version change, keep comments and layout of file
+    let data = if without_version(&old_value) == without_version(&value) {
+        set_version_line(&old_data, kind.current_version())
+    } else {
+        match serde_yaml::to_string(&value) {
+            Ok(r) => format!("{}\n", r),
+            Err(err) => return Err(Error::new(ErrorKind::Other, format!("{}", err))),
+        }
+    };
+
+    let backup_filename = format!("{}.v{}.bak", filename, version);
+
+    io_helper.file_write(&backup_filename, &old_data)?;
+    io_helper.file_write(filename, &data)?;
+
+    Ok(Some(backup_filename))
+}
+
+///
+/// Return file content without format version.
+///
+fn without_version(value: &Value) -> Value {
+    let mut value = value.clone();
+
+    if let Value::Mapping(ref mut m) = value {
+        m.remove(&Value::from(FORMAT_VERSION_KEY));
+    }
+
+    value
+}
+
+///
+/// Set format version as first key of file, after `---` if present.
+///
+fn set_version_line(data: &str, version: u64) -> String {
+    let version_prefix = format!("{}:", FORMAT_VERSION_KEY);
+    let mut lines: Vec<String> = data
+        .lines()
+        .filter(|l| !l.starts_with(&version_prefix))
+        .map(String::from)
+        .collect();
+
+    // Comments and blank lines can be before `---`
+    let position = match lines.iter().position(|l| {
+        let l = l.trim();
+        !l.is_empty() && !l.starts_with('#')
+    }) {
+        Some(i) if lines[i].trim_end() == "---" => i + 1,
+        _ => 0,
+    };
+
+    lines.insert(position, format!("{} {}", version_prefix, version));
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+
+    result
+}
+
+///
+/// Return config files and application files that must be migrated.
+///
+/// Application files are only searched if `config` is given.
+///
+pub fn get_files_to_migrate(
+    io_helper: &InputOutputHelper,
+    config_files: &[String],
+    config: Option<&Config>,
+) -> Vec<(String, SchemaKind)> {
+    let mut result: Vec<(String, SchemaKind)> = config_files
+        .iter()
+        .filter(|f| need_migration(io_helper, f, SchemaKind::Config))
+        .map(|f| (f.clone(), SchemaKind::Config))
+        .collect();
+
+    if let Some(config) = config {
+        let mut filenames = get_application_filenames(io_helper, config).unwrap_or_default();
+
+        filenames.sort();
+
+        result.extend(
+            filenames
+                .into_iter()
+                .filter(|f| need_migration(io_helper, f, SchemaKind::Application))
+                .map(|f| (f, SchemaKind::Application)),
+        );
+    }
+
+    result
+}
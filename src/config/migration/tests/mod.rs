@@ -0,0 +1,135 @@
+///
+/// Module to tests module migration.
+///
+/// Release under MIT License.
+///
+use super::{
+    get_files_to_migrate, get_version, migrate_file, migrate_value, need_migration, SchemaKind,
+    CONFIG_VERSION,
+};
+use config::serde_yaml::{from_str, Value};
+use io::tests::TestInputOutputHelper;
+
+#[test]
+fn migrate_value_without_version() {
+    let mut value: Value = from_str("---\ndownload_dir: \"dwn\"\n").unwrap();
+    let old_value = value.clone();
+
+    // File written before format version is version 1
+    assert_eq!(get_version(&value), 1);
+    assert_eq!(migrate_value(&mut value, SchemaKind::Config).unwrap(), 1);
+    assert_eq!(value, old_value);
+}
+
+#[test]
+fn migrate_value_with_old_version() {
+    let mut value: Value = from_str("---\nformat_version: 0\ndownload_dir: \"dwn\"\n").unwrap();
+
+    assert_eq!(migrate_value(&mut value, SchemaKind::Config).unwrap(), 0);
+    assert_eq!(get_version(&value), CONFIG_VERSION);
+}
+
+#[test]
+fn migrate_value_with_newer_version() {
+    let mut value: Value = from_str("---\nformat_version: 999\ndownload_dir: \"dwn\"\n").unwrap();
+
+    assert!(migrate_value(&mut value, SchemaKind::Config).is_err());
+}
+
+#[test]
+fn migrate_application_file_with_backup() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let data = "---\nformat_version: 0\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nversion: \"1.32.0\"\n";
+
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("app/atom.yml"), String::from(data));
+
+    assert!(need_migration(
+        io_helper,
+        "app/atom.yml",
+        SchemaKind::Application
+    ));
+    assert_eq!(
+        migrate_file(io_helper, "app/atom.yml", SchemaKind::Application).unwrap(),
+        Some(String::from("app/atom.yml.v0.bak"))
+    );
+    assert!(!need_migration(
+        io_helper,
+        "app/atom.yml",
+        SchemaKind::Application
+    ));
+
+    let files = io_helper.files.borrow();
+
+    assert_eq!(files.get("app/atom.yml.v0.bak").unwrap(), data);
+    // Format version first, version of application is kept
+    assert_eq!(
+        files.get("app/atom.yml").unwrap(),
+        "---\nformat_version: 1\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\nversion: \"1.32.0\"\n"
+    );
+}
+
+#[test]
+fn migrate_config_file_keep_comments() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(
+        String::from("config.yml"),
+        String::from("# My config\n---\nformat_version: 0\n# Where downloads go\ndownload_dir: \"dwn\" # shared\n"),
+    );
+
+    assert_eq!(
+        migrate_file(io_helper, "config.yml", SchemaKind::Config).unwrap(),
+        Some(String::from("config.yml.v0.bak"))
+    );
+    assert_eq!(
+        io_helper.files.borrow().get("config.yml").unwrap(),
+        "# My config\n---\nformat_version: 1\n# Where downloads go\ndownload_dir: \"dwn\" # shared\n"
+    );
+}
+
+#[test]
+fn files_without_version_are_not_migrated() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(
+        String::from("config.yml"),
+        String::from("---\ndownload_dir: \"dwn\"\n"),
+    );
+
+    assert!(!need_migration(io_helper, "config.yml", SchemaKind::Config));
+    assert_eq!(
+        migrate_file(io_helper, "config.yml", SchemaKind::Config).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn files_to_migrate_without_config() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+
+    io_helper.files.borrow_mut().insert(
+        String::from("/etc/d-sh/config.yml"),
+        String::from("---\nformat_version: 0\ndownload_dir: \"dwn\"\n"),
+    );
+    io_helper.files.borrow_mut().insert(
+        String::from("/home/d-sh/.d-sh/config.yml"),
+        String::from("---\nformat_version: 1\napplications_dir: \"app\"\n"),
+    );
+
+    let files = get_files_to_migrate(
+        io_helper,
+        &[
+            String::from("/etc/d-sh/config.yml"),
+            String::from("/home/d-sh/.d-sh/config.yml"),
+        ],
+        None,
+    );
+
+    assert_eq!(
+        files,
+        vec![(String::from("/etc/d-sh/config.yml"), SchemaKind::Config)]
+    );
+}
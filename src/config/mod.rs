@@ -6,27 +6,165 @@
 extern crate serde_yaml;
 
 pub mod dockerfile;
+pub mod layer;
+pub mod migration;
+pub mod package_manager;
+pub mod schema;
 
+#[cfg(test)]
+pub mod tests;
+
+use self::layer::{get_config_layers, merge_config_layers};
+use self::package_manager::PackageManager;
 use dirs::home_dir;
 use io::convert_path;
 use io::InputOutputHelper;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
 use std::io::{Error, ErrorKind};
 use std::path::Path;
+use template::Template;
 
-/// Config structure of D-SH
+/// Config structure of base image
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ConfigDocker {
     pub from: String,
     pub tag: String,
+    /// Template of Dockerfile in config folder, by default `Dockerfile.hbs`
+    pub template: Option<String>,
+    /// Package manager of base image, by default `apt`
+    pub package_manager: Option<PackageManager>,
+}
+
+/// Dependencies of application
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConfigDependencies {
+    /// Same dependencies whatever package manager
+    List(Vec<String>),
+    /// Dependencies by package manager name (`apt`, `dnf`, `apk`, `zypper`)
+    ByPackageManager(HashMap<String, Vec<String>>),
+}
+
+impl ConfigDependencies {
+    ///
+    /// Return dependencies for a package manager.
+    ///
+    pub fn get(&self, package_manager: &PackageManager) -> Vec<String> {
+        match self {
+            ConfigDependencies::List(d) => d.clone(),
+            ConfigDependencies::ByPackageManager(d) => match d.get(package_manager.name()) {
+                Some(r) => r.clone(),
+                None => Vec::new(),
+            },
+        }
+    }
+}
+
+/// Folders of application files
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConfigApplicationsDir {
+    /// One folder
+    One(String),
+    /// Folders searched in order
+    List(Vec<String>),
+}
+
+impl ConfigApplicationsDir {
+    ///
+    /// Return folders in search order.
+    ///
+    pub fn dirs(&self) -> Vec<&str> {
+        match self {
+            ConfigApplicationsDir::One(d) => vec![d.as_str()],
+            ConfigApplicationsDir::List(d) => d.iter().map(|d| d.as_str()).collect(),
+        }
+    }
+
+    ///
+    /// Return folder where applications are added, the first one.
+    ///
+    pub fn main_dir(&self) -> &str {
+        self.dirs().first().cloned().unwrap_or("")
+    }
+}
+
+impl fmt::Display for ConfigApplicationsDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.dirs().join(", "))
+    }
+}
+
+/// Where dependencies of applications are installed
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependenciesLayer {
+    /// All dependencies are installed in base image
+    Base,
+    /// Dependencies are installed in application image, except dependencies shared by
+    /// many applications that stay in base image
+    App,
+}
+
+/// Engine used to build and run images
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerEngine {
+    Docker,
+    Podman,
+    /// Docker if installed, else Podman
+    Auto,
+    /// Local Docker daemon called by Engine API, `docker` command for remote daemon
+    #[serde(rename = "docker-api")]
+    DockerApi,
 }
 
 /// Config structure of D-SH
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     pub download_dir: String,
-    pub applications_dir: String,
+    /// Folders of application files, an application hides same application in next folders
+    pub applications_dir: ConfigApplicationsDir,
+    /// Default base image
     pub dockerfile: ConfigDocker,
+    /// Other base images by name
+    pub bases: Option<HashMap<String, ConfigDocker>>,
+    /// Where dependencies of applications are installed, by default `base`
+    pub dependencies_layer: Option<DependenciesLayer>,
+    /// Engine used to build and run images, by default `docker`
+    pub container_engine: Option<ContainerEngine>,
     pub tmp_dir: Option<String>,
+    /// Registry to share images
+    pub registry: Option<ConfigRegistry>,
+    /// Docker daemon, like `ssh://user@workstation` (`docker -H`)
+    pub docker_host: Option<String>,
+    /// Docker context (`docker --context`)
+    pub docker_context: Option<String>,
+    /// Version of config file format, see `migration`
+    pub format_version: Option<u64>,
+}
+
+/// Registry where images are pushed and pulled
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConfigRegistry {
+    /// Prefix of images in registry, like `registry.example.com:5000/team`
+    pub url: String,
+    /// Pull image from registry before build if same hash, by default true
+    pub cache: Option<bool>,
+}
+
+/// How to find last version of application
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ConfigUpdateCheck {
+    /// Releases JSON endpoint like GitHub API, version is read in `tag_name`
+    Github { url: String },
+    /// Web page where version is search with `regex` (by default `version_regex`)
+    Html { url: String, regex: Option<String> },
+    /// Candidate version of package in base image
+    Apt { package: String },
 }
 
 /// Config structure of D-SH
@@ -37,48 +175,135 @@ pub struct ConfigApplication {
     pub cmd_line_args: Option<Vec<String>>,
     pub interactive: Option<bool>,
     pub ipc_host: Option<bool>,
-    pub dependencies: Option<Vec<String>>,
+    pub dependencies: Option<ConfigDependencies>,
     pub download_filename: String,
     pub url: Option<String>,
     pub skip_redownload: Option<bool>,
+    pub version_regex: Option<String>,
+    pub base: Option<String>,
+    /// Version of application, available as `{{version}}` in `url` and `download_filename`
+    pub version: Option<String>,
+    /// How to find last version of application
+    pub update_check: Option<ConfigUpdateCheck>,
+    /// Docker daemon of application, instead of daemon of config file
+    pub docker_host: Option<String>,
+    /// Docker context of application, instead of context of config file
+    pub docker_context: Option<String>,
+    /// Version of application file format, see `migration`
+    pub format_version: Option<u64>,
 }
 
 /// Default config filename.
 pub const DEFAULT_CONFIG_FILE_PATH: &str = ".d-sh/";
 pub const DEFAULT_CONFIG_FILE: &str = "config.yml";
+/// Environment variable with folder of config, instead of `~/.d-sh/`.
+pub const CONFIG_DIR_ENV: &str = "D_SH_HOME";
+/// Name of base image define by `dockerfile`.
+pub const DEFAULT_BASE_NAME: &str = "default";
 
 ///
-/// Function to return config filename.
+/// Return base image config from name.
+///
+/// `default` is base image define by `dockerfile`.
 ///
-pub fn get_config_filename() -> Option<String> {
-    create_config_filename_path(DEFAULT_CONFIG_FILE)
+pub fn get_config_base<'a>(config: &'a Config, name: &str) -> Option<&'a ConfigDocker> {
+    if name == DEFAULT_BASE_NAME {
+        return Some(&config.dockerfile);
+    }
+
+    match config.bases {
+        Some(ref bases) => bases.get(name),
+        None => None,
+    }
 }
 
 ///
-/// Function to create a path for file.
+/// Return name of all base images, default base image first.
 ///
-pub fn create_config_filename_path(filename: &str) -> Option<String> {
-    match home_dir() {
-        Some(path) => {
-            let home_dir = match path.to_str() {
-                None => String::from(""),
-                Some(p) => {
-                    let mut result = String::from(p);
+pub fn get_config_base_names(config: &Config) -> Vec<String> {
+    let mut names: Vec<String> = match config.bases {
+        Some(ref bases) => bases
+            .keys()
+            .filter(|k| *k != DEFAULT_BASE_NAME)
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    };
 
-                    if !p.ends_with("/") {
-                        result.push_str("/");
-                    }
+    names.sort();
+    names.insert(0, String::from(DEFAULT_BASE_NAME));
 
-                    result
-                }
-            };
+    names
+}
 
-            let mut config_file = String::from(home_dir);
-            config_file.push_str(DEFAULT_CONFIG_FILE_PATH);
-            config_file.push_str(filename);
+///
+/// Return package manager of base image.
+///
+pub fn get_base_package_manager(base: &ConfigDocker) -> PackageManager {
+    match base.package_manager {
+        Some(ref pm) => pm.clone(),
+        None => PackageManager::default(),
+    }
+}
 
-            Some(config_file)
-        }
+///
+/// Return name of base image of application.
+///
+pub fn get_application_base_name(config_application: &ConfigApplication) -> &str {
+    match config_application.base {
+        Some(ref b) => b,
+        None => DEFAULT_BASE_NAME,
+    }
+}
+
+///
+/// Return folder of config, with `/` at end.
+///
+/// Folder is `config_dir` (option `--config-dir`) if given, else `D_SH_HOME` environment
+/// variable, else `~/.d-sh/`.
+///
+pub fn get_config_dir(config_dir: Option<&str>) -> Option<String> {
+    let dir = match config_dir {
+        Some(d) => convert_path(d),
+        None => match env::var(CONFIG_DIR_ENV) {
+            Ok(ref d) if !d.is_empty() => convert_path(d),
+            _ => {
+                let home = home_dir()?;
+                let home = home.to_str()?;
+
+                Path::new(home)
+                    .join(DEFAULT_CONFIG_FILE_PATH)
+                    .to_str()?
+                    .to_string()
+            }
+        },
+    };
+
+    if dir.ends_with("/") {
+        Some(dir)
+    } else {
+        Some(format!("{}/", dir))
+    }
+}
+
+///
+/// Function to return config filename.
+///
+/// `config_dir` is folder of config, see `get_config_dir`.
+///
+pub fn get_config_filename(config_dir: Option<&str>) -> Option<String> {
+    create_config_filename_path(config_dir, DEFAULT_CONFIG_FILE)
+}
+
+///
+/// Function to create a path for file in folder of config.
+///
+/// `config_dir` is folder of config, see `get_config_dir`.
+///
+pub fn create_config_filename_path(config_dir: Option<&str>, filename: &str) -> Option<String> {
+    match config_dir {
+        Some(dir) if dir.ends_with("/") => Some(format!("{}{}", dir, filename)),
+        Some(dir) => Some(format!("{}/{}", dir, filename)),
         None => None,
     }
 }
@@ -86,25 +311,29 @@ pub fn create_config_filename_path(filename: &str) -> Option<String> {
 ///
 /// Return config structure.
 ///
-pub fn get_config(io_helper: &InputOutputHelper) -> Result<Config, Error> {
-    match get_config_filename() {
-        Some(config_file) => {
-            let data = io_helper.file_read_at_string(&config_file)?;
-            // let deserialized_config: Config = serde_yaml::from_str(&data).unwrap();
-            //
-            // Ok(deserialized_config)
-
-            match serde_yaml::from_str(&data) {
-                Ok(deserialized_config) => Ok(deserialized_config),
-                Err(err) => Err(Error::new(
-                    ErrorKind::Other,
-                    format!("File format of config file is wrong, {}!", err),
-                )),
-            }
-        }
-        None => Err(Error::new(
+pub fn get_config(
+    io_helper: &InputOutputHelper,
+    config_dir: Option<&str>,
+) -> Result<Config, Error> {
+    let dir = env::current_dir().ok();
+    let vars: Vec<(String, String)> = env::vars().collect();
+
+    let layers = get_config_layers(io_helper, config_dir, dir.as_deref(), &vars)?;
+
+    if layers.is_empty() {
+        return Err(Error::new(
             ErrorKind::PermissionDenied,
             "Cannot read config file !",
+        ));
+    }
+
+    let merged = merge_config_layers(&layers);
+
+    match serde_yaml::from_value(merged.value) {
+        Ok(deserialized_config) => Ok(deserialized_config),
+        Err(err) => Err(Error::new(
+            ErrorKind::Other,
+            format!("File format of config file is wrong, {}!", err),
         )),
     }
 }
@@ -115,18 +344,66 @@ pub fn get_config(io_helper: &InputOutputHelper) -> Result<Config, Error> {
 pub fn get_config_application(
     io_helper: &InputOutputHelper,
     filename: &str,
+) -> Result<ConfigApplication, Error> {
+    get_config_application_with_version(io_helper, filename, None)
+}
+
+///
+/// Return config application structure.
+///
+/// `url` and `download_filename` are rendered with `version` of application, or with
+/// `version` parameter if given.
+///
+pub fn get_config_application_with_version(
+    io_helper: &InputOutputHelper,
+    filename: &str,
+    version: Option<&str>,
 ) -> Result<ConfigApplication, Error> {
     let new_filename = convert_path(&filename);
 
     let data = io_helper.file_read_at_string(&new_filename)?;
 
-    match serde_yaml::from_str(&data) {
-        Ok(deserialized_config) => Ok(deserialized_config),
-        Err(err) => Err(Error::new(
-            ErrorKind::Other,
-            format!("File format of config application file is wrong, {}!", err),
-        )),
+    let mut config_application: ConfigApplication = match serde_yaml::from_str(&data) {
+        Ok(deserialized_config) => deserialized_config,
+        Err(err) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("File format of config application file is wrong, {}!", err),
+            ));
+        }
+    };
+
+    if let Some(v) = version {
+        config_application.version = Some(String::from(v));
     }
+
+    let handlebars = Template::new();
+    let template_data = json!({ "version": config_application.version });
+
+    config_application.download_filename =
+        match handlebars.render_template(&config_application.download_filename, &template_data) {
+            Ok(r) => r,
+            Err(err) => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("Cannot render 'download_filename' of application, {}!", err),
+                ));
+            }
+        };
+
+    if let Some(url) = config_application.url.take() {
+        match handlebars.render_template(&url, &template_data) {
+            Ok(r) => config_application.url = Some(r),
+            Err(err) => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("Cannot render 'url' of application, {}!", err),
+                ));
+            }
+        }
+    }
+
+    Ok(config_application)
 }
 
 ///
@@ -145,3 +422,61 @@ pub fn get_filename(dir: &str, app: &str, ext: Option<&str>) -> String {
 
     String::from(application_filename_full_path)
 }
+
+///
+/// Return filename of application, in first folder where it exists.
+///
+/// If application doesn't exist, filename is in first folder.
+///
+pub fn get_application_filename(
+    io_helper: &InputOutputHelper,
+    config: &Config,
+    app: &str,
+) -> String {
+    let dirs = config.applications_dir.dirs();
+
+    if dirs.len() > 1 {
+        for dir in &dirs {
+            let filename = get_filename(dir, app, Some(&".yml"));
+
+            if io_helper.file_exits(&convert_path(&filename)) {
+                return filename;
+            }
+        }
+    }
+
+    get_filename(config.applications_dir.main_dir(), app, Some(&".yml"))
+}
+
+///
+/// Return filename of all applications.
+///
+/// An application hides same application in next folders.
+///
+pub fn get_application_filenames(
+    io_helper: &InputOutputHelper,
+    config: &Config,
+) -> Result<Vec<String>, Error> {
+    let mut names = Vec::new();
+    let mut result = Vec::new();
+
+    for dir in config.applications_dir.dirs() {
+        let mut filenames = io_helper.dir_list_file(dir, "*.yml")?;
+
+        filenames.sort();
+
+        for filename in filenames {
+            let name = match Path::new(&filename).file_stem().and_then(|n| n.to_str()) {
+                Some(n) => String::from(n),
+                None => continue,
+            };
+
+            if !names.contains(&name) {
+                names.push(name);
+                result.push(filename);
+            }
+        }
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,85 @@
+///
+/// Module of package manager of base image.
+///
+/// Release under MIT License.
+///
+
+///
+/// Package manager of linux distribution.
+///
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    /// Debian, Ubuntu
+    #[default]
+    Apt,
+    /// Fedora, CentOS
+    Dnf,
+    /// Alpine
+    Apk,
+    /// OpenSUSE
+    Zypper,
+}
+
+impl PackageManager {
+    ///
+    /// Return package manager from name.
+    ///
+    pub fn from_name(name: &str) -> Option<PackageManager> {
+        match name {
+            "apt" => Some(PackageManager::Apt),
+            "dnf" => Some(PackageManager::Dnf),
+            "apk" => Some(PackageManager::Apk),
+            "zypper" => Some(PackageManager::Zypper),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Return name of package manager.
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt",
+            PackageManager::Dnf => "dnf",
+            PackageManager::Apk => "apk",
+            PackageManager::Zypper => "zypper",
+        }
+    }
+
+    ///
+    /// Command to install packages from repository. Packages must be add after.
+    ///
+    pub fn install_command(&self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt-get update && apt-get install -y",
+            PackageManager::Dnf => "dnf install -y",
+            PackageManager::Apk => "apk add --no-cache",
+            PackageManager::Zypper => "zypper --non-interactive install",
+        }
+    }
+
+    ///
+    /// Command to install a package file (`.deb`, `.rpm`, `.apk`). File must be add after.
+    ///
+    pub fn install_file_command(&self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt-get update && apt-get install -y",
+            PackageManager::Dnf => "dnf install -y",
+            PackageManager::Apk => "apk add --no-cache --allow-untrusted",
+            PackageManager::Zypper => "zypper --non-interactive install --allow-unsigned-rpm",
+        }
+    }
+
+    ///
+    /// Command to clean cache of package manager.
+    ///
+    pub fn clean_command(&self) -> &'static str {
+        match self {
+            PackageManager::Apt => "apt-get clean && rm -rf /var/lib/apt/lists/*",
+            PackageManager::Dnf => "dnf clean all",
+            PackageManager::Apk => "rm -rf /var/cache/apk/*",
+            PackageManager::Zypper => "zypper clean --all",
+        }
+    }
+}
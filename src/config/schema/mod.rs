@@ -0,0 +1,113 @@
+///
+/// Module with strict schema of config and application files, used to check files.
+///
+/// Structures of config accept unknown keys, so a file with an unknown key can still be
+/// used. Structures of this module refuse unknown keys.
+///
+/// Release under MIT License.
+///
+use config::package_manager::PackageManager;
+use config::serde_yaml::{Error, Value};
+use config::{
+    ConfigApplicationsDir, ConfigDependencies, ConfigUpdateCheck, ContainerEngine,
+    DependenciesLayer,
+};
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests;
+
+/// Strict base image, all keys are optional because a config file can be a part of config
+// Fields are only deserialized to check file, serialized to test same keys as config
+#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StrictConfigDocker {
+    from: Option<String>,
+    tag: Option<String>,
+    template: Option<String>,
+    package_manager: Option<PackageManager>,
+}
+
+/// Strict registry
+// Fields are only deserialized to check file, serialized to test same keys as config
+#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StrictConfigRegistry {
+    url: Option<String>,
+    cache: Option<bool>,
+}
+
+/// Strict config, all keys are optional because a config file can be a part of config
+// Fields are only deserialized to check file, serialized to test same keys as config
+#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StrictConfig {
+    format_version: Option<u64>,
+    download_dir: Option<String>,
+    applications_dir: Option<ConfigApplicationsDir>,
+    dockerfile: Option<StrictConfigDocker>,
+    bases: Option<HashMap<String, StrictConfigDocker>>,
+    dependencies_layer: Option<DependenciesLayer>,
+    container_engine: Option<ContainerEngine>,
+    tmp_dir: Option<String>,
+    registry: Option<StrictConfigRegistry>,
+    docker_host: Option<String>,
+    docker_context: Option<String>,
+}
+
+/// Strict application
+// Fields are only deserialized to check file, serialized to test same keys as config
+#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StrictConfigApplication {
+    format_version: Option<u64>,
+    image_name: String,
+    cmd_line: String,
+    cmd_line_args: Option<Vec<String>>,
+    interactive: Option<bool>,
+    ipc_host: Option<bool>,
+    dependencies: Option<ConfigDependencies>,
+    download_filename: String,
+    url: Option<String>,
+    skip_redownload: Option<bool>,
+    version_regex: Option<String>,
+    base: Option<String>,
+    version: Option<String>,
+    update_check: Option<ConfigUpdateCheck>,
+    docker_host: Option<String>,
+    docker_context: Option<String>,
+}
+
+///
+/// Check content of a config file: unknown keys and type of values.
+///
+/// Error has line and column of wrong key.
+///
+pub fn check_config_file(data: &str) -> Result<(), Error> {
+    // Empty file is an empty part of config
+    if data.trim().is_empty() {
+        return Ok(());
+    }
+
+    serde_yaml::from_str::<StrictConfig>(data).map(|_| ())
+}
+
+///
+/// Check values of a config layer, like a layer of environment variable.
+///
+pub fn check_config_value(value: &Value) -> Result<(), Error> {
+    serde_yaml::from_value::<StrictConfig>(value.clone()).map(|_| ())
+}
+
+///
+/// Check content of an application file: unknown keys, missing keys and type of values.
+///
+/// Error has line and column of wrong key.
+///
+pub fn check_application_file(data: &str) -> Result<(), Error> {
+    serde_yaml::from_str::<StrictConfigApplication>(data).map(|_| ())
+}
@@ -0,0 +1,118 @@
+///
+/// Module to tests module schema.
+///
+/// Release under MIT License.
+///
+use super::{
+    check_application_file, check_config_file, check_config_value, StrictConfig,
+    StrictConfigApplication,
+};
+use config::package_manager::PackageManager;
+use config::serde_yaml::from_str;
+use config::{
+    Config, ConfigApplication, ConfigApplicationsDir, ConfigDependencies, ConfigDocker,
+    ConfigRegistry, ConfigUpdateCheck, ContainerEngine, DependenciesLayer,
+};
+use std::collections::HashMap;
+
+fn create_config_docker() -> ConfigDocker {
+    ConfigDocker {
+        from: String::from("ubuntu:18.04"),
+        tag: String::from("d-base-image:v1.0.0"),
+        template: Some(String::from("Dockerfile.hbs")),
+        package_manager: Some(PackageManager::Apt),
+    }
+}
+
+#[test]
+fn config_file_can_be_part_of_config() {
+    assert!(check_config_file("---\ndockerfile:\n  tag: \"my-base:v1\"\n").is_ok());
+    assert!(check_config_file("").is_ok());
+}
+
+#[test]
+fn config_file_with_unknown_key() {
+    let err = check_config_file("---\ntmp_dir: \"/tmp\"\ndockerfile:\n  tags: \"my-base:v1\"\n")
+        .unwrap_err();
+    let location = err.location().unwrap();
+
+    assert_eq!(location.line(), 4);
+    assert_eq!(location.column(), 3);
+}
+
+#[test]
+fn config_value_with_wrong_type() {
+    assert!(check_config_value(&from_str("---\ncontainer_engine: rocket\n").unwrap()).is_err());
+    assert!(check_config_value(&from_str("---\ncontainer_engine: podman\n").unwrap()).is_ok());
+    assert!(check_config_value(&from_str("---\ncontainer_engine: docker-api\n").unwrap()).is_ok());
+}
+
+#[test]
+fn application_file_with_unknown_key() {
+    let err = check_application_file(
+        "---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.deb\"\ninteractiv: true\n",
+    )
+    .unwrap_err();
+
+    assert_eq!(err.location().unwrap().line(), 5);
+}
+
+#[test]
+fn strict_config_has_same_keys_as_config() {
+    let mut bases = HashMap::new();
+    bases.insert(String::from("fedora"), create_config_docker());
+
+    // All keys are set, a new key of config must be added here
+    let config = Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: create_config_docker(),
+        bases: Some(bases),
+        dependencies_layer: Some(DependenciesLayer::App),
+        container_engine: Some(ContainerEngine::Podman),
+        tmp_dir: Some(String::from("/tmp")),
+        registry: Some(ConfigRegistry {
+            url: String::from("localhost:5000"),
+            cache: Some(true),
+        }),
+        docker_host: Some(String::from("ssh://me@host")),
+        docker_context: Some(String::from("remote")),
+        format_version: Some(1),
+    };
+
+    let value = serde_json::to_value(&config).unwrap();
+    let strict: StrictConfig = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(serde_json::to_value(&strict).unwrap(), value);
+}
+
+#[test]
+fn strict_application_has_same_keys_as_application() {
+    // All keys are set, a new key of application must be added here
+    let config_application = ConfigApplication {
+        image_name: String::from("run-atom:latest"),
+        cmd_line: String::from("atom"),
+        cmd_line_args: Some(vec![String::from("-f")]),
+        interactive: Some(true),
+        ipc_host: Some(true),
+        dependencies: Some(ConfigDependencies::List(vec![String::from("git")])),
+        download_filename: String::from("atom.deb"),
+        url: Some(String::from("https://example.com/atom.deb")),
+        skip_redownload: Some(true),
+        version_regex: Some(String::from("v([0-9.]+)")),
+        base: Some(String::from("fedora")),
+        version: Some(String::from("1.32.0")),
+        update_check: Some(ConfigUpdateCheck::Html {
+            url: String::from("https://example.com"),
+            regex: Some(String::from("v([0-9.]+)")),
+        }),
+        docker_host: Some(String::from("ssh://me@host")),
+        docker_context: Some(String::from("remote")),
+        format_version: Some(1),
+    };
+
+    let value = serde_json::to_value(&config_application).unwrap();
+    let strict: StrictConfigApplication = serde_json::from_value(value.clone()).unwrap();
+
+    assert_eq!(serde_json::to_value(&strict).unwrap(), value);
+}
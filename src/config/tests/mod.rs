@@ -0,0 +1,31 @@
+///
+/// Module to provide config for tests.
+///
+/// Release under MIT License.
+///
+use super::{Config, ConfigApplicationsDir, ConfigDocker};
+
+///
+/// Return minimal config. Tests override only fields they need:
+/// `Config { tmp_dir: Some(...), ..create_config() }`.
+///
+pub fn create_config() -> Config {
+    Config {
+        download_dir: String::from("dwn"),
+        applications_dir: ConfigApplicationsDir::One(String::from("app")),
+        dockerfile: ConfigDocker {
+            from: String::from("tata"),
+            tag: String::from("tutu"),
+            template: None,
+            package_manager: None,
+        },
+        bases: None,
+        dependencies_layer: None,
+        container_engine: None,
+        tmp_dir: None,
+        registry: None,
+        docker_host: None,
+        docker_context: None,
+        format_version: None,
+    }
+}
@@ -0,0 +1,300 @@
+///
+/// Module to send HTTP/1.1 requests on a unix socket.
+///
+/// Release under MIT License.
+///
+use std::cmp::min;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
+use std::os::unix::net::UnixStream;
+
+#[cfg(test)]
+mod tests;
+
+/// Size of chunk when request body is sent.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+///
+/// Reader of body with `Transfer-Encoding: chunked`.
+///
+pub struct ChunkedReader<R: BufRead> {
+    inner: R,
+    /// Bytes to read in current chunk
+    remaining: usize,
+    /// Last chunk is read
+    done: bool,
+}
+
+impl<R: BufRead> ChunkedReader<R> {
+    pub fn new(inner: R) -> ChunkedReader<R> {
+        ChunkedReader {
+            inner: inner,
+            remaining: 0,
+            done: false,
+        }
+    }
+
+    ///
+    /// Read one line without `\r\n`.
+    ///
+    fn read_line(&mut self) -> Result<String, Error> {
+        let mut line = String::new();
+
+        if self.inner.read_line(&mut line)? == 0 {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "Connection closed in chunked body",
+            ));
+        }
+
+        Ok(String::from(line.trim_end_matches(&['\r', '\n'][..])))
+    }
+}
+
+impl<R: BufRead> Read for ChunkedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        if self.done || buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.remaining == 0 {
+            let size_line = self.read_line()?;
+            // Chunk extensions are after ';'
+            let size = size_line.split(';').next().unwrap_or("").trim();
+
+            self.remaining = match usize::from_str_radix(size, 16) {
+                Ok(r) => r,
+                Err(_) => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Bad chunk size '{}'", size_line),
+                    ));
+                }
+            };
+
+            if self.remaining == 0 {
+                // Skip trailers
+                while !self.read_line()?.is_empty() {}
+
+                self.done = true;
+                return Ok(0);
+            }
+        }
+
+        let len = min(buf.len(), self.remaining);
+        let n = self.inner.read(&mut buf[..len])?;
+
+        if n == 0 {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "Connection closed in chunked body",
+            ));
+        }
+
+        self.remaining -= n;
+
+        if self.remaining == 0 {
+            // End of chunk
+            self.read_line()?;
+        }
+
+        Ok(n)
+    }
+}
+
+///
+/// Response of HTTP request.
+///
+pub struct Response {
+    /// Status code, like `200`.
+    pub status: u16,
+    /// Body, already decoded if chunked.
+    pub body: Box<BufRead>,
+}
+
+impl Response {
+    ///
+    /// Read all body as string.
+    ///
+    pub fn body_string(&mut self) -> Result<String, Error> {
+        let mut body = String::new();
+        self.body.read_to_string(&mut body)?;
+
+        Ok(body)
+    }
+}
+
+///
+/// Read status line and headers of response.
+///
+pub fn read_response(stream: UnixStream) -> Result<Response, Error> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+
+    reader.read_line(&mut line)?;
+
+    // HTTP/1.1 200 OK
+    let status = match line.split_whitespace().nth(1).map(|s| s.parse::<u16>()) {
+        Some(Ok(r)) => r,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Bad HTTP status line '{}'", line.trim()),
+            ));
+        }
+    };
+
+    let mut headers = Vec::new();
+
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "Connection closed in headers",
+            ));
+        }
+
+        let header = line.trim();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some(index) = header.find(':') {
+            headers.push((
+                header[..index].trim().to_lowercase(),
+                String::from(header[index + 1..].trim()),
+            ));
+        }
+    }
+
+    let chunked = headers
+        .iter()
+        .any(|(n, v)| n == "transfer-encoding" && v.to_lowercase().contains("chunked"));
+    let content_length = headers
+        .iter()
+        .find(|(n, _)| n == "content-length")
+        .and_then(|(_, v)| v.parse::<u64>().ok());
+
+    // Connection is closed by server after response, so without length body is read to end
+    let body: Box<BufRead> = if chunked {
+        Box::new(BufReader::new(ChunkedReader::new(reader)))
+    } else if let Some(length) = content_length {
+        Box::new(reader.take(length))
+    } else {
+        Box::new(reader)
+    };
+
+    Ok(Response {
+        status: status,
+        body: body,
+    })
+}
+
+///
+/// Send request on unix socket and return response.
+///
+/// `path` must contain query string, already encoded.
+/// `body` is sent with `Transfer-Encoding: chunked`.
+///
+pub fn request(
+    socket: &str,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: Option<&mut Read>,
+) -> Result<Response, Error> {
+    let mut stream = UnixStream::connect(socket)?;
+
+    let mut head = format!(
+        "{} {} HTTP/1.1\r\nHost: docker\r\nConnection: close\r\n",
+        method, path
+    );
+
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    match body {
+        Some(body) => {
+            head.push_str("Transfer-Encoding: chunked\r\n\r\n");
+            stream.write_all(head.as_bytes())?;
+
+            let mut buf = vec![0; CHUNK_SIZE];
+
+            loop {
+                let n = body.read(&mut buf)?;
+
+                if n == 0 {
+                    break;
+                }
+
+                stream.write_all(format!("{:x}\r\n", n).as_bytes())?;
+                stream.write_all(&buf[..n])?;
+                stream.write_all(b"\r\n")?;
+            }
+
+            stream.write_all(b"0\r\n\r\n")?;
+        }
+        None => {
+            head.push_str("\r\n");
+            stream.write_all(head.as_bytes())?;
+        }
+    }
+
+    stream.flush()?;
+
+    read_response(stream)
+}
+
+///
+/// Encode value, except unreserved characters and `keep` characters.
+///
+fn encode(value: &str, keep: &[u8]) -> String {
+    let mut result = String::new();
+
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                result.push(b as char)
+            }
+            b if keep.contains(&b) => result.push(b as char),
+            _ => result.push_str(&format!("%{:02X}", b)),
+        }
+    }
+
+    result
+}
+
+///
+/// Encode query string value.
+///
+pub fn url_encode(value: &str) -> String {
+    encode(value, &[])
+}
+
+///
+/// Encode image name in path, like `/images/localhost:5000/ubuntu:18.04/json`.
+///
+/// `/` and `:` are kept, daemon reads them in path like `docker` command.
+///
+pub fn url_encode_path(value: &str) -> String {
+    encode(value, b"/:")
+}
+
+///
+/// Create query string from parameters, like `?t=run-atom&nocache=1`.
+///
+pub fn query_string(params: &[(&str, String)]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+
+    let query: Vec<String> = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, url_encode(value)))
+        .collect();
+
+    format!("?{}", query.join("&"))
+}
@@ -0,0 +1,55 @@
+///
+/// Module to tests module http.
+///
+/// Release under MIT License.
+///
+use super::{query_string, url_encode, url_encode_path, ChunkedReader};
+use std::io::Read;
+
+#[test]
+fn encode_query_string() {
+    assert_eq!(url_encode("run-atom:latest"), "run-atom%3Alatest");
+    assert_eq!(
+        query_string(&[
+            ("t", String::from("run-atom")),
+            ("labels", String::from("{\"a\":\"b c\"}"))
+        ]),
+        "?t=run-atom&labels=%7B%22a%22%3A%22b%20c%22%7D"
+    );
+    assert_eq!(query_string(&[]), "");
+}
+
+#[test]
+fn encode_image_name_in_path() {
+    assert_eq!(
+        url_encode_path("localhost:5000/run-atom:latest"),
+        "localhost:5000/run-atom:latest"
+    );
+    assert_eq!(
+        url_encode_path("ubuntu@sha256:12ab"),
+        "ubuntu%40sha256:12ab"
+    );
+    assert_eq!(url_encode_path("a b?c#d"), "a%20b%3Fc%23d");
+}
+
+#[test]
+fn read_chunked_body() {
+    let data = "5\r\nhello\r\n7;ext=1\r\n world!\r\n0\r\nTrailer: 1\r\n\r\nnext response";
+    let mut body = String::new();
+
+    ChunkedReader::new(data.as_bytes())
+        .read_to_string(&mut body)
+        .unwrap();
+
+    assert_eq!(body, "hello world!");
+}
+
+#[test]
+fn read_chunked_body_truncated() {
+    let data = "5\r\nhel";
+    let mut body = String::new();
+
+    assert!(ChunkedReader::new(data.as_bytes())
+        .read_to_string(&mut body)
+        .is_err());
+}
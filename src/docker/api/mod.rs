@@ -0,0 +1,745 @@
+///
+/// Module to talk with Docker daemon by Engine API, without `docker` command.
+///
+/// Release under MIT License.
+///
+use self::http::{query_string, request, url_encode_path, Response};
+use config::ContainerEngine;
+use docker::remote::{DockerEndpoint, RemoteDisplay};
+use docker::{
+    get_container_helper, get_image_info, get_image_repository, ContainerHelper,
+    DefaultContainerHelper, ImageInfo,
+};
+use helper::HelperError;
+use serde_json::{Map, Value};
+use std::cmp::min;
+use std::env;
+use std::fs::File;
+use std::io::{copy, BufRead, ErrorKind, Read};
+use std::process::{Command, Stdio};
+
+pub mod http;
+#[cfg(test)]
+mod tests;
+
+/// Default socket of Docker daemon.
+pub const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// Engine API version used (Docker 1.13).
+const API_VERSION: &str = "v1.25";
+
+/// Length of short image id, like `docker image list`.
+const SHORT_ID_LENGTH: usize = 12;
+
+///
+/// Return error message of daemon if request failed.
+///
+fn check_status(mut response: Response) -> Result<Response, HelperError> {
+    if response.status < 300 {
+        return Ok(response);
+    }
+
+    let body = response.body_string().unwrap_or_default();
+
+    // Error is like `{"message": "No such image: run-atom:latest"}`
+    let message = match serde_json::from_str::<Value>(&body) {
+        Ok(ref v) if v["message"].is_string() => String::from(v["message"].as_str().unwrap()),
+        _ if !body.trim().is_empty() => String::from(body.trim()),
+        _ => format!("Docker daemon return HTTP status {}", response.status),
+    };
+
+    Err(match response.status {
+        404 => HelperError::NotFound(message),
+        _ => HelperError::failed(&message),
+    })
+}
+
+///
+/// Read JSON messages streamed by daemon (build, load...).
+///
+/// `output` is called for each line. Returning error if a message contains error.
+///
+fn read_stream(response: Response, output: &mut FnMut(&str)) -> Result<(), HelperError> {
+    // Text of `stream` can be split in many messages
+    let mut pending = String::new();
+
+    for line in response.body.lines() {
+        let line = match line {
+            Ok(r) => r,
+            Err(err) => return Err(HelperError::failed(&format!("{}", err))),
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(r) => r,
+            Err(_) => {
+                output(&line);
+                continue;
+            }
+        };
+
+        if let Some(err) = message["error"].as_str() {
+            if !pending.is_empty() {
+                output(&pending);
+            }
+
+            // Error is displayed like `docker` command
+            output(err.trim());
+
+            return Err(HelperError::failed(err.trim()));
+        }
+
+        if let Some(text) = message["stream"].as_str() {
+            pending.push_str(text);
+
+            while let Some(index) = pending.find('\n') {
+                output(&pending[..index]);
+                pending = String::from(&pending[index + 1..]);
+            }
+        } else if let Some(status) = message["status"].as_str() {
+            match message["id"].as_str() {
+                Some(id) => output(&format!("{}: {}", id, status)),
+                None => output(status),
+            }
+        }
+    }
+
+    if !pending.is_empty() {
+        output(&pending);
+    }
+
+    Ok(())
+}
+
+///
+/// Return stdout and stderr of container from multiplexed logs.
+///
+/// Each frame is 8 bytes header (stream type, 3 zero, size in big endian) and payload.
+///
+fn read_container_output(data: &[u8]) -> (String, String) {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut pos = 0;
+
+    while pos + 8 <= data.len() {
+        let size = ((data[pos + 4] as usize) << 24)
+            | ((data[pos + 5] as usize) << 16)
+            | ((data[pos + 6] as usize) << 8)
+            | (data[pos + 7] as usize);
+        let start = pos + 8;
+        let end = min(start + size, data.len());
+
+        // 1 is stdout, 2 is stderr
+        match data[pos] {
+            1 => stdout.extend_from_slice(&data[start..end]),
+            2 => stderr.extend_from_slice(&data[start..end]),
+            _ => {}
+        }
+
+        pos = end;
+    }
+
+    (
+        String::from_utf8_lossy(&stdout).to_string(),
+        String::from_utf8_lossy(&stderr).to_string(),
+    )
+}
+
+///
+/// Return path of Dockerfile in build context.
+///
+fn get_dockerfile_in_context(
+    docker_filename: &str,
+    docker_context_path: &str,
+) -> Result<String, HelperError> {
+    let context = format!("{}/", docker_context_path.trim_end_matches('/'));
+
+    if docker_filename.starts_with(&context) {
+        Ok(String::from(&docker_filename[context.len()..]))
+    } else {
+        Err(HelperError::failed(&format!(
+            "Dockerfile '{}' must be in build context '{}'!",
+            docker_filename, docker_context_path
+        )))
+    }
+}
+
+///
+/// Split `KEY=VALUE` option.
+///
+fn split_key_value(value: &str) -> (String, String) {
+    match value.find('=') {
+        Some(index) => (
+            String::from(&value[..index]),
+            String::from(&value[index + 1..]),
+        ),
+        None => (String::from(value), String::new()),
+    }
+}
+
+///
+/// Return next value of option, like `/bin/sh` for `--entrypoint /bin/sh`.
+///
+fn next_option_value<'a, I: Iterator<Item = &'a String>>(
+    options: &mut I,
+    option: &str,
+) -> Result<&'a String, HelperError> {
+    match options.next() {
+        Some(r) => Ok(r),
+        None => Err(HelperError::failed(&format!(
+            "Option '{}' need a value!",
+            option
+        ))),
+    }
+}
+
+///
+/// Docker helper using Engine API on unix socket.
+///
+/// Interactive run, push and pull (need credentials of registry) still use `docker` command.
+///
+pub struct ApiContainerHelper {
+    /// Path of unix socket of daemon
+    pub socket: String,
+}
+
+impl ApiContainerHelper {
+    pub fn new(socket: &str) -> ApiContainerHelper {
+        ApiContainerHelper {
+            socket: String::from(socket),
+        }
+    }
+
+    ///
+    /// Return endpoint of `docker` command on same socket, like `-H unix:///var/run/docker.sock`.
+    ///
+    fn endpoint(&self) -> DockerEndpoint {
+        DockerEndpoint {
+            host: Some(format!("unix://{}", self.socket)),
+            context: None,
+        }
+    }
+
+    ///
+    /// Send request to daemon.
+    ///
+    /// `path` is path of API without version, like `/images/json`.
+    ///
+    fn send(
+        &self,
+        method: &str,
+        path: &str,
+        headers: &[(&str, &str)],
+        body: Option<&mut Read>,
+    ) -> Result<Response, HelperError> {
+        match request(
+            &self.socket,
+            method,
+            &format!("/{}{}", API_VERSION, path),
+            headers,
+            body,
+        ) {
+            Ok(r) => Ok(r),
+            Err(err) => {
+                let message = format!(
+                    "Cannot talk to Docker daemon at unix://{}: {}",
+                    self.socket, err
+                );
+
+                Err(match err.kind() {
+                    ErrorKind::NotFound | ErrorKind::ConnectionRefused => {
+                        HelperError::DaemonUnreachable(message)
+                    }
+                    ErrorKind::PermissionDenied => HelperError::PermissionDenied(message),
+                    _ => HelperError::failed(&message),
+                })
+            }
+        }
+    }
+
+    ///
+    /// Send request to daemon and return JSON of response.
+    ///
+    fn send_json(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Value, HelperError> {
+        let response = match body {
+            Some(b) => {
+                let data = b.to_string();
+
+                self.send(
+                    method,
+                    path,
+                    &[("Content-Type", "application/json")],
+                    Some(&mut data.as_bytes()),
+                )?
+            }
+            None => self.send(method, path, &[], None)?,
+        };
+
+        let body = match check_status(response)?.body_string() {
+            Ok(r) => r,
+            Err(err) => return Err(HelperError::failed(&format!("{}", err))),
+        };
+
+        // Some requests return no content
+        if body.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+
+        match serde_json::from_str(&body) {
+            Ok(r) => Ok(r),
+            Err(err) => Err(HelperError::failed(&format!(
+                "Bad response of Docker daemon: {}",
+                err
+            ))),
+        }
+    }
+
+    ///
+    /// Return short id of images with name.
+    ///
+    pub fn list(&self, image_name: &str) -> Result<Vec<String>, HelperError> {
+        let filters = json!({ "reference": [image_name] }).to_string();
+        let images = self.send_json(
+            "GET",
+            &format!("/images/json{}", query_string(&[("filters", filters)])),
+            None,
+        )?;
+
+        Ok(images
+            .as_array()
+            .map(|a| a.as_slice())
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(|i| i["Id"].as_str())
+            .map(|id| {
+                let id = id.trim_start_matches("sha256:");
+                String::from(&id[..min(SHORT_ID_LENGTH, id.len())])
+            })
+            .collect())
+    }
+
+    ///
+    /// Return information of image, None if image not found.
+    ///
+    pub fn inspect(&self, image_name: &str) -> Result<Option<ImageInfo>, HelperError> {
+        let response = self.send(
+            "GET",
+            &format!("/images/{}/json", url_encode_path(image_name)),
+            &[],
+            None,
+        )?;
+
+        if response.status == 404 {
+            return Ok(None);
+        }
+
+        let body = match check_status(response)?.body_string() {
+            Ok(r) => r,
+            Err(err) => return Err(HelperError::failed(&format!("{}", err))),
+        };
+
+        match serde_json::from_str(&body) {
+            Ok(image) => Ok(Some(get_image_info(&image))),
+            Err(err) => Err(HelperError::failed(&format!(
+                "Bad response of Docker daemon: {}",
+                err
+            ))),
+        }
+    }
+
+    ///
+    /// Remove image.
+    ///
+    pub fn remove(&self, image_name: &str) -> Result<(), HelperError> {
+        self.send_json(
+            "DELETE",
+            &format!("/images/{}", url_encode_path(image_name)),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    ///
+    /// Build image, `output` is called for each line of build.
+    ///
+    /// Build context is sent as tar archive.
+    ///
+    pub fn build(
+        &self,
+        docker_filename: &str,
+        docker_context_path: &str,
+        docker_tag: &str,
+        build_options: Option<&Vec<String>>,
+        output: &mut FnMut(&str),
+    ) -> Result<(), HelperError> {
+        let mut params = vec![
+            ("t", String::from(docker_tag)),
+            (
+                "dockerfile",
+                get_dockerfile_in_context(docker_filename, docker_context_path)?,
+            ),
+        ];
+        let mut labels = Map::new();
+        let mut build_args = Map::new();
+        let empty = Vec::new();
+        let mut options = build_options.unwrap_or(&empty).iter();
+
+        while let Some(opt) = options.next() {
+            match opt.as_str() {
+                "--no-cache" => params.push(("nocache", String::from("1"))),
+                "--pull" => params.push(("pull", String::from("1"))),
+                "--label" => {
+                    let (key, value) = split_key_value(next_option_value(&mut options, opt)?);
+                    labels.insert(key, Value::String(value));
+                }
+                "--build-arg" => {
+                    let (key, value) = split_key_value(next_option_value(&mut options, opt)?);
+                    build_args.insert(key, Value::String(value));
+                }
+                _ => {
+                    return Err(HelperError::failed(&format!(
+                        "Build option '{}' is not supported!",
+                        opt
+                    )))
+                }
+            }
+        }
+
+        if !labels.is_empty() {
+            params.push(("labels", Value::Object(labels).to_string()));
+        }
+
+        if !build_args.is_empty() {
+            params.push(("buildargs", Value::Object(build_args).to_string()));
+        }
+
+        let mut tar = match Command::new("tar")
+            .args(&["-c", "-C", docker_context_path, "."])
+            .stdout(Stdio::piped())
+            .spawn()
+        {
+            Ok(r) => r,
+            Err(err) => return Err(HelperError::failed(&format!("Unable to run tar: {}", err))),
+        };
+
+        let response = {
+            let context = tar.stdout.as_mut().unwrap();
+
+            self.send(
+                "POST",
+                &format!("/build{}", query_string(&params)),
+                &[("Content-Type", "application/x-tar")],
+                Some(context),
+            )
+        };
+
+        match tar.wait() {
+            Ok(ref status) if status.success() => {}
+            _ => {
+                return Err(HelperError::failed(&format!(
+                    "Unable to create archive of build context '{}'!",
+                    docker_context_path
+                )));
+            }
+        }
+
+        read_stream(check_status(response?)?, output)
+    }
+
+    ///
+    /// Run container and return its stdout.
+    ///
+    /// Only `--rm`, `--entrypoint`, `-e` and `-v` options are supported.
+    ///
+    pub fn run_output(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> Result<String, HelperError> {
+        let mut remove = false;
+        let mut entrypoint = None;
+        let mut envs = Vec::new();
+        let mut binds = Vec::new();
+        let empty = Vec::new();
+        let mut options = run_options.unwrap_or(&empty).iter();
+
+        while let Some(opt) = options.next() {
+            match opt.as_str() {
+                "--rm" => remove = true,
+                "--entrypoint" => entrypoint = Some(next_option_value(&mut options, opt)?),
+                "-e" | "--env" => {
+                    let env = next_option_value(&mut options, opt)?;
+
+                    // `-e DISPLAY` take value of current environment
+                    if env.contains('=') {
+                        envs.push(env.clone());
+                    } else if let Ok(value) = env::var(env) {
+                        envs.push(format!("{}={}", env, value));
+                    }
+                }
+                "-v" | "--volume" => binds.push(next_option_value(&mut options, opt)?.clone()),
+                _ => {
+                    return Err(HelperError::failed(&format!(
+                        "Run option '{}' is not supported!",
+                        opt
+                    )))
+                }
+            }
+        }
+
+        let mut cmd_args: Vec<String> = cmd.iter().map(|c| String::from(*c)).collect();
+
+        if let Some(o) = cmd_options {
+            cmd_args.extend(o.iter().cloned());
+        }
+
+        let mut config = json!({
+            "Image": image_name,
+            "Env": envs,
+            "HostConfig": { "Binds": binds },
+        });
+
+        if let Some(e) = entrypoint {
+            config["Entrypoint"] = json!([e]);
+        }
+
+        if !cmd_args.is_empty() {
+            config["Cmd"] = json!(cmd_args);
+        }
+
+        let container = self.send_json("POST", "/containers/create", Some(&config))?;
+        let id = match container["Id"].as_str() {
+            Some(r) => String::from(r),
+            None => return Err(HelperError::failed("Docker daemon return no container id!")),
+        };
+
+        let result = self.wait_output(&id);
+
+        if remove {
+            // Container is removed only after logs are read
+            self.send_json("DELETE", &format!("/containers/{}?force=1", id), None)?;
+        }
+
+        result
+    }
+
+    ///
+    /// Start container, wait its end and return its stdout.
+    ///
+    fn wait_output(&self, id: &str) -> Result<String, HelperError> {
+        self.send_json("POST", &format!("/containers/{}/start", id), None)?;
+
+        let exit = self.send_json("POST", &format!("/containers/{}/wait", id), None)?;
+
+        let mut response = check_status(self.send(
+            "GET",
+            &format!("/containers/{}/logs?stdout=1&stderr=1", id),
+            &[],
+            None,
+        )?)?;
+        let mut data = Vec::new();
+
+        if let Err(err) = response.body.read_to_end(&mut data) {
+            return Err(HelperError::failed(&format!("{}", err)));
+        }
+
+        let (stdout, stderr) = read_container_output(&data);
+
+        match exit["StatusCode"].as_i64() {
+            Some(0) => Ok(stdout),
+            Some(code) => Err(HelperError::Failed {
+                code: Some(code as i32),
+                message: String::from(stderr.trim()),
+            }),
+            None => Err(HelperError::failed("Docker daemon return no exit code!")),
+        }
+    }
+
+    ///
+    /// Save images in tar file.
+    ///
+    pub fn save(&self, image_names: &[String], output_filename: &str) -> Result<(), HelperError> {
+        let params: Vec<(&str, String)> =
+            image_names.iter().map(|i| ("names", i.clone())).collect();
+        let mut response = check_status(self.send(
+            "GET",
+            &format!("/images/get{}", query_string(&params)),
+            &[],
+            None,
+        )?)?;
+
+        let mut file = match File::create(output_filename) {
+            Ok(r) => r,
+            Err(err) => {
+                return Err(HelperError::failed(&format!(
+                    "Unable to create '{}': {}",
+                    output_filename, err
+                )))
+            }
+        };
+
+        match copy(&mut response.body, &mut file) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(HelperError::failed(&format!(
+                "Unable to write '{}': {}",
+                output_filename, err
+            ))),
+        }
+    }
+
+    ///
+    /// Load images from tar file.
+    ///
+    pub fn load(&self, input_filename: &str) -> Result<(), HelperError> {
+        let mut file = match File::open(input_filename) {
+            Ok(r) => r,
+            Err(err) => {
+                return Err(HelperError::failed(&format!(
+                    "Unable to read '{}': {}",
+                    input_filename, err
+                )))
+            }
+        };
+
+        let response = self.send(
+            "POST",
+            "/images/load?quiet=1",
+            &[("Content-Type", "application/x-tar")],
+            Some(&mut file),
+        )?;
+
+        read_stream(check_status(response)?, &mut |_| {})
+    }
+
+    ///
+    /// Add tag `target` to image `source`.
+    ///
+    pub fn tag(&self, source: &str, target: &str) -> Result<(), HelperError> {
+        let repository = get_image_repository(target);
+        let tag = if repository.len() < target.len() {
+            String::from(&target[repository.len() + 1..])
+        } else {
+            String::from("latest")
+        };
+
+        let params = [("repo", String::from(repository)), ("tag", tag)];
+
+        self.send_json(
+            "POST",
+            &format!(
+                "/images/{}/tag{}",
+                url_encode_path(source),
+                query_string(&params)
+            ),
+            None,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ContainerHelper for ApiContainerHelper {
+    fn list_image(&self, image_name: &str) -> Result<Vec<String>, HelperError> {
+        self.list(image_name)
+    }
+
+    fn inspect_image(&self, image_name: &str) -> Result<Option<ImageInfo>, HelperError> {
+        self.inspect(image_name)
+    }
+
+    fn remove_image(&self, image_name: &str) -> Result<(), HelperError> {
+        self.remove(image_name)
+    }
+
+    fn run_container(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> Result<(), HelperError> {
+        // Application need terminal of user
+        DefaultContainerHelper::new(&self.endpoint()).run_container(
+            image_name,
+            run_options,
+            cmd,
+            cmd_options,
+        )
+    }
+
+    fn run_container_output(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> Result<String, HelperError> {
+        self.run_output(image_name, run_options, cmd, cmd_options)
+    }
+
+    fn build_image(
+        &self,
+        docker_filename: &str,
+        docker_context_path: &str,
+        docker_tag: &str,
+        build_options: Option<&Vec<String>>,
+        output: &mut FnMut(&str),
+    ) -> Result<(), HelperError> {
+        self.build(
+            docker_filename,
+            docker_context_path,
+            docker_tag,
+            build_options,
+            output,
+        )
+    }
+
+    fn save_images(
+        &self,
+        image_names: &[String],
+        output_filename: &str,
+    ) -> Result<(), HelperError> {
+        self.save(image_names, output_filename)
+    }
+
+    fn load_images(&self, input_filename: &str) -> Result<(), HelperError> {
+        self.load(input_filename)
+    }
+
+    fn tag_image(&self, source: &str, target: &str) -> Result<(), HelperError> {
+        self.tag(source, target)
+    }
+
+    fn push_image(&self, image_name: &str) -> Result<(), HelperError> {
+        // Credentials of registry are managed by `docker` command
+        DefaultContainerHelper::new(&self.endpoint()).push_image(image_name)
+    }
+
+    fn pull_image(&self, image_name: &str) -> Result<(), HelperError> {
+        DefaultContainerHelper::new(&self.endpoint()).pull_image(image_name)
+    }
+
+    fn with_endpoint<'a>(&'a self, endpoint: &DockerEndpoint) -> Box<ContainerHelper + 'a> {
+        if endpoint.is_default() {
+            Box::new(ApiContainerHelper::new(&self.socket))
+        } else {
+            get_container_helper(Some(&ContainerEngine::DockerApi), endpoint)
+        }
+    }
+
+    fn forward_display(&self, _display: &str) -> Result<Option<RemoteDisplay>, HelperError> {
+        // Socket of daemon is always local
+        Ok(None)
+    }
+}
@@ -0,0 +1,332 @@
+///
+/// Module to tests module api with a fake Docker daemon.
+///
+/// Release under MIT License.
+///
+use super::http::ChunkedReader;
+use super::ApiContainerHelper;
+use docker::remote::DockerEndpoint;
+use helper::HelperError;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tempfile::{Builder, TempDir};
+
+/// Request received by fake daemon
+struct TestRequest {
+    /// Like `GET /v1.25/images/json`
+    request_line: String,
+    body: Vec<u8>,
+}
+
+/// Fake Docker daemon on unix socket
+struct TestDaemon {
+    /// Keep folder of socket
+    dir: TempDir,
+    socket: String,
+    requests: Arc<Mutex<Vec<TestRequest>>>,
+}
+
+impl TestDaemon {
+    ///
+    /// Start daemon, each connection get next response.
+    ///
+    fn start(responses: Vec<Vec<u8>>) -> TestDaemon {
+        let dir = Builder::new().prefix("d-sh-test").tempdir().unwrap();
+        let socket = dir.path().join("docker.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let server_requests = requests.clone();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                let mut chunked = false;
+
+                reader.read_line(&mut request_line).unwrap();
+
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+
+                    if header.trim().is_empty() {
+                        break;
+                    }
+
+                    if header.to_lowercase() == "transfer-encoding: chunked\r\n" {
+                        chunked = true;
+                    }
+                }
+
+                let mut body = Vec::new();
+
+                if chunked {
+                    ChunkedReader::new(reader).read_to_end(&mut body).unwrap();
+                }
+
+                server_requests.lock().unwrap().push(TestRequest {
+                    request_line: String::from(request_line.trim_end()),
+                    body: body,
+                });
+
+                stream.write_all(&response).unwrap();
+            }
+        });
+
+        TestDaemon {
+            socket: dir.path().join("docker.sock").to_str().unwrap().to_string(),
+            dir: dir,
+            requests: requests,
+        }
+    }
+
+    fn helper(&self) -> ApiContainerHelper {
+        ApiContainerHelper::new(&self.socket)
+    }
+
+    fn request_lines(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.request_line.clone())
+            .collect()
+    }
+}
+
+fn json_response(status: &str, body: &str) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+fn chunked_response(chunks: &[&str]) -> Vec<u8> {
+    let mut response = String::from("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n");
+
+    for chunk in chunks {
+        response.push_str(&format!("{:x}\r\n{}\r\n", chunk.len(), chunk));
+    }
+
+    response.push_str("0\r\n\r\n");
+
+    response.into_bytes()
+}
+
+#[test]
+fn list_image_with_reference_filter() {
+    let daemon = TestDaemon::start(vec![json_response(
+        "200 OK",
+        "[{\"Id\":\"sha256:0123456789abcdef0123\"},{\"Id\":\"sha256:fedcba\"}]",
+    )]);
+
+    assert_eq!(
+        daemon.helper().list("run-atom").unwrap(),
+        vec![String::from("0123456789ab"), String::from("fedcba")]
+    );
+    assert_eq!(
+        daemon.request_lines(),
+        vec![String::from(
+            "GET /v1.25/images/json?filters=%7B%22reference%22%3A%5B%22run-atom%22%5D%7D HTTP/1.1"
+        )]
+    );
+}
+
+#[test]
+fn inspect_image_found_and_not_found() {
+    let daemon = TestDaemon::start(vec![
+        json_response(
+            "200 OK",
+            "{\"Id\":\"sha256:1234\",\"Config\":{\"Labels\":{\"d-sh.hash\":\"5678\"}},\"RepoDigests\":[]}",
+        ),
+        json_response("404 Not Found", "{\"message\":\"No such image: run-atom\"}"),
+    ]);
+    let helper = daemon.helper();
+
+    let image = helper.inspect("run-atom:latest").unwrap().unwrap();
+
+    assert_eq!(image.id, "sha256:1234");
+    assert_eq!(image.labels.get("d-sh.hash").unwrap(), "5678");
+    assert!(helper.inspect("run-atom").unwrap().is_none());
+    assert_eq!(
+        daemon.request_lines(),
+        vec![
+            String::from("GET /v1.25/images/run-atom:latest/json HTTP/1.1"),
+            String::from("GET /v1.25/images/run-atom/json HTTP/1.1"),
+        ]
+    );
+}
+
+#[test]
+fn remove_image_return_message_of_daemon() {
+    let daemon = TestDaemon::start(vec![json_response(
+        "409 Conflict",
+        "{\"message\":\"conflict: unable to remove repository reference\"}",
+    )]);
+
+    assert_eq!(
+        daemon.helper().remove("run-atom"),
+        Err(HelperError::failed(
+            "conflict: unable to remove repository reference"
+        ))
+    );
+}
+
+#[test]
+fn daemon_not_running() {
+    let daemon = TestDaemon::start(Vec::new());
+    let helper = ApiContainerHelper::new(daemon.dir.path().join("other.sock").to_str().unwrap());
+
+    match helper.remove("run-atom") {
+        Err(HelperError::DaemonUnreachable(msg)) => {
+            assert!(msg.starts_with("Cannot talk to Docker daemon at unix://"))
+        }
+        _ => panic!("Daemon must be unreachable"),
+    }
+}
+
+#[test]
+fn build_image_stream_output() {
+    let daemon = TestDaemon::start(vec![chunked_response(&[
+        "{\"stream\":\"Step 1/2 : FROM ubuntu\"}\r\n{\"stream\":\"\\n\"}\r\n",
+        "{\"stream\":\" ---> 1234\\nStep 2/2 : RUN false\\n\"}\r\n",
+        "{\"errorDetail\":{\"message\":\"failed\"},\"error\":\"The command returned a non-zero code: 1\"}\r\n",
+    ])]);
+    let context = daemon.dir.path().join("context");
+
+    fs::create_dir(&context).unwrap();
+    fs::write(context.join("Dockerfile"), "FROM ubuntu\nRUN false\n").unwrap();
+
+    let context = context.to_str().unwrap();
+    let build_options = vec![
+        String::from("--no-cache"),
+        String::from("--label"),
+        String::from("d-sh.hash=1234"),
+    ];
+    let mut lines = Vec::new();
+
+    let result = daemon.helper().build(
+        &format!("{}/Dockerfile", context),
+        context,
+        "run-atom:latest",
+        Some(&build_options),
+        &mut |l| lines.push(String::from(l)),
+    );
+
+    assert_eq!(
+        result,
+        Err(HelperError::failed(
+            "The command returned a non-zero code: 1"
+        ))
+    );
+    assert_eq!(
+        lines,
+        vec![
+            String::from("Step 1/2 : FROM ubuntu"),
+            String::from(" ---> 1234"),
+            String::from("Step 2/2 : RUN false"),
+            String::from("The command returned a non-zero code: 1"),
+        ]
+    );
+
+    let requests = daemon.requests.lock().unwrap();
+
+    assert_eq!(
+        requests[0].request_line,
+        "POST /v1.25/build?t=run-atom%3Alatest&dockerfile=Dockerfile&nocache=1&labels=%7B%22d-sh.hash%22%3A%221234%22%7D HTTP/1.1"
+    );
+    // Context is sent as tar archive
+    assert!(!requests[0].body.is_empty());
+}
+
+#[test]
+fn build_image_with_unsupported_option() {
+    let daemon = TestDaemon::start(Vec::new());
+    let build_options = vec![String::from("--squash")];
+
+    assert_eq!(
+        daemon.helper().build(
+            "/tmp/d-sh/Dockerfile",
+            "/tmp/d-sh",
+            "run-atom:latest",
+            Some(&build_options),
+            &mut |_| {},
+        ),
+        Err(HelperError::failed(
+            "Build option '--squash' is not supported!"
+        ))
+    );
+}
+
+#[test]
+fn run_container_output_return_stdout() {
+    // Logs are multiplexed: stream type (1 is stdout), 3 zero, size and payload
+    let mut frames = Vec::new();
+    frames.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 3]);
+    frames.extend_from_slice(b"1.2");
+    frames.extend_from_slice(&[2, 0, 0, 0, 0, 0, 0, 2]);
+    frames.extend_from_slice(b"ko");
+    frames.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 2]);
+    frames.extend_from_slice(b"\r\n");
+
+    let mut logs = format!(
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+        frames.len()
+    )
+    .into_bytes();
+    logs.extend_from_slice(&frames);
+
+    let daemon = TestDaemon::start(vec![
+        json_response("201 Created", "{\"Id\":\"abcd\"}"),
+        json_response("204 No Content", ""),
+        json_response("200 OK", "{\"StatusCode\":0}"),
+        logs,
+        json_response("204 No Content", ""),
+    ]);
+    let run_options = vec![
+        String::from("--rm"),
+        String::from("--entrypoint"),
+        String::from("/bin/sh"),
+    ];
+    let cmd_options = vec![String::from("echo 1.2")];
+
+    assert_eq!(
+        daemon.helper().run_output(
+            "d-base-image:v1.0.0",
+            Some(&run_options),
+            Some("-c"),
+            Some(&cmd_options)
+        ),
+        Ok(String::from("1.2\r\n"))
+    );
+
+    let requests = daemon.requests.lock().unwrap();
+    let config: ::serde_json::Value = ::serde_json::from_slice(&requests[0].body).unwrap();
+
+    assert_eq!(config["Entrypoint"], json!(["/bin/sh"]));
+    assert_eq!(config["Cmd"], json!(["-c", "echo 1.2"]));
+    assert_eq!(
+        requests[4].request_line,
+        "DELETE /v1.25/containers/abcd?force=1 HTTP/1.1"
+    );
+}
+
+#[test]
+fn docker_command_use_same_socket() {
+    let helper = ApiContainerHelper::new("/run/user/1000/docker.sock");
+
+    assert_eq!(
+        helper.endpoint(),
+        DockerEndpoint::new(
+            Some(&String::from("unix:///run/user/1000/docker.sock")),
+            None
+        )
+    );
+}
@@ -3,19 +3,161 @@
 ///
 /// Release under MIT License.
 ///
-use std::process::Command;
+use self::api::{ApiContainerHelper, DOCKER_SOCKET};
+use self::podman::PodmanContainerHelper;
+use self::remote::{open_display_tunnel, DockerEndpoint, RemoteDisplay};
+use config::ContainerEngine;
+use helper::HelperError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::channel;
+use std::thread;
 
+pub mod api;
+pub mod podman;
+pub mod remote;
 #[cfg(test)]
 pub mod tests;
 
+///
+/// Information of a docker image.
+///
+pub struct ImageInfo {
+    /// Id of image.
+    pub id: String,
+    /// Labels of image.
+    pub labels: HashMap<String, String>,
+    /// Digests of image in registry, like `ubuntu@sha256:...`.
+    pub repo_digests: Vec<String>,
+    /// Tags of image, like `run-atom:latest`.
+    pub tags: Vec<String>,
+    /// Creation date in seconds since epoch.
+    pub created: Option<u64>,
+    /// Size of image in bytes.
+    pub size: u64,
+}
+
+///
+/// Return seconds since epoch of date like `2019-01-31T10:20:30.123456789Z` or
+/// `2019-01-31T11:20:30+01:00`.
+///
+/// returning None if date has bad format.
+///
+pub fn parse_date(date: &str) -> Option<u64> {
+    let number = |start: usize, end: usize| -> Option<i64> {
+        match date.get(start..end) {
+            Some(n) if n.chars().all(|c| c.is_ascii_digit()) => n.parse().ok(),
+            _ => None,
+        }
+    };
+
+    let (year, month, day) = (number(0, 4)?, number(5, 7)?, number(8, 10)?);
+    let (hour, minute, second) = (number(11, 13)?, number(14, 16)?, number(17, 19)?);
+
+    // Skip fraction of second
+    let zone = date[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+
+    let offset = match zone.chars().next() {
+        Some('Z') => 0,
+        Some(sign) if sign == '+' || sign == '-' => {
+            let hours: i64 = zone.get(1..3)?.parse().ok()?;
+            let minutes: i64 = zone.get(4..6)?.parse().ok()?;
+            let offset = hours * 3600 + minutes * 60;
+
+            if sign == '+' {
+                offset
+            } else {
+                -offset
+            }
+        }
+        _ => return None,
+    };
+
+    // Days since epoch of civil date (algorithm of Howard Hinnant)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let seconds = days * 86400 + hour * 3600 + minute * 60 + second - offset;
+
+    if seconds < 0 {
+        None
+    } else {
+        Some(seconds as u64)
+    }
+}
+
+///
+/// Return list of string of JSON array.
+///
+fn get_string_array(value: &Value) -> Vec<String> {
+    match value.as_array() {
+        Some(d) => d
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|v| v.to_string())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+///
+/// Return image name without tag.
+///
+/// `ubuntu:18.04` is `ubuntu`, `localhost:5000/ubuntu` stay `localhost:5000/ubuntu`.
+///
+pub fn get_image_repository(image_name: &str) -> &str {
+    // Tag is after last ':' only if not in host part
+    match image_name.rfind(':') {
+        Some(index) if !image_name[index..].contains('/') => &image_name[..index],
+        _ => image_name,
+    }
+}
+
+///
+/// Return information of image from JSON of `docker image inspect`.
+///
+pub fn get_image_info(image: &Value) -> ImageInfo {
+    let mut labels = HashMap::new();
+
+    // Labels is null if image have no label
+    if let Some(l) = image["Config"]["Labels"].as_object() {
+        for (key, value) in l {
+            if let Some(v) = value.as_str() {
+                labels.insert(key.to_string(), v.to_string());
+            }
+        }
+    }
+
+    ImageInfo {
+        id: image["Id"].as_str().unwrap_or("").to_string(),
+        labels: labels,
+        // RepoDigests is empty if image is build locally
+        repo_digests: get_string_array(&image["RepoDigests"]),
+        tags: get_string_array(&image["RepoTags"]),
+        created: image["Created"].as_str().and_then(parse_date),
+        size: image["Size"].as_u64().unwrap_or(0),
+    }
+}
+
 /// Trait to write one screen.
 pub trait ContainerHelper {
     /// List image.
     /// Return list of image id.
-    fn list_image(&self, image_name: &str) -> Vec<String>;
+    fn list_image(&self, image_name: &str) -> Result<Vec<String>, HelperError>;
+    /// Inspect image.
+    /// Return None if image not found.
+    fn inspect_image(&self, image_name: &str) -> Result<Option<ImageInfo>, HelperError>;
     /// Remove image.
-    /// Return true if ok.
-    fn remove_image(&self, image_name: &str) -> bool;
+    fn remove_image(&self, image_name: &str) -> Result<(), HelperError>;
     /// Run a image.
     /// `image_name` is docker image
     /// `run_options` is option of docker like volume, port...
@@ -27,95 +169,463 @@ pub trait ContainerHelper {
         run_options: Option<&Vec<String>>,
         cmd: Option<&str>,
         cmd_options: Option<&Vec<String>>,
-    ) -> bool;
+    ) -> Result<(), HelperError>;
+    /// Run a image and return output.
+    /// Same parameters as `run_container`.
+    fn run_container_output(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> Result<String, HelperError>;
     /// Build a docker image
     /// `docker_filename` is path of docker_filename
     /// `docker_context_path` is context of build
     /// `docker_tag` is docker tag
     /// `build_options` is docker build args (--build-args)
+    /// `output` is called for each line of stdout and stderr of build
     fn build_image(
         &self,
         docker_filename: &str,
         docker_context_path: &str,
         docker_tag: &str,
         build_options: Option<&Vec<String>>,
-    ) -> bool;
+        output: &mut FnMut(&str),
+    ) -> Result<(), HelperError>;
+    /// Save images in a tar file (like `docker save`).
+    fn save_images(&self, image_names: &[String], output_filename: &str)
+        -> Result<(), HelperError>;
+    /// Load images from a tar file (like `docker load`).
+    fn load_images(&self, input_filename: &str) -> Result<(), HelperError>;
+    /// Add tag `target` to image `source`.
+    fn tag_image(&self, source: &str, target: &str) -> Result<(), HelperError>;
+    /// Push image in registry.
+    fn push_image(&self, image_name: &str) -> Result<(), HelperError>;
+    /// Pull image from registry.
+    fn pull_image(&self, image_name: &str) -> Result<(), HelperError>;
+    /// Return same helper talking to another Docker daemon.
+    fn with_endpoint<'a>(&'a self, endpoint: &DockerEndpoint) -> Box<ContainerHelper + 'a>;
+    /// Forward local `display` to Docker host.
+    /// Return None if Docker host is local.
+    fn forward_display(&self, display: &str) -> Result<Option<RemoteDisplay>, HelperError>;
 }
 
-/// Default print on tty.
-pub struct DefaultContainerHelper;
+///
+/// Return arguments of `docker container run`.
+///
+pub fn get_run_args(
+    image_name: &str,
+    run_options: Option<&Vec<String>>,
+    cmd: Option<&str>,
+    cmd_options: Option<&Vec<String>>,
+) -> Vec<String> {
+    // docker run
+    let mut args = vec![String::from("container"), String::from("run")];
 
-impl ContainerHelper for DefaultContainerHelper {
-    fn list_image(&self, image_name: &str) -> Vec<String> {
-        match Command::new("docker")
-            .args(&["image", "list", "--format", "{{.ID}}", image_name])
-            .output()
-        {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let result: Vec<&str> = stdout.split(r"\n").collect();
-                // Convert to string
-                result
-                    .iter()
-                    .map(|s| s.to_string())
-                    .filter(|s| !s.trim().is_empty()) // Remove empty line
-                    .collect()
-            }
-            Err(_) => Vec::new(),
+    // -v /tmp/.X11-unix/:/tmp/.X11-unix/
+    // -v /dev/shm:/dev/shm
+    // -v ${HOME}:/home/${USER}
+    // -e DISPLAY
+    // -e USERNAME_TO_RUN=${USER}
+    // -e USERNAME_TO_RUN_GID=${GID}
+    // -e USERNAME_TO_RUN_UID=${UID}
+    if run_options.is_some() {
+        for opt in run_options.unwrap() {
+            args.push(opt.to_string());
         }
     }
 
-    fn remove_image(&self, image_name: &str) -> bool {
-        match Command::new("docker")
-            .args(&["image", "rm", image_name])
-            .status()
-        {
-            Ok(status) => status.success(),
-            Err(_) => false,
+    // ${APPLICATION_IMAGE_DOCKER}
+    args.push(String::from(image_name));
+
+    // ${APPLICATION_COMMAND_LINE}
+    if cmd.is_some() {
+        args.push(String::from(cmd.unwrap()));
+    }
+
+    // $@
+    if cmd_options.is_some() {
+        for opt in cmd_options.unwrap() {
+            args.push(opt.to_string());
         }
     }
 
-    fn run_container(
+    args
+}
+
+///
+/// Container engine with same command line as Docker.
+///
+pub trait EngineCommand {
+    /// Return command of engine, like `docker`.
+    fn engine_command(&self) -> Command;
+    /// Return Docker daemon of engine.
+    fn engine_endpoint(&self) -> &DockerEndpoint;
+    /// Return same engine talking to another Docker daemon.
+    fn engine_with_endpoint(&self, endpoint: &DockerEndpoint) -> Box<ContainerHelper>;
+    /// Return host of context, like `ssh://user@workstation`.
+    fn engine_context_host(&self, context: &str) -> Result<String, HelperError> {
+        let output = match self
+            .engine_command()
+            .args(&[
+                "context",
+                "inspect",
+                "--format",
+                "{{.Endpoints.docker.Host}}",
+                context,
+            ])
+            .output()
+        {
+            Ok(r) => r,
+            Err(err) => return Err(get_spawn_error(&err)),
+        };
+
+        if output.status.success() {
+            Ok(String::from(String::from_utf8_lossy(&output.stdout).trim()))
+        } else {
+            Err(get_engine_error(
+                output.status.code(),
+                &String::from_utf8_lossy(&output.stderr),
+            ))
+        }
+    }
+    /// Return arguments of `container run`.
+    fn engine_run_args(
         &self,
         image_name: &str,
         run_options: Option<&Vec<String>>,
         cmd: Option<&str>,
         cmd_options: Option<&Vec<String>>,
-    ) -> bool {
-        // docker run
-        let mut args = vec![String::from("container"), String::from("run")];
-
-        // -v /tmp/.X11-unix/:/tmp/.X11-unix/
-        // -v /dev/shm:/dev/shm
-        // -v ${HOME}:/home/${USER}
-        // -e DISPLAY
-        // -e USERNAME_TO_RUN=${USER}
-        // -e USERNAME_TO_RUN_GID=${GID}
-        // -e USERNAME_TO_RUN_UID=${UID}
-        if run_options.is_some() {
-            for opt in run_options.unwrap() {
-                args.push(opt.to_string());
+    ) -> Vec<String> {
+        get_run_args(image_name, run_options, cmd, cmd_options)
+    }
+}
+
+///
+/// Return true if engine is installed.
+///
+fn is_engine_installed(binary: &str) -> bool {
+    match Command::new(binary).arg("--version").output() {
+        Ok(output) => output.status.success(),
+        Err(_) => false,
+    }
+}
+
+///
+/// Return engine to use, `auto` is Docker if installed, else Podman if installed.
+///
+pub fn select_container_engine(
+    engine: Option<&ContainerEngine>,
+    is_installed: &Fn(&str) -> bool,
+) -> ContainerEngine {
+    match engine {
+        Some(ContainerEngine::Podman) => ContainerEngine::Podman,
+        Some(ContainerEngine::DockerApi) => ContainerEngine::DockerApi,
+        Some(ContainerEngine::Auto) if !is_installed("docker") && is_installed("podman") => {
+            ContainerEngine::Podman
+        }
+        _ => ContainerEngine::Docker,
+    }
+}
+
+///
+/// Return unix socket of Docker daemon, None if daemon is not local.
+///
+fn get_docker_socket() -> Option<String> {
+    match env::var("DOCKER_HOST") {
+        Ok(ref host) if host.starts_with("unix://") => Some(String::from(&host["unix://".len()..])),
+        // Remote daemon is managed by `docker` command
+        Ok(ref host) if !host.is_empty() => None,
+        _ => Some(String::from(DOCKER_SOCKET)),
+    }
+}
+
+///
+/// Return helper of container engine.
+///
+/// With `docker-api` engine, local Docker daemon is called by Engine API if its socket
+/// exists.
+///
+pub fn get_container_helper(
+    engine: Option<&ContainerEngine>,
+    endpoint: &DockerEndpoint,
+) -> Box<ContainerHelper> {
+    match select_container_engine(engine, &is_engine_installed) {
+        ContainerEngine::Podman => Box::new(PodmanContainerHelper::new(endpoint)),
+        ContainerEngine::DockerApi => {
+            let socket = match endpoint.host {
+                Some(ref host) if host.starts_with("unix://") => {
+                    Some(String::from(&host["unix://".len()..]))
+                }
+                Some(_) => None,
+                None if endpoint.context.is_some() => None,
+                None => get_docker_socket(),
+            };
+
+            match socket {
+                Some(ref socket) if Path::new(socket).exists() => {
+                    Box::new(ApiContainerHelper::new(socket))
+                }
+                _ => Box::new(DefaultContainerHelper::new(endpoint)),
             }
         }
+        _ => Box::new(DefaultContainerHelper::new(endpoint)),
+    }
+}
+
+/// Start of lines with error of daemon.
+const DAEMON_ERROR_PREFIXES: [&str; 3] = [
+    "Error response from daemon",
+    "Cannot connect",
+    "Got permission denied",
+];
+/// Start of lines with error of engine command, like `Error: ...` of podman.
+const ENGINE_ERROR_PREFIX: &str = "Error: ";
+
+///
+/// Return error of daemon from first line of stderr starting with one of `prefixes`.
+///
+/// Other lines are not read, they can be output of a program (like commands of Dockerfile).
+///
+fn get_daemon_error(stderr: &str, prefixes: &[&str]) -> Option<HelperError> {
+    let line = stderr
+        .lines()
+        .map(|l| l.trim())
+        .find(|l| prefixes.iter().any(|p| l.starts_with(p)))?;
+    let message = String::from(line);
+    let lower = line.to_lowercase();
+
+    if lower.starts_with("cannot connect") || lower.contains("is the docker daemon running") {
+        Some(HelperError::DaemonUnreachable(message))
+    } else if lower.contains("permission denied") {
+        Some(HelperError::PermissionDenied(message))
+    } else if lower.contains("no such image")
+        || lower.contains("no such container")
+        || lower.contains("image not known")
+        || lower.contains("manifest unknown")
+        || lower.contains("not found")
+    {
+        Some(HelperError::NotFound(message))
+    } else {
+        None
+    }
+}
+
+///
+/// Return error of engine from its stderr.
+///
+pub fn get_engine_error(code: Option<i32>, stderr: &str) -> HelperError {
+    let mut prefixes = DAEMON_ERROR_PREFIXES.to_vec();
+
+    prefixes.push(ENGINE_ERROR_PREFIX);
+
+    match get_daemon_error(stderr, &prefixes) {
+        Some(err) => err,
+        None => HelperError::Failed {
+            code: code,
+            message: String::from(stderr.trim()),
+        },
+    }
+}
+
+///
+/// Return error of build from its stderr.
+///
+/// Stderr has output of commands of Dockerfile, so only last line is kept, full output is in
+/// log of build.
+///
+pub fn get_build_error(code: Option<i32>, stderr: &[String]) -> HelperError {
+    match get_daemon_error(&stderr.join("\n"), &DAEMON_ERROR_PREFIXES) {
+        Some(err) => err,
+        None => HelperError::Failed {
+            code: code,
+            message: stderr
+                .iter()
+                .rev()
+                .map(|l| l.trim())
+                .find(|l| !l.is_empty())
+                .map(String::from)
+                .unwrap_or_default(),
+        },
+    }
+}
+
+///
+/// Return id of images from output of `image list`, one image by line.
+///
+fn parse_image_list(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty()) // Remove empty line
+        .map(String::from)
+        .collect()
+}
+
+///
+/// Return error when engine command cannot be run.
+///
+fn get_spawn_error(err: &Error) -> HelperError {
+    match err.kind() {
+        ErrorKind::NotFound => {
+            HelperError::DaemonUnreachable(format!("Container engine is not installed: {}", err))
+        }
+        ErrorKind::PermissionDenied => HelperError::PermissionDenied(format!("{}", err)),
+        _ => HelperError::failed(&format!("Unable to run container engine: {}", err)),
+    }
+}
 
-        // ${APPLICATION_IMAGE_DOCKER}
-        args.push(String::from(image_name));
+///
+/// Run engine command, its stdout is displayed and its stderr is kept for error.
+///
+fn run_engine_command(command: &mut Command) -> Result<(), HelperError> {
+    let output = match command
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::piped())
+        .output()
+    {
+        Ok(r) => r,
+        Err(err) => return Err(get_spawn_error(&err)),
+    };
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(get_engine_error(
+            output.status.code(),
+            &String::from_utf8_lossy(&output.stderr),
+        ))
+    }
+}
+
+/// Default print on tty.
+pub struct DefaultContainerHelper {
+    /// Docker daemon, given to `docker` command
+    endpoint: DockerEndpoint,
+}
 
-        // ${APPLICATION_COMMAND_LINE}
-        if cmd.is_some() {
-            args.push(String::from(cmd.unwrap()));
+impl DefaultContainerHelper {
+    pub fn new(endpoint: &DockerEndpoint) -> DefaultContainerHelper {
+        DefaultContainerHelper {
+            endpoint: endpoint.clone(),
         }
+    }
+}
 
-        // $@
-        if cmd_options.is_some() {
-            for opt in cmd_options.unwrap() {
-                args.push(opt.to_string());
-            }
+impl EngineCommand for DefaultContainerHelper {
+    fn engine_command(&self) -> Command {
+        let mut command = Command::new("docker");
+
+        if let Some(ref host) = self.endpoint.host {
+            command.arg("-H").arg(host);
+        } else if let Some(ref context) = self.endpoint.context {
+            command.arg("--context").arg(context);
+        }
+
+        command
+    }
+
+    fn engine_endpoint(&self) -> &DockerEndpoint {
+        &self.endpoint
+    }
+
+    fn engine_with_endpoint(&self, endpoint: &DockerEndpoint) -> Box<ContainerHelper> {
+        get_container_helper(Some(&ContainerEngine::Docker), endpoint)
+    }
+}
+
+impl<T: EngineCommand> ContainerHelper for T {
+    fn list_image(&self, image_name: &str) -> Result<Vec<String>, HelperError> {
+        let output = self
+            .engine_command()
+            .args(&["image", "list", "--format", "{{.ID}}", image_name])
+            .output()
+            .map_err(|err| HelperError::failed(&format!("{}", err)))?;
+
+        if !output.status.success() {
+            return Err(get_engine_error(
+                output.status.code(),
+                &String::from_utf8_lossy(&output.stderr),
+            ));
+        }
+
+        Ok(parse_image_list(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn inspect_image(&self, image_name: &str) -> Result<Option<ImageInfo>, HelperError> {
+        let output = self
+            .engine_command()
+            .args(&["image", "inspect", "--format", "{{json .}}", image_name])
+            .output()
+            .map_err(|err| HelperError::failed(&format!("{}", err)))?;
+
+        if !output.status.success() {
+            return match get_engine_error(
+                output.status.code(),
+                &String::from_utf8_lossy(&output.stderr),
+            ) {
+                HelperError::NotFound(_) => Ok(None),
+                err => Err(err),
+            };
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        match serde_json::from_str(stdout.trim()) {
+            Ok(image) => Ok(Some(get_image_info(&image))),
+            Err(err) => Err(HelperError::failed(&format!(
+                "Bad output of image inspect: {}",
+                err
+            ))),
         }
+    }
+
+    fn remove_image(&self, image_name: &str) -> Result<(), HelperError> {
+        run_engine_command(self.engine_command().args(&["image", "rm", image_name]))
+    }
+
+    fn run_container(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> Result<(), HelperError> {
+        let args = self.engine_run_args(image_name, run_options, cmd, cmd_options);
 
-        match Command::new("docker").args(&args).status() {
-            Ok(status) => status.success(),
-            Err(_) => false,
+        // Application use terminal, so stderr is not kept
+        match self.engine_command().args(&args).status() {
+            Ok(ref status) if status.success() => Ok(()),
+            Ok(status) => Err(HelperError::Failed {
+                code: status.code(),
+                message: String::new(),
+            }),
+            Err(err) => Err(get_spawn_error(&err)),
+        }
+    }
+
+    fn run_container_output(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> Result<String, HelperError> {
+        let args = self.engine_run_args(image_name, run_options, cmd, cmd_options);
+
+        match self.engine_command().args(&args).output() {
+            Ok(output) => {
+                if output.status.success() {
+                    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+                } else {
+                    Err(get_engine_error(
+                        output.status.code(),
+                        &String::from_utf8_lossy(&output.stderr),
+                    ))
+                }
+            }
+            Err(err) => Err(get_spawn_error(&err)),
         }
     }
 
@@ -125,7 +635,8 @@ impl ContainerHelper for DefaultContainerHelper {
         docker_context_path: &str,
         docker_tag: &str,
         build_options: Option<&Vec<String>>,
-    ) -> bool {
+        output: &mut FnMut(&str),
+    ) -> Result<(), HelperError> {
         // docker build
         let mut args = vec![String::from("image"), String::from("build")];
 
@@ -144,9 +655,113 @@ impl ContainerHelper for DefaultContainerHelper {
         // PATH
         args.push(String::from(docker_context_path));
 
-        match Command::new("docker").args(&args).status() {
-            Ok(status) => status.success(),
-            Err(_) => false,
+        let mut child = match self
+            .engine_command()
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+        {
+            Ok(c) => c,
+            Err(err) => return Err(get_spawn_error(&err)),
+        };
+
+        // Read stdout and stderr in same time to keep order of lines
+        let (sender, receiver) = channel();
+
+        let readers: Vec<(bool, Box<Read + Send>)> = vec![
+            (false, Box::new(child.stdout.take().unwrap())),
+            (true, Box::new(child.stderr.take().unwrap())),
+        ];
+
+        for (is_stderr, reader) in readers {
+            let sender = sender.clone();
+
+            thread::spawn(move || {
+                for line in BufReader::new(reader).lines() {
+                    match line {
+                        Ok(l) => {
+                            if sender.send((is_stderr, l)).is_err() {
+                                break;
+                            }
+                        }
+                        Err(_) => break,
+                    }
+                }
+            });
         }
+
+        // Only threads have sender now, loop stop when both streams are closed
+        drop(sender);
+
+        // Stderr is kept to know cause of error
+        let mut stderr = Vec::new();
+
+        for (is_stderr, line) in receiver {
+            output(&line);
+
+            if is_stderr {
+                stderr.push(line);
+            }
+        }
+
+        match child.wait() {
+            Ok(ref status) if status.success() => Ok(()),
+            Ok(status) => Err(get_build_error(status.code(), &stderr)),
+            Err(err) => Err(HelperError::failed(&format!("{}", err))),
+        }
+    }
+
+    fn save_images(
+        &self,
+        image_names: &[String],
+        output_filename: &str,
+    ) -> Result<(), HelperError> {
+        run_engine_command(
+            self.engine_command()
+                .args(&["image", "save", "-o", output_filename])
+                .args(image_names),
+        )
+    }
+
+    fn load_images(&self, input_filename: &str) -> Result<(), HelperError> {
+        run_engine_command(
+            self.engine_command()
+                .args(&["image", "load", "-i", input_filename]),
+        )
+    }
+
+    fn tag_image(&self, source: &str, target: &str) -> Result<(), HelperError> {
+        run_engine_command(
+            self.engine_command()
+                .args(&["image", "tag", source, target]),
+        )
+    }
+
+    fn push_image(&self, image_name: &str) -> Result<(), HelperError> {
+        run_engine_command(self.engine_command().args(&["image", "push", image_name]))
+    }
+
+    fn pull_image(&self, image_name: &str) -> Result<(), HelperError> {
+        run_engine_command(self.engine_command().args(&["image", "pull", image_name]))
+    }
+
+    fn with_endpoint<'a>(&'a self, endpoint: &DockerEndpoint) -> Box<ContainerHelper + 'a> {
+        self.engine_with_endpoint(endpoint)
+    }
+
+    fn forward_display(&self, display: &str) -> Result<Option<RemoteDisplay>, HelperError> {
+        let endpoint = self.engine_endpoint();
+
+        if !endpoint.is_remote() {
+            return Ok(None);
+        }
+
+        let host = match endpoint.host {
+            Some(ref h) => h.clone(),
+            None => self.engine_context_host(endpoint.context.as_ref().unwrap())?,
+        };
+
+        open_display_tunnel(&host, display).map(Some)
     }
 }
@@ -0,0 +1,128 @@
+///
+/// Module to use rootless Podman instead of Docker.
+///
+/// Release under MIT License.
+///
+use docker::remote::DockerEndpoint;
+use docker::{get_run_args, ContainerHelper, EngineCommand};
+use helper::HelperError;
+use std::process::Command;
+
+#[cfg(test)]
+mod tests;
+
+/// Environment variable with user to run, set by `run` command.
+const USERNAME_TO_RUN_ENV: &str = "USERNAME_TO_RUN=";
+
+/// Options to not use SELinux labels in container, so mounted folders can be read.
+const SELINUX_DISABLE_OPTIONS: [&str; 2] = ["--security-opt", "label=disable"];
+
+///
+/// Convert options of `docker run` to `podman run`.
+///
+/// SELinux separation is disabled for container with volumes, to not relabel folders of
+/// host like `$HOME`. If user to run is given, container is run with current
+/// user (`--userns=keep-id`) and entrypoint that create user is not used.
+///
+pub fn get_podman_run_options(run_options: &[String]) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut username = None;
+    let mut has_volume = false;
+    let mut has_label = false;
+    let mut options = run_options.iter();
+
+    while let Some(opt) = options.next() {
+        result.push(opt.clone());
+
+        match opt.as_str() {
+            "-v" | "--volume" => has_volume = true,
+            "--security-opt" => {
+                if let Some(security) = options.next() {
+                    has_label = has_label || security.starts_with("label=");
+
+                    result.push(security.clone());
+                }
+            }
+            "-e" | "--env" => {
+                if let Some(env) = options.next() {
+                    if let Some(name) = env.strip_prefix(USERNAME_TO_RUN_ENV) {
+                        username = Some(String::from(name));
+                    }
+
+                    result.push(env.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if has_volume && !has_label {
+        result.extend(SELINUX_DISABLE_OPTIONS.iter().map(|o| String::from(*o)));
+    }
+
+    if let Some(u) = username {
+        result.push(String::from("--userns=keep-id"));
+        // Empty entrypoint to run command directly
+        result.push(String::from("--entrypoint"));
+        result.push(String::new());
+        result.push(String::from("-e"));
+        result.push(format!("HOME=/home/{}", u));
+    }
+
+    result
+}
+
+/// Podman engine.
+pub struct PodmanContainerHelper {
+    /// Podman service, given as `--url` or `--connection`
+    endpoint: DockerEndpoint,
+}
+
+impl PodmanContainerHelper {
+    pub fn new(endpoint: &DockerEndpoint) -> PodmanContainerHelper {
+        PodmanContainerHelper {
+            endpoint: endpoint.clone(),
+        }
+    }
+}
+
+impl EngineCommand for PodmanContainerHelper {
+    fn engine_command(&self) -> Command {
+        let mut command = Command::new("podman");
+
+        if let Some(ref host) = self.endpoint.host {
+            command.arg("--url").arg(host);
+        } else if let Some(ref context) = self.endpoint.context {
+            command.arg("--connection").arg(context);
+        }
+
+        command
+    }
+
+    fn engine_endpoint(&self) -> &DockerEndpoint {
+        &self.endpoint
+    }
+
+    fn engine_with_endpoint(&self, endpoint: &DockerEndpoint) -> Box<ContainerHelper> {
+        Box::new(PodmanContainerHelper::new(endpoint))
+    }
+
+    fn engine_context_host(&self, context: &str) -> Result<String, HelperError> {
+        Err(HelperError::failed(&format!(
+            "Display cannot be forwarded to Podman connection '{}', use docker_host",
+            context
+        )))
+    }
+
+    fn engine_run_args(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> Vec<String> {
+        let podman_options = run_options.map(|o| get_podman_run_options(o));
+
+        get_run_args(image_name, podman_options.as_ref(), cmd, cmd_options)
+    }
+}
@@ -0,0 +1,107 @@
+///
+/// Module to tests module podman.
+///
+/// Release under MIT License.
+///
+use super::get_podman_run_options;
+use config::ContainerEngine;
+use docker::select_container_engine;
+
+fn to_vec(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| String::from(*a)).collect()
+}
+
+#[test]
+fn podman_run_options_disable_selinux_label() {
+    let options = to_vec(&[
+        "-v",
+        "/home/user:/home/user",
+        "--volume",
+        "/tmp/.X11-unix/:/tmp/.X11-unix/:ro",
+        "-v",
+        "/data:/data:ro,Z",
+        "-v",
+        "/anonymous",
+        "-d",
+    ]);
+
+    assert_eq!(
+        get_podman_run_options(&options),
+        to_vec(&[
+            "-v",
+            "/home/user:/home/user",
+            "--volume",
+            "/tmp/.X11-unix/:/tmp/.X11-unix/:ro",
+            "-v",
+            "/data:/data:ro,Z",
+            "-v",
+            "/anonymous",
+            "-d",
+            "--security-opt",
+            "label=disable",
+        ])
+    );
+
+    // Label given by user is kept
+    let options = to_vec(&["-v", "/data:/data", "--security-opt", "label=type:spc_t"]);
+
+    assert_eq!(get_podman_run_options(&options), options);
+}
+
+#[test]
+fn podman_run_options_keep_user_id() {
+    let options = to_vec(&["-e", "DISPLAY=:0", "-e", "USERNAME_TO_RUN=user"]);
+
+    assert_eq!(
+        get_podman_run_options(&options),
+        to_vec(&[
+            "-e",
+            "DISPLAY=:0",
+            "-e",
+            "USERNAME_TO_RUN=user",
+            "--userns=keep-id",
+            "--entrypoint",
+            "",
+            "-e",
+            "HOME=/home/user",
+        ])
+    );
+
+    // Without user, entrypoint is not changed
+    let options = to_vec(&["--entrypoint", "/bin/sh", "-e", "DISPLAY=:0"]);
+
+    assert_eq!(get_podman_run_options(&options), options);
+}
+
+#[test]
+fn select_container_engine_from_config() {
+    let all_installed = |_: &str| true;
+    let podman_installed = |engine: &str| engine == "podman";
+    let nothing_installed = |_: &str| false;
+
+    assert_eq!(
+        select_container_engine(None, &podman_installed),
+        ContainerEngine::Docker
+    );
+    assert_eq!(
+        select_container_engine(Some(&ContainerEngine::Podman), &nothing_installed),
+        ContainerEngine::Podman
+    );
+    assert_eq!(
+        select_container_engine(Some(&ContainerEngine::Auto), &all_installed),
+        ContainerEngine::Docker
+    );
+    assert_eq!(
+        select_container_engine(Some(&ContainerEngine::Auto), &podman_installed),
+        ContainerEngine::Podman
+    );
+    assert_eq!(
+        select_container_engine(Some(&ContainerEngine::Auto), &nothing_installed),
+        ContainerEngine::Docker
+    );
+    // Engine API is only used if asked
+    assert_eq!(
+        select_container_engine(Some(&ContainerEngine::DockerApi), &all_installed),
+        ContainerEngine::DockerApi
+    );
+}
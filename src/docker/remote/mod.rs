@@ -0,0 +1,307 @@
+///
+/// Module to use a remote Docker daemon, display of application is forwarded by SSH.
+///
+/// Release under MIT License.
+///
+use config::{Config, ConfigApplication};
+use helper::HelperError;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+
+#[cfg(test)]
+mod tests;
+
+/// Folder of local X11 sockets.
+const X11_SOCKET_DIR: &str = "/tmp/.X11-unix";
+/// TCP port of X11 display `:0`.
+const X11_TCP_PORT: u16 = 6000;
+/// Message of ssh when port of remote forward is allocated.
+const SSH_ALLOCATED_PORT: &str = "Allocated port ";
+
+///
+/// Docker daemon used by container engine.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DockerEndpoint {
+    /// Daemon socket, like `ssh://user@workstation` (`-H`)
+    pub host: Option<String>,
+    /// Docker context (`--context`), used only if host is not set
+    pub context: Option<String>,
+}
+
+impl DockerEndpoint {
+    ///
+    /// Create endpoint, host wins over context.
+    ///
+    pub fn new(host: Option<&String>, context: Option<&String>) -> DockerEndpoint {
+        match host {
+            Some(h) => DockerEndpoint {
+                host: Some(h.clone()),
+                context: None,
+            },
+            None => DockerEndpoint {
+                host: None,
+                context: context.cloned(),
+            },
+        }
+    }
+
+    ///
+    /// Return true if daemon is the daemon by default.
+    ///
+    pub fn is_default(&self) -> bool {
+        self.host.is_none() && self.context.is_none()
+    }
+
+    ///
+    /// Return true if daemon is not on this computer.
+    ///
+    /// Context other than `default` is considered as remote.
+    ///
+    pub fn is_remote(&self) -> bool {
+        match self.host {
+            Some(ref h) => !h.starts_with("unix://"),
+            None => match self.context {
+                Some(ref c) => c != "default",
+                None => false,
+            },
+        }
+    }
+}
+
+///
+/// Return Docker daemon of config file.
+///
+pub fn get_config_endpoint(config: &Config) -> DockerEndpoint {
+    DockerEndpoint::new(config.docker_host.as_ref(), config.docker_context.as_ref())
+}
+
+///
+/// Return Docker daemon of application if it doesn't use daemon of config file.
+///
+pub fn get_application_endpoint(config_application: &ConfigApplication) -> Option<DockerEndpoint> {
+    let endpoint = DockerEndpoint::new(
+        config_application.docker_host.as_ref(),
+        config_application.docker_context.as_ref(),
+    );
+
+    if endpoint.is_default() {
+        None
+    } else {
+        Some(endpoint)
+    }
+}
+
+///
+/// Return arguments of ssh to connect to host like `ssh://user@workstation:2222`.
+///
+/// returning None if host is not reached by ssh.
+///
+pub fn get_ssh_destination(host: &str) -> Option<Vec<String>> {
+    if !host.starts_with("ssh://") {
+        return None;
+    }
+
+    let destination = host["ssh://".len()..].trim_end_matches('/');
+
+    if destination.is_empty() {
+        return None;
+    }
+
+    // Like `user@workstation:2222`
+    let (user, host_port) = match destination.rfind('@') {
+        Some(index) => (&destination[..index + 1], &destination[index + 1..]),
+        None => ("", destination),
+    };
+
+    match host_port.rfind(':') {
+        Some(index) => {
+            let port = &host_port[index + 1..];
+
+            if port.is_empty() || !port.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+
+            Some(vec![
+                String::from("-p"),
+                String::from(port),
+                format!("{}{}", user, &host_port[..index]),
+            ])
+        }
+        None => Some(vec![String::from(destination)]),
+    }
+}
+
+///
+/// Return local X11 socket of display like `:0` or `unix:1.0`.
+///
+/// returning None if display is not local.
+///
+pub fn get_x11_socket(display: &str) -> Option<String> {
+    let number = display
+        .strip_prefix("unix:")
+        .or_else(|| display.strip_prefix(':'))?;
+
+    // Screen is after '.'
+    let number = number.split('.').next().unwrap_or("");
+
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(format!("{}/X{}", X11_SOCKET_DIR, number))
+}
+
+///
+/// Return port of remote forward from message of ssh, like
+/// `Allocated port 41235 for remote forward to /tmp/.X11-unix/X0`.
+///
+pub fn get_allocated_port(line: &str) -> Option<u16> {
+    let index = line.find(SSH_ALLOCATED_PORT)?;
+
+    line[index + SSH_ALLOCATED_PORT.len()..]
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+///
+/// Return display of port allocated on remote host, like `localhost:35235` for port 41235.
+///
+pub fn get_remote_display(port: u16) -> Result<String, HelperError> {
+    if port < X11_TCP_PORT {
+        return Err(HelperError::failed(&format!(
+            "Port {} allocated by ssh on remote host cannot be used as X11 display (lower than {})",
+            port, X11_TCP_PORT
+        )));
+    }
+
+    Ok(format!("localhost:{}", port - X11_TCP_PORT))
+}
+
+///
+/// Display of application on remote Docker host.
+///
+pub struct RemoteDisplay {
+    /// Value of `DISPLAY` in container, like `localhost:35235`
+    pub display: String,
+    /// SSH tunnel, closed when display is dropped
+    tunnel: Option<Child>,
+}
+
+impl RemoteDisplay {
+    pub fn new(display: &str, tunnel: Option<Child>) -> RemoteDisplay {
+        RemoteDisplay {
+            display: String::from(display),
+            tunnel: tunnel,
+        }
+    }
+}
+
+impl Drop for RemoteDisplay {
+    fn drop(&mut self) {
+        if let Some(ref mut tunnel) = self.tunnel {
+            let _ = tunnel.kill();
+            let _ = tunnel.wait();
+        }
+    }
+}
+
+///
+/// Return error of ssh from its stderr.
+///
+fn get_ssh_error(stderr: &str) -> HelperError {
+    let message = String::from(stderr.trim());
+
+    if message.to_lowercase().contains("permission denied") {
+        HelperError::PermissionDenied(message)
+    } else {
+        HelperError::Network(message)
+    }
+}
+
+///
+/// Open SSH tunnel from a port of remote host to local X11 display.
+///
+/// Port is allocated by sshd, container must use host network to reach it.
+///
+pub fn open_display_tunnel(host: &str, display: &str) -> Result<RemoteDisplay, HelperError> {
+    let destination = match get_ssh_destination(host) {
+        Some(r) => r,
+        None => {
+            return Err(HelperError::failed(&format!(
+                "Display can only be forwarded to docker host 'ssh://', not '{}'",
+                host
+            )));
+        }
+    };
+
+    let socket = match get_x11_socket(display) {
+        Some(r) => r,
+        None => {
+            return Err(HelperError::failed(&format!(
+                "Display '{}' is not a local X11 display",
+                display
+            )));
+        }
+    };
+
+    let mut tunnel = match Command::new("ssh")
+        .args(&["-N", "-T", "-o", "ExitOnForwardFailure=yes", "-R"])
+        .arg(format!("0:{}", socket))
+        .args(&destination)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(HelperError::failed(&format!("Unable to run ssh: {}", err)));
+        }
+    };
+
+    let mut reader = BufReader::new(tunnel.stderr.take().unwrap());
+    let mut stderr = String::new();
+
+    loop {
+        let mut line = String::new();
+
+        match reader.read_line(&mut line) {
+            Ok(n) if n > 0 => {}
+            // Tunnel is closed
+            _ => break,
+        }
+
+        if let Some(port) = get_allocated_port(&line) {
+            let remote_display = match get_remote_display(port) {
+                Ok(r) => r,
+                Err(err) => {
+                    let _ = tunnel.kill();
+                    let _ = tunnel.wait();
+
+                    return Err(err);
+                }
+            };
+
+            // Keep stderr open, else ssh is killed when it writes
+            tunnel.stderr = Some(reader.into_inner());
+
+            return Ok(RemoteDisplay::new(&remote_display, Some(tunnel)));
+        }
+
+        stderr.push_str(&line);
+    }
+
+    let _ = tunnel.kill();
+    let _ = tunnel.wait();
+
+    if stderr.trim().is_empty() {
+        return Err(HelperError::Network(String::from(
+            "SSH tunnel closed before port of display is allocated",
+        )));
+    }
+
+    Err(get_ssh_error(&stderr))
+}
@@ -0,0 +1,111 @@
+///
+/// Module to tests module remote.
+///
+/// Release under MIT License.
+///
+use super::{
+    get_allocated_port, get_remote_display, get_ssh_destination, get_x11_socket,
+    open_display_tunnel, DockerEndpoint,
+};
+use helper::HelperError;
+
+#[test]
+fn endpoint_is_remote() {
+    let unix = String::from("unix:///var/run/docker.sock");
+    let ssh = String::from("ssh://me@workstation");
+    let default_context = String::from("default");
+    let context = String::from("workstation");
+
+    assert!(!DockerEndpoint::new(None, None).is_remote());
+    assert!(DockerEndpoint::new(None, None).is_default());
+    assert!(!DockerEndpoint::new(Some(&unix), None).is_remote());
+    assert!(DockerEndpoint::new(Some(&ssh), None).is_remote());
+    assert!(!DockerEndpoint::new(None, Some(&default_context)).is_remote());
+    assert!(DockerEndpoint::new(None, Some(&context)).is_remote());
+
+    // Host wins over context
+    assert_eq!(
+        DockerEndpoint::new(Some(&unix), Some(&context)),
+        DockerEndpoint {
+            host: Some(unix.clone()),
+            context: None,
+        }
+    );
+}
+
+#[test]
+fn ssh_destination_of_host() {
+    assert_eq!(
+        get_ssh_destination("ssh://me@workstation"),
+        Some(vec![String::from("me@workstation")])
+    );
+    assert_eq!(
+        get_ssh_destination("ssh://me@workstation:2222/"),
+        Some(vec![
+            String::from("-p"),
+            String::from("2222"),
+            String::from("me@workstation"),
+        ])
+    );
+    assert_eq!(
+        get_ssh_destination("ssh://workstation:2222"),
+        Some(vec![
+            String::from("-p"),
+            String::from("2222"),
+            String::from("workstation"),
+        ])
+    );
+    assert_eq!(get_ssh_destination("ssh://workstation:ssh"), None);
+    assert_eq!(get_ssh_destination("tcp://workstation:2376"), None);
+}
+
+#[test]
+fn x11_socket_of_display() {
+    assert_eq!(
+        get_x11_socket(":0"),
+        Some(String::from("/tmp/.X11-unix/X0"))
+    );
+    assert_eq!(
+        get_x11_socket("unix:1.0"),
+        Some(String::from("/tmp/.X11-unix/X1"))
+    );
+    assert_eq!(get_x11_socket("localhost:10.0"), None);
+    assert_eq!(get_x11_socket(":"), None);
+}
+
+#[test]
+fn allocated_port_of_ssh() {
+    assert_eq!(
+        get_allocated_port("Allocated port 41235 for remote forward to /tmp/.X11-unix/X0\r\n"),
+        Some(41235)
+    );
+    assert_eq!(
+        get_allocated_port("Warning: Permanently added 'workstation'"),
+        None
+    );
+}
+
+#[test]
+fn remote_display_of_port() {
+    assert_eq!(
+        get_remote_display(41235),
+        Ok(String::from("localhost:35235"))
+    );
+    assert_eq!(
+        get_remote_display(5900),
+        Err(HelperError::failed(
+            "Port 5900 allocated by ssh on remote host cannot be used as X11 display (lower than 6000)"
+        ))
+    );
+}
+
+#[test]
+fn display_tunnel_need_ssh_host() {
+    match open_display_tunnel("tcp://workstation:2376", ":0") {
+        Err(HelperError::Failed { message, .. }) => assert_eq!(
+            message,
+            "Display can only be forwarded to docker host 'ssh://', not 'tcp://workstation:2376'"
+        ),
+        _ => panic!("Tunnel must not be opened"),
+    }
+}
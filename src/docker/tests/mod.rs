@@ -3,7 +3,11 @@
 ///
 /// Release under MIT License.
 ///
-use super::ContainerHelper;
+use super::remote::{DockerEndpoint, RemoteDisplay};
+use super::{
+    get_build_error, get_engine_error, parse_date, parse_image_list, ContainerHelper, ImageInfo,
+};
+use helper::HelperError;
 use std::cell::RefCell;
 use std::clone::Clone;
 use std::collections::HashMap;
@@ -65,19 +69,107 @@ pub struct TestContainerHelper {
     pub containers: RefCell<Vec<TestRunContainer>>,
     pub builds: RefCell<Vec<TestBuildImage>>,
     pub builds_error: RefCell<HashMap<String, bool>>,
+    pub labels: RefCell<HashMap<String, HashMap<String, String>>>,
+    pub image_ids: RefCell<HashMap<String, String>>,
+    /// Output of build by docker tag
+    pub builds_output: RefCell<HashMap<String, Vec<String>>>,
+    /// Output of container by image name
+    pub run_outputs: RefCell<HashMap<String, String>>,
+    /// Images saved by tar file
+    pub saves: RefCell<HashMap<String, Vec<String>>>,
+    /// Tar files loaded
+    pub loads: RefCell<Vec<String>>,
+    pub loads_error: RefCell<HashMap<String, bool>>,
+    /// Images in registry, labels are kept in `labels`
+    pub registry_images: RefCell<Vec<String>>,
+    pub repo_digests: RefCell<HashMap<String, Vec<String>>>,
+    /// Error of all commands, like daemon not running
+    pub error: RefCell<Option<HelperError>>,
+    /// Size of image by image name
+    pub sizes: RefCell<HashMap<String, u64>>,
+    /// Creation date (seconds since epoch) by image name
+    pub created: RefCell<HashMap<String, u64>>,
+    /// Docker daemons asked by `with_endpoint`
+    pub endpoints: RefCell<Vec<DockerEndpoint>>,
+    /// Display in container if Docker host is remote
+    pub remote_display: RefCell<Option<String>>,
+}
+
+impl TestContainerHelper {
+    ///
+    /// Return error set in `error`.
+    ///
+    fn check_error(&self) -> std::result::Result<(), HelperError> {
+        match *self.error.borrow() {
+            Some(ref err) => Err(err.clone()),
+            None => Ok(()),
+        }
+    }
 }
 
 impl ContainerHelper for TestContainerHelper {
-    fn list_image(&self, image_name: &str) -> Vec<String> {
-        self.images
+    fn list_image(&self, image_name: &str) -> std::result::Result<Vec<String>, HelperError> {
+        self.check_error()?;
+
+        Ok(self
+            .images
             .borrow()
             .iter()
             .filter(|i| *i == image_name)
             .map(|i| i.to_string())
-            .collect()
+            .collect())
+    }
+
+    fn inspect_image(
+        &self,
+        image_name: &str,
+    ) -> std::result::Result<Option<ImageInfo>, HelperError> {
+        self.check_error()?;
+
+        if !self.images.borrow().iter().any(|i| i == image_name) {
+            return Ok(None);
+        }
+
+        let labels = match self.labels.borrow().get(image_name) {
+            Some(l) => l.clone(),
+            None => HashMap::new(),
+        };
+
+        let id = match self.image_ids.borrow().get(image_name) {
+            Some(i) => i.clone(),
+            None => format!("sha256:{}", image_name),
+        };
+
+        let repo_digests = match self.repo_digests.borrow().get(image_name) {
+            Some(d) => d.clone(),
+            None => Vec::new(),
+        };
+
+        // Tags are all images with same id
+        let mut tags: Vec<String> = self
+            .images
+            .borrow()
+            .iter()
+            .filter(|i| *i == image_name || self.image_ids.borrow().get(i.as_str()) == Some(&id))
+            .cloned()
+            .collect();
+
+        tags.sort();
+        tags.dedup();
+
+        Ok(Some(ImageInfo {
+            id: id,
+            labels: labels,
+            repo_digests: repo_digests,
+            tags: tags,
+            created: self.created.borrow().get(image_name).cloned(),
+            size: self.sizes.borrow().get(image_name).cloned().unwrap_or(0),
+        }))
     }
 
-    fn remove_image(&self, image_name: &str) -> bool {
+    fn remove_image(&self, image_name: &str) -> std::result::Result<(), HelperError> {
+        self.check_error()?;
+
         let nb_image = self
             .images
             .borrow()
@@ -89,9 +181,12 @@ impl ContainerHelper for TestContainerHelper {
             // Remove item
             self.images.borrow_mut().retain(|i| *i != image_name);
 
-            true
+            Ok(())
         } else {
-            false
+            Err(HelperError::NotFound(format!(
+                "No such image: {}",
+                image_name
+            )))
         }
     }
 
@@ -101,7 +196,9 @@ impl ContainerHelper for TestContainerHelper {
         run_options: Option<&Vec<String>>,
         cmd: Option<&str>,
         cmd_options: Option<&Vec<String>>,
-    ) -> bool {
+    ) -> std::result::Result<(), HelperError> {
+        self.check_error()?;
+
         let nb_image = self
             .images
             .borrow()
@@ -134,21 +231,53 @@ impl ContainerHelper for TestContainerHelper {
 
             self.containers.borrow_mut().push(new_running_container);
 
-            true
+            Ok(())
         } else {
-            false
+            Err(HelperError::NotFound(format!(
+                "No such image: {}",
+                image_name
+            )))
         }
     }
 
+    fn run_container_output(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> std::result::Result<String, HelperError> {
+        self.run_container(image_name, run_options, cmd, cmd_options)?;
+
+        Ok(self
+            .run_outputs
+            .borrow()
+            .get(image_name)
+            .cloned()
+            .unwrap_or_default())
+    }
+
     fn build_image(
         &self,
         docker_filename: &str,
         docker_context_path: &str,
         docker_tag: &str,
         build_options: Option<&Vec<String>>,
-    ) -> bool {
+        output: &mut FnMut(&str),
+    ) -> std::result::Result<(), HelperError> {
+        self.check_error()?;
+
+        if let Some(lines) = self.builds_output.borrow().get(docker_tag) {
+            for line in lines {
+                output(line);
+            }
+        }
+
         if self.builds_error.borrow().contains_key(docker_tag) {
-            return false;
+            return Err(HelperError::Failed {
+                code: Some(1),
+                message: String::from("The command returned a non-zero code: 1"),
+            });
         }
 
         self.images.borrow_mut().push(String::from(docker_tag));
@@ -158,6 +287,32 @@ impl ContainerHelper for TestContainerHelper {
             None => Vec::new(),
         };
 
+        // Keep label like docker
+        let mut labels = HashMap::new();
+
+        for (index, opt) in b_opts.iter().enumerate() {
+            if opt == "--label" {
+                if let Some(label) = b_opts.get(index + 1) {
+                    let mut kv = label.splitn(2, '=');
+                    let key = kv.next().unwrap_or("");
+                    let value = kv.next().unwrap_or("");
+
+                    labels.insert(String::from(key), String::from(value));
+                }
+            }
+        }
+
+        self.labels
+            .borrow_mut()
+            .insert(String::from(docker_tag), labels);
+
+        // Each build create a new image id
+        let id = format!("sha256:{}-{}", docker_tag, self.builds.borrow().len());
+
+        self.image_ids
+            .borrow_mut()
+            .insert(String::from(docker_tag), id);
+
         let build = TestBuildImage {
             build_options: b_opts,
             tag: String::from(docker_tag),
@@ -167,7 +322,209 @@ impl ContainerHelper for TestContainerHelper {
 
         self.builds.borrow_mut().push(build);
 
-        true
+        Ok(())
+    }
+
+    fn save_images(
+        &self,
+        image_names: &[String],
+        output_filename: &str,
+    ) -> std::result::Result<(), HelperError> {
+        self.check_error()?;
+
+        let images = self.images.borrow();
+
+        if let Some(i) = image_names.iter().find(|i| !images.contains(i)) {
+            return Err(HelperError::NotFound(format!("No such image: {}", i)));
+        }
+
+        self.saves
+            .borrow_mut()
+            .insert(String::from(output_filename), image_names.to_vec());
+
+        Ok(())
+    }
+
+    fn load_images(&self, input_filename: &str) -> std::result::Result<(), HelperError> {
+        self.check_error()?;
+
+        if self.loads_error.borrow().contains_key(input_filename) {
+            return Err(HelperError::failed("invalid tar header"));
+        }
+
+        self.loads.borrow_mut().push(String::from(input_filename));
+
+        Ok(())
+    }
+
+    fn tag_image(&self, source: &str, target: &str) -> std::result::Result<(), HelperError> {
+        self.check_error()?;
+
+        if !self.images.borrow().iter().any(|i| i == source) {
+            return Err(HelperError::NotFound(format!("No such image: {}", source)));
+        }
+
+        self.images.borrow_mut().push(String::from(target));
+
+        let labels = self.labels.borrow().get(source).cloned();
+
+        if let Some(l) = labels {
+            self.labels.borrow_mut().insert(String::from(target), l);
+        }
+
+        let id = self.image_ids.borrow().get(source).cloned();
+
+        if let Some(i) = id {
+            self.image_ids.borrow_mut().insert(String::from(target), i);
+        }
+
+        Ok(())
+    }
+
+    fn push_image(&self, image_name: &str) -> std::result::Result<(), HelperError> {
+        self.check_error()?;
+
+        if !self.images.borrow().iter().any(|i| i == image_name) {
+            return Err(HelperError::NotFound(format!(
+                "An image does not exist locally with the tag: {}",
+                image_name
+            )));
+        }
+
+        self.registry_images
+            .borrow_mut()
+            .push(String::from(image_name));
+
+        Ok(())
+    }
+
+    fn pull_image(&self, image_name: &str) -> std::result::Result<(), HelperError> {
+        self.check_error()?;
+
+        if !self
+            .registry_images
+            .borrow()
+            .iter()
+            .any(|i| i == image_name)
+        {
+            return Err(HelperError::NotFound(format!(
+                "manifest for {} not found",
+                image_name
+            )));
+        }
+
+        self.images.borrow_mut().push(String::from(image_name));
+
+        Ok(())
+    }
+
+    fn with_endpoint<'a>(&'a self, endpoint: &DockerEndpoint) -> Box<ContainerHelper + 'a> {
+        self.endpoints.borrow_mut().push(endpoint.clone());
+
+        // Same images and containers on all Docker daemons
+        Box::new(self)
+    }
+
+    fn forward_display(
+        &self,
+        _display: &str,
+    ) -> std::result::Result<Option<RemoteDisplay>, HelperError> {
+        self.check_error()?;
+
+        Ok(self
+            .remote_display
+            .borrow()
+            .as_ref()
+            .map(|d| RemoteDisplay::new(d, None)))
+    }
+}
+
+impl ContainerHelper for &TestContainerHelper {
+    fn list_image(&self, image_name: &str) -> std::result::Result<Vec<String>, HelperError> {
+        (**self).list_image(image_name)
+    }
+
+    fn inspect_image(
+        &self,
+        image_name: &str,
+    ) -> std::result::Result<Option<ImageInfo>, HelperError> {
+        (**self).inspect_image(image_name)
+    }
+
+    fn remove_image(&self, image_name: &str) -> std::result::Result<(), HelperError> {
+        (**self).remove_image(image_name)
+    }
+
+    fn run_container(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> std::result::Result<(), HelperError> {
+        (**self).run_container(image_name, run_options, cmd, cmd_options)
+    }
+
+    fn run_container_output(
+        &self,
+        image_name: &str,
+        run_options: Option<&Vec<String>>,
+        cmd: Option<&str>,
+        cmd_options: Option<&Vec<String>>,
+    ) -> std::result::Result<String, HelperError> {
+        (**self).run_container_output(image_name, run_options, cmd, cmd_options)
+    }
+
+    fn build_image(
+        &self,
+        docker_filename: &str,
+        docker_context_path: &str,
+        docker_tag: &str,
+        build_options: Option<&Vec<String>>,
+        output: &mut FnMut(&str),
+    ) -> std::result::Result<(), HelperError> {
+        (**self).build_image(
+            docker_filename,
+            docker_context_path,
+            docker_tag,
+            build_options,
+            output,
+        )
+    }
+
+    fn save_images(
+        &self,
+        image_names: &[String],
+        output_filename: &str,
+    ) -> std::result::Result<(), HelperError> {
+        (**self).save_images(image_names, output_filename)
+    }
+
+    fn load_images(&self, input_filename: &str) -> std::result::Result<(), HelperError> {
+        (**self).load_images(input_filename)
+    }
+
+    fn tag_image(&self, source: &str, target: &str) -> std::result::Result<(), HelperError> {
+        (**self).tag_image(source, target)
+    }
+
+    fn push_image(&self, image_name: &str) -> std::result::Result<(), HelperError> {
+        (**self).push_image(image_name)
+    }
+
+    fn pull_image(&self, image_name: &str) -> std::result::Result<(), HelperError> {
+        (**self).pull_image(image_name)
+    }
+
+    fn with_endpoint<'a>(&'a self, endpoint: &DockerEndpoint) -> Box<ContainerHelper + 'a> {
+        (**self).with_endpoint(endpoint)
+    }
+
+    fn forward_display(
+        &self,
+        display: &str,
+    ) -> std::result::Result<Option<RemoteDisplay>, HelperError> {
+        (**self).forward_display(display)
     }
 }
 
@@ -178,6 +535,100 @@ impl TestContainerHelper {
             containers: RefCell::new(Vec::new()),
             builds: RefCell::new(Vec::new()),
             builds_error: RefCell::new(HashMap::new()),
+            labels: RefCell::new(HashMap::new()),
+            image_ids: RefCell::new(HashMap::new()),
+            builds_output: RefCell::new(HashMap::new()),
+            run_outputs: RefCell::new(HashMap::new()),
+            saves: RefCell::new(HashMap::new()),
+            loads: RefCell::new(Vec::new()),
+            loads_error: RefCell::new(HashMap::new()),
+            registry_images: RefCell::new(Vec::new()),
+            repo_digests: RefCell::new(HashMap::new()),
+            error: RefCell::new(None),
+            sizes: RefCell::new(HashMap::new()),
+            created: RefCell::new(HashMap::new()),
+            endpoints: RefCell::new(Vec::new()),
+            remote_display: RefCell::new(None),
         }
     }
 }
+
+#[test]
+fn parse_date_of_inspect() {
+    assert_eq!(parse_date("1970-01-01T00:00:00Z"), Some(0));
+    assert_eq!(
+        parse_date("2019-01-31T10:20:30.123456789Z"),
+        Some(1_548_930_030)
+    );
+    assert_eq!(parse_date("2019-01-31T11:20:30+01:00"), Some(1_548_930_030));
+    assert_eq!(parse_date("2019-01-31"), None);
+    assert_eq!(parse_date("yesterday"), None);
+}
+
+#[test]
+fn engine_error_from_daemon_line() {
+    assert_eq!(
+        get_engine_error(
+            Some(1),
+            "Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?\n"
+        ),
+        HelperError::DaemonUnreachable(String::from(
+            "Cannot connect to the Docker daemon at unix:///var/run/docker.sock. Is the docker daemon running?"
+        ))
+    );
+    assert_eq!(
+        get_engine_error(
+            Some(1),
+            "Error response from daemon: No such image: run-atom:latest\n"
+        ),
+        HelperError::NotFound(String::from(
+            "Error response from daemon: No such image: run-atom:latest"
+        ))
+    );
+    assert_eq!(
+        get_engine_error(Some(125), "unknown flag: --foo\n"),
+        HelperError::Failed {
+            code: Some(125),
+            message: String::from("unknown flag: --foo"),
+        }
+    );
+}
+
+#[test]
+fn build_error_without_daemon_error() {
+    // Output of commands of Dockerfile is not an error of daemon
+    let stderr = vec![
+        String::from("/bin/sh: 1: curl: not found"),
+        String::from("dpkg: error: permission denied"),
+        String::from("The command '/bin/sh -c curl' returned a non-zero code: 127"),
+    ];
+
+    assert_eq!(
+        get_build_error(Some(1), &stderr),
+        HelperError::Failed {
+            code: Some(1),
+            message: String::from("The command '/bin/sh -c curl' returned a non-zero code: 127"),
+        }
+    );
+
+    let stderr = vec![
+        String::from("Sending build context to Docker daemon  2.048kB"),
+        String::from("Got permission denied while trying to connect to the Docker daemon socket"),
+    ];
+
+    assert_eq!(
+        get_build_error(Some(1), &stderr),
+        HelperError::PermissionDenied(String::from(
+            "Got permission denied while trying to connect to the Docker daemon socket"
+        ))
+    );
+}
+
+#[test]
+fn parse_list_of_images() {
+    assert_eq!(
+        parse_image_list("4bb46517cac3\n0d6b2b0c7b3f\n\n"),
+        vec![String::from("4bb46517cac3"), String::from("0d6b2b0c7b3f")]
+    );
+    assert!(parse_image_list("").is_empty());
+}
@@ -3,39 +3,112 @@
 ///
 /// Release under MIT License.
 ///
-use std::process::Command;
+use helper::HelperError;
+use std::process::{Command, ExitStatus};
 
 #[cfg(test)]
 pub mod tests;
 
 pub trait DownloadHelper {
     /// Download a file.
-    fn download(&self, url: &str, output_filename: &str) -> bool;
+    fn download(&self, url: &str, output_filename: &str) -> Result<(), HelperError>;
     /// Download file if updated. Check date of file.
-    fn download_if_update(&self, url: &str, output_filename: &str) -> bool;
+    fn download_if_update(&self, url: &str, output_filename: &str) -> Result<(), HelperError>;
+    /// Return version of package file (like `.deb`).
+    fn package_version(&self, filename: &str) -> Option<String>;
+    /// Return content of url.
+    /// Return None if url cannot be read.
+    fn get_content(&self, url: &str) -> Option<String>;
+}
+
+///
+/// Return error of curl from its exit code.
+///
+/// Curl display its error message, so only exit code is used.
+///
+fn get_curl_error(url: &str, status: &ExitStatus) -> HelperError {
+    match status.code() {
+        // 5: proxy not resolved, 6: host not resolved, 7: cannot connect, 28: timeout,
+        // 35: SSL error, 52: empty reply, 56: failure in receiving data
+        Some(5) | Some(6) | Some(7) | Some(28) | Some(35) | Some(52) | Some(56) => {
+            HelperError::Network(format!("Unable to download '{}'", url))
+        }
+        // HTTP error like 404 (with `-f` option)
+        Some(22) => HelperError::NotFound(format!("Server return error for '{}'", url)),
+        // Cannot write output file
+        Some(23) => HelperError::PermissionDenied(format!("Unable to write file of '{}'", url)),
+        code => HelperError::Failed {
+            code: code,
+            message: format!("Unable to download '{}'", url),
+        },
+    }
+}
+
+///
+/// Run curl with arguments.
+///
+fn run_curl(url: &str, args: &[&str]) -> Result<(), HelperError> {
+    match Command::new("curl").args(args).status() {
+        Ok(ref status) if status.success() => Ok(()),
+        Ok(status) => Err(get_curl_error(url, &status)),
+        Err(err) => Err(HelperError::failed(&format!("Unable to run curl: {}", err))),
+    }
 }
 
 /// Default run process
 pub struct DefaultDownloadHelper;
 
 impl DownloadHelper for DefaultDownloadHelper {
-    fn download(&self, url: &str, output_filename: &str) -> bool {
-        match Command::new("curl")
-            .args(&["-o", output_filename, "-L", url])
-            .status()
+    fn download(&self, url: &str, output_filename: &str) -> Result<(), HelperError> {
+        run_curl(url, &["-f", "-o", output_filename, "-L", url])
+    }
+
+    fn download_if_update(&self, url: &str, output_filename: &str) -> Result<(), HelperError> {
+        run_curl(
+            url,
+            &[
+                "-f",
+                "-o",
+                output_filename,
+                "-z",
+                output_filename,
+                "-L",
+                url,
+            ],
+        )
+    }
+
+    fn package_version(&self, filename: &str) -> Option<String> {
+        match Command::new("dpkg-deb")
+            .args(&["-f", filename, "Version"])
+            .output()
         {
-            Ok(_) => true,
-            Err(_) => false,
+            Ok(output) => {
+                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+                if output.status.success() && !version.is_empty() {
+                    Some(version)
+                } else {
+                    None
+                }
+            }
+            Err(_) => None,
         }
     }
 
-    fn download_if_update(&self, url: &str, output_filename: &str) -> bool {
+    fn get_content(&self, url: &str) -> Option<String> {
         match Command::new("curl")
-            .args(&["-o", output_filename, "-z", output_filename, "-L", url])
-            .status()
+            .args(&["-s", "-S", "-f", "-L", url])
+            .output()
         {
-            Ok(_) => true,
-            Err(_) => false,
+            Ok(output) => {
+                if output.status.success() {
+                    Some(String::from_utf8_lossy(&output.stdout).to_string())
+                } else {
+                    None
+                }
+            }
+            Err(_) => None,
         }
     }
 }
@@ -3,11 +3,16 @@
 ///
 /// Release under MIT License.
 ///
-use super::DownloadHelper;
+use super::{DefaultDownloadHelper, DownloadHelper};
+use helper::HelperError;
 use io::tests::TestInputOutputHelper;
 use io::InputOutputHelper;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 /// When run a container
 pub struct TestDownload {
@@ -21,12 +26,18 @@ pub struct TestDownloadHelper<'a> {
     pub dl: RefCell<Vec<TestDownload>>,
     pub update_dl_files: RefCell<HashMap<String, bool>>,
     pub urls_error: RefCell<HashMap<String, bool>>,
+    pub package_versions: RefCell<HashMap<String, String>>,
+    /// Content of url
+    pub contents: RefCell<HashMap<String, String>>,
 }
 
 impl<'a> DownloadHelper for TestDownloadHelper<'a> {
-    fn download(&self, url: &str, output_filename: &str) -> bool {
+    fn download(&self, url: &str, output_filename: &str) -> Result<(), HelperError> {
         if self.urls_error.borrow().contains_key(url) {
-            return false;
+            return Err(HelperError::Network(format!(
+                "Unable to download '{}'",
+                url
+            )));
         }
 
         let c = TestDownload {
@@ -38,12 +49,12 @@ impl<'a> DownloadHelper for TestDownloadHelper<'a> {
         self.dl.borrow_mut().push(c);
 
         match self.io_helper.file_write(output_filename, url) {
-            Ok(_) => true,
-            Err(_) => false,
+            Ok(_) => Ok(()),
+            Err(err) => Err(HelperError::failed(&format!("{}", err))),
         }
     }
 
-    fn download_if_update(&self, url: &str, output_filename: &str) -> bool {
+    fn download_if_update(&self, url: &str, output_filename: &str) -> Result<(), HelperError> {
         if !self.update_dl_files.borrow().contains_key(output_filename)
             && self.io_helper.files.borrow().contains_key(output_filename)
         {
@@ -55,11 +66,19 @@ impl<'a> DownloadHelper for TestDownloadHelper<'a> {
 
             self.dl.borrow_mut().push(c);
 
-            return true;
+            return Ok(());
         }
 
         self.download(url, output_filename)
     }
+
+    fn package_version(&self, filename: &str) -> Option<String> {
+        self.package_versions.borrow().get(filename).cloned()
+    }
+
+    fn get_content(&self, url: &str) -> Option<String> {
+        self.contents.borrow().get(url).cloned()
+    }
 }
 
 impl<'a> TestDownloadHelper<'a> {
@@ -69,6 +88,108 @@ impl<'a> TestDownloadHelper<'a> {
             dl: RefCell::new(Vec::new()),
             update_dl_files: RefCell::new(HashMap::new()),
             urls_error: RefCell::new(HashMap::new()),
+            package_versions: RefCell::new(HashMap::new()),
+            contents: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+/// Fake web server on local tcp port
+pub struct TestHttpServer {
+    pub url: String,
+    /// Request line received, like `GET /releases HTTP/1.1`
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestHttpServer {
+    ///
+    /// Start server, each connection get next response.
+    ///
+    pub fn start(responses: Vec<(&str, &str)>) -> TestHttpServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let server_requests = requests.clone();
+        let responses: Vec<Vec<u8>> = responses
+            .iter()
+            .map(|&(status, body)| http_response(status, body))
+            .collect();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+
+                reader.read_line(&mut request_line).unwrap();
+
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                }
+
+                server_requests
+                    .lock()
+                    .unwrap()
+                    .push(String::from(request_line.trim_end()));
+
+                stream.write_all(&response).unwrap();
+            }
+        });
+
+        TestHttpServer {
+            url: url,
+            requests: requests,
         }
     }
+
+    pub fn request_lines(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn http_response(status: &str, body: &str) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+#[test]
+fn get_content_from_local_server() {
+    let server = TestHttpServer::start(vec![("200 OK", "<a href=\"app_1.2.3.deb\">app</a>")]);
+
+    assert_eq!(
+        DefaultDownloadHelper.get_content(&format!("{}/download", server.url)),
+        Some(String::from("<a href=\"app_1.2.3.deb\">app</a>"))
+    );
+    assert_eq!(server.request_lines(), vec!["GET /download HTTP/1.1"]);
+}
+
+#[test]
+fn get_content_return_none_if_page_not_found() {
+    let server = TestHttpServer::start(vec![("404 Not Found", "Not Found")]);
+
+    assert_eq!(
+        DefaultDownloadHelper.get_content(&format!("{}/missing", server.url)),
+        None
+    );
+    assert_eq!(server.request_lines(), vec!["GET /missing HTTP/1.1"]);
+}
+
+#[test]
+fn get_content_return_none_if_server_is_down() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/releases", listener.local_addr().unwrap());
+
+    drop(listener);
+
+    assert_eq!(DefaultDownloadHelper.get_content(&url), None);
 }
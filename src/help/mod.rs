@@ -16,11 +16,14 @@ mod tests;
 ///
 pub fn help(commands: &[Command], io_helper: &InputOutputHelper) {
     io_helper.println(&format!(""));
-    io_helper.println(&format!("Usage: d-sh COMMAND"));
+    io_helper.println(&format!("Usage: d-sh [--config-dir DIR] COMMAND"));
     io_helper.println(&format!(""));
     io_helper.println(&format!("A tool to container all your life"));
     io_helper.println(&format!(""));
     io_helper.println(&format!("Options:"));
+    io_helper.println(&format!(
+        "  --config-dir DIR         Use DIR as config folder instead of ~/.d-sh"
+    ));
     io_helper.println(&format!(
         "  -h, --help               Print this current help"
     ));
@@ -36,7 +39,7 @@ pub fn help(commands: &[Command], io_helper: &InputOutputHelper) {
             "  {:<width$}{}",
             command,
             cmd.description,
-            width = 13
+            width = 17
         ));
     }
 }
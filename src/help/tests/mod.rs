@@ -44,10 +44,10 @@ fn display_help() {
 
     let stdout = io_helper.stdout.borrow();
 
-    assert_eq!(stdout.len(), 11);
+    assert_eq!(stdout.len(), 12);
 
-    match stdout.get(10) {
-        Some(s) => assert_eq!(s, "  test (tst)   It's a test"),
+    match stdout.get(11) {
+        Some(s) => assert_eq!(s, "  test (tst)       It's a test"),
         None => panic!("Help is not valid"),
     }
 }
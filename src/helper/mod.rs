@@ -0,0 +1,63 @@
+///
+/// Module with error of helpers (container engine, download).
+///
+/// Release under MIT License.
+///
+use std::fmt;
+
+#[cfg(test)]
+mod tests;
+
+///
+/// Error returned by helpers, to know real cause of failure.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub enum HelperError {
+    /// Daemon of container engine is not running or cannot be reached.
+    DaemonUnreachable(String),
+    /// Permission denied, like user not in `docker` group.
+    PermissionDenied(String),
+    /// Image, container or url not found.
+    NotFound(String),
+    /// Command exit with non-zero code (with its stderr), or request refused by daemon.
+    Failed { code: Option<i32>, message: String },
+    /// Network error, like host not found.
+    Network(String),
+}
+
+impl HelperError {
+    ///
+    /// Return error without exit code.
+    ///
+    pub fn failed(message: &str) -> HelperError {
+        HelperError::Failed {
+            code: None,
+            message: String::from(message),
+        }
+    }
+}
+
+impl fmt::Display for HelperError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HelperError::DaemonUnreachable(msg) => write!(f, "Daemon unreachable: {}", msg),
+            HelperError::PermissionDenied(msg) => write!(f, "Permission denied: {}", msg),
+            HelperError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            HelperError::Failed {
+                code: Some(c),
+                message,
+            } => {
+                if message.is_empty() {
+                    write!(f, "Exit with code {}", c)
+                } else {
+                    write!(f, "{} (exit code {})", message, c)
+                }
+            }
+            HelperError::Failed {
+                code: None,
+                message,
+            } => write!(f, "{}", message),
+            HelperError::Network(msg) => write!(f, "Network error: {}", msg),
+        }
+    }
+}
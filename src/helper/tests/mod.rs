@@ -0,0 +1,41 @@
+///
+/// Module to tests module helper.
+///
+/// Release under MIT License.
+///
+use super::HelperError;
+
+#[test]
+fn display_helper_error() {
+    assert_eq!(
+        format!(
+            "{}",
+            HelperError::DaemonUnreachable(String::from("docker.sock not found"))
+        ),
+        "Daemon unreachable: docker.sock not found"
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            HelperError::Failed {
+                code: Some(125),
+                message: String::from("unknown flag: --foo"),
+            }
+        ),
+        "unknown flag: --foo (exit code 125)"
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            HelperError::Failed {
+                code: Some(1),
+                message: String::new(),
+            }
+        ),
+        "Exit with code 1"
+    );
+    assert_eq!(
+        format!("{}", HelperError::failed("invalid tar header")),
+        "invalid tar header"
+    );
+}
@@ -1,5 +1,7 @@
 use dirs::home_dir;
 use glob::glob;
+use sha2::{Digest, Sha256};
+use std::env;
 use std::fs::{copy, create_dir_all, hard_link, remove_dir_all, write, File};
 ///
 /// Module to print output.
@@ -8,10 +10,15 @@ use std::fs::{copy, create_dir_all, hard_link, remove_dir_all, write, File};
 ///
 use std::io::{stdin, stdout, Error, ErrorKind, Read, Write};
 use std::path::Path;
+use std::process::Command;
+use tempfile::Builder;
 
 #[cfg(test)]
 pub mod tests;
 
+/// Prefix of temporary folder.
+pub const TMP_DIR_PREFIX: &str = "d-sh-";
+
 /// Convert path with start "~/"
 pub fn convert_path(x: &str) -> String {
     if x.starts_with("~/") {
@@ -47,6 +54,32 @@ pub trait InputOutputHelper {
     fn remove_dir_all(&self, dir: &str) -> Result<(), Error>;
     /// Create an hardlink or copy file if not possible
     fn hardlink_or_copy_file(&self, from: &str, to: &str) -> Result<(), Error>;
+    /// Return sha256 of file in hexadecimal
+    fn file_sha256(&self, filename: &str) -> Result<String, Error>;
+    /// Create a new folder with unique name in `parent` folder, only readable by current user
+    fn create_tmp_dir(&self, parent: &str) -> Result<String, Error>;
+    /// Create tar archive `archive` with content of folder `dir`
+    fn archive_create(&self, archive: &str, dir: &str) -> Result<(), Error>;
+    /// Extract tar archive `archive` in folder `dir`
+    fn archive_extract(&self, archive: &str, dir: &str) -> Result<(), Error>;
+    /// Open file in editor of user (`$VISUAL`, `$EDITOR` or vi) and wait end of edition
+    fn edit_file(&self, filename: &str) -> Result<(), Error>;
+}
+
+///
+/// Run tar command.
+///
+fn run_tar(args: &[&str]) -> Result<(), Error> {
+    let status = Command::new("tar").args(args).status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::Other,
+            format!("tar command failed ({})", status),
+        ))
+    }
 }
 
 /// Default print on tty.
@@ -142,4 +175,59 @@ impl InputOutputHelper for DefaultInputOutputHelper {
             },
         }
     }
+
+    fn file_sha256(&self, filename: &str) -> Result<String, Error> {
+        let mut file = File::open(filename)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = [0; 8192];
+
+        loop {
+            let size = file.read(&mut buffer)?;
+
+            if size == 0 {
+                break;
+            }
+
+            hasher.input(&buffer[..size]);
+        }
+
+        Ok(format!("{:x}", hasher.result()))
+    }
+
+    fn create_tmp_dir(&self, parent: &str) -> Result<String, Error> {
+        create_dir_all(parent)?;
+
+        let tmp_dir = Builder::new().prefix(TMP_DIR_PREFIX).tempdir_in(parent)?;
+
+        // Folder is removed by command, not when `tmp_dir` is dropped
+        Ok(tmp_dir.keep().display().to_string())
+    }
+
+    fn archive_create(&self, archive: &str, dir: &str) -> Result<(), Error> {
+        run_tar(&["-c", "-f", archive, "-C", dir, "."])
+    }
+
+    fn archive_extract(&self, archive: &str, dir: &str) -> Result<(), Error> {
+        run_tar(&["-x", "-f", archive, "-C", dir])
+    }
+
+    fn edit_file(&self, filename: &str) -> Result<(), Error> {
+        let editor = env::var("VISUAL")
+            .or_else(|_| env::var("EDITOR"))
+            .unwrap_or(String::from("vi"));
+        // Editor can have arguments, like `code --wait`
+        let mut args = editor.split_whitespace();
+        let program = args.next().unwrap_or("vi");
+
+        let status = Command::new(program).args(args).arg(filename).status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::Other,
+                format!("Editor '{}' failed ({})", editor, status),
+            ))
+        }
+    }
 }
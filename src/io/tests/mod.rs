@@ -3,8 +3,9 @@
 ///
 /// Release under MIT License.
 ///
-use io::InputOutputHelper;
+use io::{InputOutputHelper, TMP_DIR_PREFIX};
 use regex::Regex;
+use sha2::{Digest, Sha256};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io::{Error, ErrorKind};
@@ -27,6 +28,12 @@ pub struct TestInputOutputHelper {
     pub files: RefCell<HashMap<String, String>>,
     pub files_error: RefCell<HashMap<String, bool>>,
     pub files_delete: RefCell<HashMap<String, String>>,
+    /// Temporary folders created
+    pub tmp_dirs: RefCell<Vec<String>>,
+    /// Content of archives created, relative filename to content
+    pub archives: RefCell<HashMap<String, HashMap<String, String>>>,
+    /// Content written by editor, one by edition
+    pub edits: RefCell<Vec<String>>,
 }
 
 impl InputOutputHelper for TestInputOutputHelper {
@@ -134,6 +141,77 @@ impl InputOutputHelper for TestInputOutputHelper {
             Err(err) => Err(err),
         }
     }
+
+    fn file_sha256(&self, filename: &str) -> Result<String, Error> {
+        let content = self.file_read_at_string(filename)?;
+
+        Ok(format!("{:x}", Sha256::digest(content.as_bytes())))
+    }
+
+    fn create_tmp_dir(&self, parent: &str) -> Result<String, Error> {
+        if self.files_error.borrow().contains_key(parent) {
+            return Err(Error::new(ErrorKind::PermissionDenied, "Cannot write"));
+        }
+
+        let tmp_dir = format!(
+            "{}/{}{}",
+            parent.trim_end_matches('/'),
+            TMP_DIR_PREFIX,
+            self.tmp_dirs.borrow().len()
+        );
+
+        self.tmp_dirs.borrow_mut().push(tmp_dir.clone());
+
+        Ok(tmp_dir)
+    }
+
+    fn archive_create(&self, archive: &str, dir: &str) -> Result<(), Error> {
+        if self.files_error.borrow().contains_key(archive) {
+            return Err(Error::new(ErrorKind::PermissionDenied, "Cannot write"));
+        }
+
+        let prefix = format!("{}/", dir.trim_end_matches('/'));
+
+        let content: HashMap<String, String> = self
+            .files
+            .borrow()
+            .iter()
+            .filter(|(k, _)| k.starts_with(&prefix))
+            .map(|(k, v)| (String::from(&k[prefix.len()..]), v.to_string()))
+            .collect();
+
+        self.archives
+            .borrow_mut()
+            .insert(String::from(archive), content);
+        self.files
+            .borrow_mut()
+            .insert(String::from(archive), String::new());
+
+        Ok(())
+    }
+
+    fn archive_extract(&self, archive: &str, dir: &str) -> Result<(), Error> {
+        let content = match self.archives.borrow().get(archive) {
+            Some(c) => c.clone(),
+            None => return Err(Error::new(ErrorKind::NotFound, "Not found")),
+        };
+
+        for (filename, data) in content {
+            self.file_write(&format!("{}/{}", dir, filename), &data)?;
+        }
+
+        Ok(())
+    }
+
+    fn edit_file(&self, filename: &str) -> Result<(), Error> {
+        if self.edits.borrow().is_empty() {
+            return Err(Error::new(ErrorKind::Other, "Editor failed"));
+        }
+
+        let data = self.edits.borrow_mut().remove(0);
+
+        self.file_write(filename, &data)
+    }
 }
 
 impl TestInputOutputHelper {
@@ -145,6 +223,9 @@ impl TestInputOutputHelper {
             files: RefCell::new(HashMap::new()),
             files_error: RefCell::new(HashMap::new()),
             files_delete: RefCell::new(HashMap::new()),
+            tmp_dirs: RefCell::new(Vec::new()),
+            archives: RefCell::new(HashMap::new()),
+            edits: RefCell::new(Vec::new()),
         }
     }
 }
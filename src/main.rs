@@ -7,32 +7,50 @@ extern crate serde_derive;
 #[macro_use]
 extern crate serde_json;
 
-#[cfg(test)]
-extern crate regex;
-
+extern crate ctrlc;
 extern crate dirs;
 extern crate glob;
 extern crate handlebars;
-extern crate rand;
+extern crate regex;
+extern crate sha2;
+extern crate tempfile;
 extern crate users;
 
+mod cleanup;
 mod command;
 mod config;
 mod docker;
 mod download;
 mod help;
+mod helper;
 mod io;
+mod registry;
 mod template;
+mod update;
+mod version;
 
+use cleanup::install_interrupt_handler;
 use command::build::BUILD;
 use command::check::CHECK;
+use command::config::CONFIG;
 use command::delete::DELETE;
+use command::export::EXPORT;
+use command::import::IMPORT;
 use command::init::INIT;
 use command::list::LIST;
+use command::logs::LOGS;
+use command::migrate::MIGRATE;
+use command::outdated::OUTDATED;
+use command::pull::PULL;
+use command::push::PUSH;
 use command::run::RUN;
+use command::validate::VALIDATE;
 use command::Command;
 use command::CommandExitCode;
-use docker::DefaultContainerHelper;
+use config::get_config;
+use config::get_config_dir;
+use docker::get_container_helper;
+use docker::remote::get_config_endpoint;
 use download::DefaultDownloadHelper;
 use help::help;
 use help::version;
@@ -40,21 +58,83 @@ use io::DefaultInputOutputHelper;
 use io::InputOutputHelper;
 use std::env;
 
-const ALL_COMMANDS: &'static [Command] = &[BUILD, CHECK, DELETE, INIT, LIST, RUN];
+const ALL_COMMANDS: &'static [Command] = &[
+    BUILD, CHECK, CONFIG, DELETE, EXPORT, IMPORT, INIT, LIST, LOGS, MIGRATE, OUTDATED, PULL, PUSH,
+    RUN, VALIDATE,
+];
+
+///
+/// Remove global option `--config-dir` placed before command.
+///
+/// Return arguments without option and value of option.
+///
+fn extract_config_dir(args: Vec<String>) -> Result<(Vec<String>, Option<String>), String> {
+    let mut others = Vec::new();
+    let mut config_dir = None;
+    let mut iter = args.into_iter();
+
+    // Program name
+    others.extend(iter.next());
+
+    while let Some(arg) = iter.next() {
+        if arg == "--config-dir" {
+            match iter.next() {
+                Some(dir) => config_dir = Some(dir),
+                None => {
+                    return Err(String::from(
+                        "D-SH: option '--config-dir' requires a folder",
+                    ))
+                }
+            }
+        } else if let Some(dir) = arg.strip_prefix("--config-dir=") {
+            config_dir = Some(String::from(dir));
+        } else {
+            // Command and its arguments
+            others.push(arg);
+            others.extend(iter);
+            break;
+        }
+    }
+
+    Ok((others, config_dir))
+}
 
 ///
 /// Main function of D-SH
 ///
 fn main() {
+    let io_helper = &DefaultInputOutputHelper;
+
     // Get command line options
-    let args: Vec<String> = env::args().collect();
+    let (args, config_dir) = match extract_config_dir(env::args().collect()) {
+        Ok(r) => r,
+        Err(err) => {
+            io_helper.eprintln(&err);
+            std::process::exit(CommandExitCode::BadArgument as i32)
+        }
+    };
+    let config_dir = get_config_dir(config_dir.as_deref());
+    let config_dir = config_dir.as_deref();
     // Default exit code
     let mut exit_code = CommandExitCode::Ok;
 
-    let io_helper = &DefaultInputOutputHelper;
-    let dck_help = &DefaultContainerHelper;
+    // Container engine and Docker daemon are set in config file, config file may not exist yet
+    let config = get_config(io_helper, config_dir);
+    let container_engine = config
+        .as_ref()
+        .ok()
+        .and_then(|c| c.container_engine.as_ref());
+    let endpoint = config
+        .as_ref()
+        .ok()
+        .map(get_config_endpoint)
+        .unwrap_or_default();
+    let dck_box = get_container_helper(container_engine, &endpoint);
+    let dck_help = &*dck_box;
     let run_helper = &DefaultDownloadHelper;
 
+    install_interrupt_handler();
+
     if args.len() == 1 {
         help(ALL_COMMANDS, io_helper);
         exit_code = CommandExitCode::Help;
@@ -75,7 +155,14 @@ fn main() {
                 }
 
                 exit_code = match command_to_run {
-                    Some(c) => c.exec(&args[2..], io_helper, dck_help, run_helper),
+                    Some(c) => c.exec(
+                        &args[2..],
+                        io_helper,
+                        dck_help,
+                        run_helper,
+                        config_dir,
+                        &config,
+                    ),
                     None => {
                         io_helper.eprintln(&format!("D-SH: '{}' is not a d-sh command.", cmd));
                         io_helper.eprintln(&format!("See '{} --help'", args[0]));
@@ -0,0 +1,175 @@
+///
+/// Module to share images with a registry.
+///
+/// Release under MIT License.
+///
+use command::build::hash::{BASE_HASH_LABEL, HASH_LABEL};
+use config::{
+    get_application_filename, get_config_application, Config, ConfigApplication, ConfigRegistry,
+};
+use docker::{get_image_repository, ContainerHelper};
+use io::InputOutputHelper;
+
+#[cfg(test)]
+mod tests;
+
+///
+/// Return registry of config file.
+///
+pub fn get_registry(config: &Config) -> Result<&ConfigRegistry, String> {
+    match config.registry {
+        Some(ref r) => Ok(r),
+        None => Err(String::from(
+            "No registry in config file! Please add 'registry' section.",
+        )),
+    }
+}
+
+///
+/// Return config of application pushed or pulled.
+///
+pub fn get_application(
+    io_helper: &InputOutputHelper,
+    config: &Config,
+    app: &str,
+) -> Result<ConfigApplication, String> {
+    let application_filename = get_application_filename(io_helper, config, app);
+
+    match get_config_application(io_helper, &application_filename) {
+        Ok(r) => Ok(r),
+        Err(err) => Err(format!("{}", err)),
+    }
+}
+
+///
+/// Return name of image in registry.
+///
+/// `run-atom:latest` is `registry.example.com/team/run-atom:latest`.
+///
+pub fn get_registry_image_name(registry: &ConfigRegistry, image_name: &str) -> String {
+    format!("{}/{}", registry.url.trim_end_matches('/'), image_name)
+}
+
+///
+/// Return name of image in registry tagged with hash of application.
+///
+/// `run-atom:latest` is `registry.example.com/team/run-atom:<hash>`.
+///
+pub fn get_registry_cache_image_name(
+    registry: &ConfigRegistry,
+    image_name: &str,
+    hash: &str,
+) -> String {
+    get_registry_image_name(
+        registry,
+        &format!("{}:{}", get_image_repository(image_name), hash),
+    )
+}
+
+///
+/// Return true if image are pulled from registry before build.
+///
+pub fn is_registry_cache_enabled(registry: &ConfigRegistry) -> bool {
+    registry.cache.unwrap_or(true)
+}
+
+///
+/// Push image in registry with its name and with its hash (to be used as cache).
+///
+pub fn push_image(
+    dck_helper: &ContainerHelper,
+    registry: &ConfigRegistry,
+    image_name: &str,
+) -> Result<(), String> {
+    let image = match dck_helper.inspect_image(image_name) {
+        Ok(Some(r)) => r,
+        Ok(None) => return Err(format!("Image '{}' not found!", image_name)),
+        Err(err) => return Err(format!("{}", err)),
+    };
+
+    let mut remote_images = vec![get_registry_image_name(registry, image_name)];
+
+    if let Some(hash) = image.labels.get(HASH_LABEL) {
+        remote_images.push(get_registry_cache_image_name(registry, image_name, hash));
+    }
+
+    for remote_image in remote_images {
+        if let Err(err) = dck_helper.tag_image(image_name, &remote_image) {
+            return Err(format!("Unable to tag '{}'! {}", remote_image, err));
+        }
+
+        if let Err(err) = dck_helper.push_image(&remote_image) {
+            return Err(format!("Unable to push '{}'! {}", remote_image, err));
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// Pull image from registry and tag it with local name.
+///
+pub fn pull_image(
+    dck_helper: &ContainerHelper,
+    registry: &ConfigRegistry,
+    image_name: &str,
+) -> Result<(), String> {
+    let remote_image = get_registry_image_name(registry, image_name);
+
+    if let Err(err) = dck_helper.pull_image(&remote_image) {
+        return Err(format!("Unable to pull '{}'! {}", remote_image, err));
+    }
+
+    if let Err(err) = dck_helper.tag_image(&remote_image, image_name) {
+        return Err(format!("Unable to tag '{}'! {}", image_name, err));
+    }
+
+    Ok(())
+}
+
+///
+/// Pull image with same hash from registry, instead of build it.
+///
+/// `base_hash` is hash of base image config, hash of application doesn't contain base image
+/// so image in registry must be build on a base image with same config. Base images are
+/// build by each developer, their id cannot be compared.
+///
+/// returning true if image is pulled.
+///
+pub fn pull_cache_image(
+    dck_helper: &ContainerHelper,
+    registry: &ConfigRegistry,
+    image_name: &str,
+    hash: &str,
+    base_hash: Option<&str>,
+) -> bool {
+    // Without hash of base image, nothing can be checked
+    let base_hash = match base_hash {
+        Some(b) => b,
+        None => return false,
+    };
+
+    let remote_image = get_registry_cache_image_name(registry, image_name, hash);
+
+    if dck_helper.pull_image(&remote_image).is_err() {
+        return false;
+    }
+
+    // Tag is set by D-SH push, but check label to be sure
+    let same_build = match dck_helper.inspect_image(&remote_image) {
+        Ok(Some(image)) => {
+            image.labels.get(HASH_LABEL).map(|h| h.as_str()) == Some(hash)
+                && image.labels.get(BASE_HASH_LABEL).map(|h| h.as_str()) == Some(base_hash)
+        }
+        _ => false,
+    };
+
+    if !same_build {
+        // Don't keep image build by someone else on another base image
+        let _ = dck_helper.remove_image(&remote_image);
+
+        return false;
+    }
+
+    dck_helper.tag_image(&remote_image, image_name).is_ok()
+}
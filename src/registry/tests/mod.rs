@@ -0,0 +1,191 @@
+///
+/// Module to tests module registry.
+///
+/// Release under MIT License.
+///
+use super::{
+    get_registry_cache_image_name, get_registry_image_name, pull_cache_image, pull_image,
+    push_image,
+};
+use config::ConfigRegistry;
+use docker::tests::TestContainerHelper;
+use std::collections::HashMap;
+
+fn create_registry() -> ConfigRegistry {
+    ConfigRegistry {
+        url: String::from("localhost:5000/team/"),
+        cache: None,
+    }
+}
+
+///
+/// Add image `image_name` with hash `1234`, build on base image with hash `abcd`.
+///
+fn create_image(dck_helper: &TestContainerHelper, image_name: &str) {
+    let mut labels = HashMap::new();
+    labels.insert(String::from("d-sh.hash"), String::from("1234"));
+    labels.insert(String::from("d-sh.base-id"), String::from("sha256:base"));
+    labels.insert(String::from("d-sh.base-hash"), String::from("abcd"));
+
+    dck_helper
+        .images
+        .borrow_mut()
+        .push(String::from(image_name));
+    dck_helper
+        .labels
+        .borrow_mut()
+        .insert(String::from(image_name), labels);
+}
+
+#[test]
+fn registry_image_names() {
+    let registry = create_registry();
+
+    assert_eq!(
+        get_registry_image_name(&registry, "run-atom:latest"),
+        "localhost:5000/team/run-atom:latest"
+    );
+    assert_eq!(
+        get_registry_cache_image_name(&registry, "run-atom:latest", "1234"),
+        "localhost:5000/team/run-atom:1234"
+    );
+    assert_eq!(
+        get_registry_cache_image_name(&registry, "run-atom", "1234"),
+        "localhost:5000/team/run-atom:1234"
+    );
+    assert_eq!(
+        get_registry_cache_image_name(&registry, "myhost:5000/run-atom", "1234"),
+        "localhost:5000/team/myhost:5000/run-atom:1234"
+    );
+}
+
+#[test]
+fn push_image_with_name_and_hash() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let registry = create_registry();
+
+    create_image(dck_helper, "run-atom:latest");
+
+    assert_eq!(push_image(dck_helper, &registry, "run-atom:latest"), Ok(()));
+
+    assert_eq!(
+        *dck_helper.registry_images.borrow(),
+        vec![
+            String::from("localhost:5000/team/run-atom:latest"),
+            String::from("localhost:5000/team/run-atom:1234"),
+        ]
+    );
+}
+
+#[test]
+fn push_image_not_found() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let registry = create_registry();
+
+    assert_eq!(
+        push_image(dck_helper, &registry, "run-atom:latest"),
+        Err(String::from("Image 'run-atom:latest' not found!"))
+    );
+}
+
+#[test]
+fn pull_image_and_tag_with_local_name() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let registry = create_registry();
+
+    assert_eq!(
+        pull_image(dck_helper, &registry, "run-atom:latest"),
+        Err(String::from(
+            "Unable to pull 'localhost:5000/team/run-atom:latest'! Not found: manifest for localhost:5000/team/run-atom:latest not found"
+        ))
+    );
+
+    dck_helper
+        .registry_images
+        .borrow_mut()
+        .push(String::from("localhost:5000/team/run-atom:latest"));
+
+    assert_eq!(pull_image(dck_helper, &registry, "run-atom:latest"), Ok(()));
+    assert!(dck_helper
+        .images
+        .borrow()
+        .contains(&String::from("run-atom:latest")));
+}
+
+#[test]
+fn pull_cache_image_only_if_same_hash_and_base() {
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let registry = create_registry();
+
+    // Image pushed with wrong tag
+    create_image(dck_helper, "localhost:5000/team/run-atom:5678");
+    dck_helper
+        .registry_images
+        .borrow_mut()
+        .push(String::from("localhost:5000/team/run-atom:5678"));
+
+    assert!(!pull_cache_image(
+        dck_helper,
+        &registry,
+        "run-atom:latest",
+        "1234",
+        Some("abcd")
+    ));
+    assert!(!pull_cache_image(
+        dck_helper,
+        &registry,
+        "run-atom:latest",
+        "5678",
+        Some("abcd")
+    ));
+
+    create_image(dck_helper, "localhost:5000/team/run-atom:1234");
+    dck_helper
+        .registry_images
+        .borrow_mut()
+        .push(String::from("localhost:5000/team/run-atom:1234"));
+
+    // Same hash but build on another base image, pulled image is removed
+    assert!(!pull_cache_image(
+        dck_helper,
+        &registry,
+        "run-atom:latest",
+        "1234",
+        Some("efgh")
+    ));
+    assert!(!dck_helper
+        .images
+        .borrow()
+        .contains(&String::from("run-atom:latest")));
+    assert!(!dck_helper
+        .images
+        .borrow()
+        .contains(&String::from("localhost:5000/team/run-atom:1234")));
+
+    // Hash of base image unknown, nothing is pulled
+    assert!(!pull_cache_image(
+        dck_helper,
+        &registry,
+        "run-atom:latest",
+        "1234",
+        None
+    ));
+    assert!(!dck_helper
+        .images
+        .borrow()
+        .contains(&String::from("localhost:5000/team/run-atom:1234")));
+
+    create_image(dck_helper, "localhost:5000/team/run-atom:1234");
+
+    assert!(pull_cache_image(
+        dck_helper,
+        &registry,
+        "run-atom:latest",
+        "1234",
+        Some("abcd")
+    ));
+    assert!(dck_helper
+        .images
+        .borrow()
+        .contains(&String::from("run-atom:latest")));
+}
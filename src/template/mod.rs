@@ -3,16 +3,40 @@
 ///
 /// Release under MIT License.
 ///
+use config::package_manager::PackageManager;
 use handlebars::*;
 
+#[cfg(test)]
+mod tests;
+
 handlebars_helper!(ends_width_helper: |text: str, pattern: str| text.ends_with(pattern));
 
+handlebars_helper!(pkg_install_helper: |name: str| match PackageManager::from_name(name) {
+    Some(pm) => pm.install_command(),
+    None => "",
+});
+
+handlebars_helper!(pkg_install_file_helper: |name: str| match PackageManager::from_name(name) {
+    Some(pm) => pm.install_file_command(),
+    None => "",
+});
+
+handlebars_helper!(pkg_clean_helper: |name: str| match PackageManager::from_name(name) {
+    Some(pm) => pm.clean_command(),
+    None => "",
+});
+
 pub struct Template;
 
 impl Template {
     pub fn new() -> Handlebars {
         let mut handlebars = Handlebars::new();
+        // We don't generate HTML, `&&` must stay `&&`
+        handlebars.register_escape_fn(no_escape);
         handlebars.register_helper("ends_width", Box::new(ends_width_helper));
+        handlebars.register_helper("pkg_install", Box::new(pkg_install_helper));
+        handlebars.register_helper("pkg_install_file", Box::new(pkg_install_file_helper));
+        handlebars.register_helper("pkg_clean", Box::new(pkg_clean_helper));
 
         handlebars
     }
@@ -0,0 +1,55 @@
+///
+/// Module to tests module template.
+///
+/// Release under MIT License.
+///
+use super::Template;
+
+#[test]
+fn render_package_manager_commands() {
+    let handlebars = Template::new();
+
+    let data = json!({
+        "package_manager": "apt",
+        "dependencies": "git curl"
+    });
+
+    assert_eq!(
+        handlebars
+            .render_template(
+                "RUN {{pkg_install package_manager}} {{dependencies}} && {{pkg_clean package_manager}}",
+                &data
+            )
+            .unwrap(),
+        "RUN apt-get update && apt-get install -y git curl && apt-get clean && rm -rf /var/lib/apt/lists/*"
+    );
+
+    let data = json!({
+        "package_manager": "apk",
+        "application_filename": "app.apk"
+    });
+
+    assert_eq!(
+        handlebars
+            .render_template(
+                "RUN {{pkg_install_file package_manager}} /tmp/{{application_filename}} && {{pkg_clean package_manager}}",
+                &data
+            )
+            .unwrap(),
+        "RUN apk add --no-cache --allow-untrusted /tmp/app.apk && rm -rf /var/cache/apk/*"
+    );
+}
+
+#[test]
+fn render_unknown_package_manager() {
+    let handlebars = Template::new();
+
+    let data = json!({ "package_manager": "pacman" });
+
+    assert_eq!(
+        handlebars
+            .render_template("RUN {{pkg_install package_manager}}", &data)
+            .unwrap(),
+        "RUN "
+    );
+}
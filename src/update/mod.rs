@@ -0,0 +1,184 @@
+///
+/// Module to find last version of application.
+///
+/// Release under MIT License.
+///
+use config::{
+    get_application_base_name, get_config_base, Config, ConfigApplication, ConfigUpdateCheck,
+};
+use docker::ContainerHelper;
+use download::DownloadHelper;
+use regex::Regex;
+use serde_json::Value;
+use std::cmp::Ordering;
+use version::{compare_version, find_version, get_version_regex};
+
+#[cfg(test)]
+mod tests;
+
+///
+/// Return content of url.
+///
+fn get_content(dl_helper: &DownloadHelper, url: &str) -> Result<String, String> {
+    match dl_helper.get_content(url) {
+        Some(r) => Ok(r),
+        None => Err(format!("Unable to read '{}'!", url)),
+    }
+}
+
+///
+/// Read last version in releases JSON endpoint (like GitHub API).
+///
+/// Endpoint can return one release or a list of releases. Draft and pre-release are ignored.
+///
+fn get_github_version(
+    dl_helper: &DownloadHelper,
+    config_application: &ConfigApplication,
+    url: &str,
+) -> Result<String, String> {
+    let content = get_content(dl_helper, url)?;
+
+    let releases: Value = match serde_json::from_str(&content) {
+        Ok(r) => r,
+        Err(err) => return Err(format!("Bad JSON format of '{}'! {}", url, err)),
+    };
+
+    let release = match releases.as_array() {
+        Some(list) => list.iter().find(|r| {
+            !r["draft"].as_bool().unwrap_or(false) && !r["prerelease"].as_bool().unwrap_or(false)
+        }),
+        None => Some(&releases),
+    };
+
+    let tag_name = match release.and_then(|r| r["tag_name"].as_str()) {
+        Some(t) => t,
+        None => return Err(format!("No release found in '{}'!", url)),
+    };
+
+    match get_version_regex(config_application) {
+        Ok(regex) => match find_version(&regex, tag_name) {
+            Some(v) => Ok(v),
+            None => Ok(String::from(tag_name.trim_start_matches('v'))),
+        },
+        Err(err) => Err(format!("Bad version regex! {}", err)),
+    }
+}
+
+///
+/// Search all versions in web page and return the greatest.
+///
+fn get_html_version(
+    dl_helper: &DownloadHelper,
+    config_application: &ConfigApplication,
+    url: &str,
+    regex: &Option<String>,
+) -> Result<String, String> {
+    let content = get_content(dl_helper, url)?;
+
+    let regex = match regex {
+        Some(r) => Regex::new(r),
+        None => get_version_regex(config_application),
+    };
+
+    let regex = match regex {
+        Ok(r) => r,
+        Err(err) => return Err(format!("Bad version regex! {}", err)),
+    };
+
+    let version = regex
+        .captures_iter(&content)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(0)))
+        .map(|m| String::from(m.as_str()))
+        .max_by(|a, b| match compare_version(a, b) {
+            // Same numbers, keep first found
+            Ordering::Equal => Ordering::Greater,
+            o => o,
+        });
+
+    match version {
+        Some(v) => Ok(v),
+        None => Err(format!("No version found in '{}'!", url)),
+    }
+}
+
+///
+/// Return candidate version of package in base image of application.
+///
+fn get_apt_version(
+    dck_helper: &ContainerHelper,
+    config: &Config,
+    config_application: &ConfigApplication,
+    package: &str,
+) -> Result<String, String> {
+    let base_name = get_application_base_name(config_application);
+
+    let base = match get_config_base(config, base_name) {
+        Some(r) => r,
+        None => {
+            return Err(format!(
+                "Base image '{}' not found in config file!",
+                base_name
+            ))
+        }
+    };
+
+    let run_options = vec![
+        String::from("--rm"),
+        String::from("--entrypoint"),
+        String::from("/bin/sh"),
+    ];
+
+    let cmd_options = vec![format!(
+        "apt-get update > /dev/null 2>&1; apt-cache policy {}",
+        package
+    )];
+
+    let output = match dck_helper.run_container_output(
+        &base.tag,
+        Some(&run_options),
+        Some("-c"),
+        Some(&cmd_options),
+    ) {
+        Ok(r) => r,
+        Err(err) => return Err(format!("Unable to run base image '{}'! {}", base.tag, err)),
+    };
+
+    // Candidate: 1.2.3-1
+    let candidate = output
+        .lines()
+        .map(|l| l.trim())
+        .find(|l| l.starts_with("Candidate:"))
+        .map(|l| l["Candidate:".len()..].trim());
+
+    match candidate {
+        Some(c) if c != "(none)" => Ok(String::from(c)),
+        _ => Err(format!("Package '{}' not found in base image!", package)),
+    }
+}
+
+///
+/// Return last version of application.
+///
+/// returning None if application have no `update_check`.
+///
+pub fn get_latest_version(
+    dl_helper: &DownloadHelper,
+    dck_helper: &ContainerHelper,
+    config: &Config,
+    config_application: &ConfigApplication,
+) -> Result<Option<String>, String> {
+    let version = match config_application.update_check {
+        Some(ConfigUpdateCheck::Github { ref url }) => {
+            get_github_version(dl_helper, config_application, url)?
+        }
+        Some(ConfigUpdateCheck::Html { ref url, ref regex }) => {
+            get_html_version(dl_helper, config_application, url, regex)?
+        }
+        Some(ConfigUpdateCheck::Apt { ref package }) => {
+            get_apt_version(dck_helper, config, config_application, package)?
+        }
+        None => return Ok(None),
+    };
+
+    Ok(Some(version))
+}
@@ -0,0 +1,139 @@
+///
+/// Module to tests module update.
+///
+/// Release under MIT License.
+///
+use super::get_latest_version;
+use config::get_config_application;
+use config::tests::create_config;
+use docker::tests::TestContainerHelper;
+use download::tests::{TestDownloadHelper, TestHttpServer};
+use download::DefaultDownloadHelper;
+use io::tests::TestInputOutputHelper;
+
+#[test]
+fn latest_version_none_without_update_check() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\""));
+
+    let config_application = get_config_application(io_helper, "app/atom.yml").unwrap();
+
+    assert_eq!(
+        get_latest_version(dl_helper, dck_helper, &config, &config_application),
+        Ok(None)
+    );
+}
+
+#[test]
+fn latest_version_from_github_releases() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\nupdate_check:\n  type: github\n  url: \"https://api.github.com/repos/atom/atom/releases\""));
+
+    // First release is a pre-release
+    dl_helper.contents.borrow_mut().insert(
+        String::from("https://api.github.com/repos/atom/atom/releases"),
+        String::from("[{\"tag_name\": \"v1.35.0-beta0\", \"draft\": false, \"prerelease\": true}, {\"tag_name\": \"v1.34.0\", \"draft\": false, \"prerelease\": false}]"),
+    );
+
+    let config_application = get_config_application(io_helper, "app/atom.yml").unwrap();
+
+    assert_eq!(
+        get_latest_version(dl_helper, dck_helper, &config, &config_application),
+        Ok(Some(String::from("1.34.0")))
+    );
+}
+
+#[test]
+fn latest_version_from_html_page() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\nupdate_check:\n  type: html\n  url: \"https://filezilla-project.org/download.php\"\n  regex: \"FileZilla_([0-9.]+)_x86_64\""));
+
+    dl_helper.contents.borrow_mut().insert(
+        String::from("https://filezilla-project.org/download.php"),
+        String::from("<a href=\"FileZilla_3.9.0_x86_64.tar.bz2\">old</a><a href=\"FileZilla_3.40.0_x86_64.tar.bz2\">new</a>"),
+    );
+
+    let config_application = get_config_application(io_helper, "app/filezilla.yml").unwrap();
+
+    assert_eq!(
+        get_latest_version(dl_helper, dck_helper, &config, &config_application),
+        Ok(Some(String::from("3.40.0")))
+    );
+}
+
+#[test]
+fn latest_version_from_apt_candidate() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/vlc.yml"), String::from("---\nimage_name: \"run-vlc:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\nupdate_check:\n  type: apt\n  package: vlc"));
+
+    dck_helper.images.borrow_mut().push(String::from("tutu"));
+    dck_helper.run_outputs.borrow_mut().insert(
+        String::from("tutu"),
+        String::from("vlc:\n  Installed: (none)\n  Candidate: 3.0.8-0+deb10u1\n  Version table:\n"),
+    );
+
+    let config_application = get_config_application(io_helper, "app/vlc.yml").unwrap();
+
+    assert_eq!(
+        get_latest_version(dl_helper, dck_helper, &config, &config_application),
+        Ok(Some(String::from("3.0.8-0+deb10u1")))
+    );
+}
+
+#[test]
+fn latest_version_error_if_url_cannot_be_read() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\nupdate_check:\n  type: github\n  url: \"https://titi.org/releases\""));
+
+    let config_application = get_config_application(io_helper, "app/atom.yml").unwrap();
+
+    assert_eq!(
+        get_latest_version(dl_helper, dck_helper, &config, &config_application),
+        Err(String::from("Unable to read 'https://titi.org/releases'!"))
+    );
+}
+
+#[test]
+fn latest_version_from_local_github_server() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dck_helper: &TestContainerHelper = &TestContainerHelper::new();
+    let dl_helper = &DefaultDownloadHelper;
+    let config = create_config();
+    let server = TestHttpServer::start(vec![(
+        "200 OK",
+        "[{\"tag_name\": \"v1.35.0-beta0\", \"draft\": false, \"prerelease\": true}, {\"tag_name\": \"v1.34.0\", \"draft\": false, \"prerelease\": false}]",
+    )]);
+
+    io_helper.files.borrow_mut().insert(
+        String::from("app/atom.yml"),
+        format!("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"\"\nurl: \"\"\nupdate_check:\n  type: github\n  url: \"{}/releases\"", server.url),
+    );
+
+    let config_application = get_config_application(io_helper, "app/atom.yml").unwrap();
+
+    assert_eq!(
+        get_latest_version(dl_helper, dck_helper, &config, &config_application),
+        Ok(Some(String::from("1.34.0")))
+    );
+    assert_eq!(server.request_lines(), vec!["GET /releases HTTP/1.1"]);
+}
@@ -0,0 +1,103 @@
+///
+/// Module to detect version of application.
+///
+/// Release under MIT License.
+///
+use config::{get_filename, Config, ConfigApplication};
+use download::DownloadHelper;
+use io::{convert_path, InputOutputHelper};
+use regex::{Error, Regex};
+use std::cmp::Ordering;
+
+#[cfg(test)]
+mod tests;
+
+/// Label of docker image where version of application is stored.
+pub const VERSION_LABEL: &str = "d-sh.version";
+
+/// Default regex to find version in filename or url.
+pub const DEFAULT_VERSION_REGEX: &str = r"(\d+(?:\.\d+)+)";
+
+///
+/// Search version in text.
+///
+/// If regex have a group, the first group is the version.
+///
+pub fn find_version(regex: &Regex, text: &str) -> Option<String> {
+    match regex.captures(text) {
+        Some(caps) => caps
+            .get(1)
+            .or_else(|| caps.get(0))
+            .map(|m| String::from(m.as_str())),
+        None => None,
+    }
+}
+
+///
+/// Return regex to find version of application, `version_regex` or default regex.
+///
+pub fn get_version_regex(config_application: &ConfigApplication) -> Result<Regex, Error> {
+    match config_application.version_regex {
+        Some(ref r) => Regex::new(r),
+        None => Regex::new(DEFAULT_VERSION_REGEX),
+    }
+}
+
+///
+/// Compare two versions, number by number (`1.10.0` > `1.9.2`).
+///
+pub fn compare_version(a: &str, b: &str) -> Ordering {
+    let numbers = |v: &str| -> Vec<u64> {
+        v.split(|c: char| !c.is_ascii_digit())
+            .filter(|n| !n.is_empty())
+            .map(|n| n.parse::<u64>().unwrap_or(0))
+            .collect()
+    };
+
+    numbers(a).cmp(&numbers(b))
+}
+
+///
+/// Return version of application downloaded.
+///
+/// Version is read in control file of `.deb` package, otherwise search in
+/// download filename then in url with `version_regex` of application. If not found,
+/// `version` of application is used.
+///
+/// returning None if application not downloaded or version not found.
+///
+pub fn get_downloaded_version(
+    io_helper: &InputOutputHelper,
+    dl_helper: &DownloadHelper,
+    config: &Config,
+    config_application: &ConfigApplication,
+) -> Option<String> {
+    // Application from distribution repository, nothing downloaded
+    let url = match config_application.url {
+        Some(ref u) => u,
+        None => return None,
+    };
+
+    let app_dwn_filename = convert_path(&get_filename(
+        &config.download_dir,
+        &config_application.download_filename,
+        None,
+    ));
+
+    if !io_helper.file_exits(&app_dwn_filename) {
+        return None;
+    }
+
+    if config_application.download_filename.ends_with(".deb") {
+        if let Some(version) = dl_helper.package_version(&app_dwn_filename) {
+            return Some(version);
+        }
+    }
+
+    match get_version_regex(config_application) {
+        Ok(r) => find_version(&r, &config_application.download_filename)
+            .or_else(|| find_version(&r, url))
+            .or_else(|| config_application.version.clone()),
+        Err(_) => config_application.version.clone(),
+    }
+}
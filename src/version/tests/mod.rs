@@ -0,0 +1,102 @@
+///
+/// Module to tests module version.
+///
+/// Release under MIT License.
+///
+use super::get_downloaded_version;
+use config::get_config_application;
+use config::tests::create_config;
+use download::tests::TestDownloadHelper;
+use io::tests::TestInputOutputHelper;
+
+#[test]
+fn version_from_deb_package() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom-1.0.0.deb\"\nurl: \"toto\""));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom-1.0.0.deb"), String::from("toto"));
+    dl_helper
+        .package_versions
+        .borrow_mut()
+        .insert(String::from("dwn/atom-1.0.0.deb"), String::from("1.34.0"));
+
+    let config_application = get_config_application(io_helper, "app/atom.yml").unwrap();
+
+    assert_eq!(
+        get_downloaded_version(io_helper, dl_helper, &config, &config_application),
+        Some(String::from("1.34.0"))
+    );
+}
+
+#[test]
+fn version_from_filename_then_url() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom-2.1.tar.gz\"\nurl: \"https://atom.io/v3.0.0/atom.tar.gz\""));
+    io_helper.files.borrow_mut().insert(String::from("app/filezilla.yml"), String::from("---\nimage_name: \"run-filezilla:latest\"\ncmd_line: \"\"\ndownload_filename: \"filezilla.tar.bz2\"\nurl: \"https://filezilla.org/v3.39.0/filezilla.tar.bz2\""));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom-2.1.tar.gz"), String::from("toto"));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/filezilla.tar.bz2"), String::from("toto"));
+
+    let config_application = get_config_application(io_helper, "app/atom.yml").unwrap();
+
+    assert_eq!(
+        get_downloaded_version(io_helper, dl_helper, &config, &config_application),
+        Some(String::from("2.1"))
+    );
+
+    let config_application = get_config_application(io_helper, "app/filezilla.yml").unwrap();
+
+    assert_eq!(
+        get_downloaded_version(io_helper, dl_helper, &config, &config_application),
+        Some(String::from("3.39.0"))
+    );
+}
+
+#[test]
+fn version_with_custom_regex() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom.tar.gz\"\nurl: \"https://atom.io/download?release=r42\"\nversion_regex: \"release=r(\\\\d+)\""));
+    io_helper
+        .files
+        .borrow_mut()
+        .insert(String::from("dwn/atom.tar.gz"), String::from("toto"));
+
+    let config_application = get_config_application(io_helper, "app/atom.yml").unwrap();
+
+    assert_eq!(
+        get_downloaded_version(io_helper, dl_helper, &config, &config_application),
+        Some(String::from("42"))
+    );
+}
+
+#[test]
+fn version_not_found_if_not_downloaded() {
+    let io_helper: &TestInputOutputHelper = &TestInputOutputHelper::new();
+    let dl_helper: &TestDownloadHelper = &TestDownloadHelper::new(io_helper);
+    let config = create_config();
+
+    io_helper.files.borrow_mut().insert(String::from("app/atom.yml"), String::from("---\nimage_name: \"run-atom:latest\"\ncmd_line: \"\"\ndownload_filename: \"atom-1.0.0.tar.gz\"\nurl: \"toto\""));
+
+    let config_application = get_config_application(io_helper, "app/atom.yml").unwrap();
+
+    assert_eq!(
+        get_downloaded_version(io_helper, dl_helper, &config, &config_application),
+        None
+    );
+}